@@ -7,9 +7,11 @@ use rmatrix::matrices::blocked_matrix::BlockedMatrix;
 use rmatrix::matrices::multithread_matrix::MultithreadMatrix;
 use rmatrix::matrices::ndarray_matrix::NdarrayMatrix;
 use rmatrix::matrices::one_d_vec_matrix::OneDVecMatrix;
+use rmatrix::matrices::packed_matrix::PackedMatrix;
 use rmatrix::matrices::transposed_view_matrix::TransposedViewMatrix;
+use rmatrix::tuning::{self, autotune, default_path};
 
-use std::env;
+use std::{env, io};
 
 fn main() {
     println!("Starting benchmark...");
@@ -17,6 +19,20 @@ fn main() {
     let args: Vec<String> = env::args().collect();
     let run_all = args.contains(&String::from("all"));
 
+    if args.contains(&String::from("autotune")) {
+        println!("Autotuning...");
+        let config = autotune().unwrap();
+        println!("{:?} saved to {}", config, default_path().display());
+        return;
+    }
+
+    let tuning_path = default_path();
+    match tuning::init(&tuning_path) {
+        Ok(config) => println!("{:?} loaded from {}", config, tuning_path.display()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => println!("Using the default tuning."),
+        Err(e) => panic!("Cannot load {}: {}", tuning_path.display(), e),
+    }
+
     println!("Loading matrices...");
     let matrices1 = load_matrices("./benches/matrices1.json");
     let matrices2 = load_matrices("./benches/matrices2.json");
@@ -108,5 +124,17 @@ fn main() {
         println!("multithread matrix benchmark completed.");
     }
 
+    if !args.contains(&String::from("exclude_packed_matrix"))
+        && (run_all || args.contains(&String::from("packed_matrix")))
+    {
+        println!("Benchmarking packed matrix...");
+        let packed_matrix_results = benchmark_matrix::<PackedMatrix>(&matrices1, &matrices2);
+        save_benchmark_results(
+            "./benches/benchmark_results/packed_matrix_results.json",
+            &packed_matrix_results,
+        );
+        println!("packed matrix benchmark completed.");
+    }
+
     println!("Benchmark completed.");
 }
@@ -12,8 +12,8 @@ pub fn load_matrices(filename: &str) -> Vec<Vec<Vec<f64>>> {
 }
 
 pub fn benchmark_matrix<M: Matrix>(
-    matrix_vectors1: &Vec<Vec<Vec<f64>>>,
-    matrix_vectors2: &Vec<Vec<Vec<f64>>>,
+    matrix_vectors1: &[Vec<Vec<f64>>],
+    matrix_vectors2: &[Vec<Vec<f64>>],
 ) -> BenchmarkResults {
     let matrices1 = convert_to_matrices::<M>(matrix_vectors1);
     let matrices2 = convert_to_matrices::<M>(matrix_vectors2);
@@ -31,14 +31,14 @@ pub fn benchmark_matrix<M: Matrix>(
     )
 }
 
-fn convert_to_matrices<M: Matrix>(matrix_vectors: &Vec<Vec<Vec<f64>>>) -> Vec<M> {
+fn convert_to_matrices<M: Matrix>(matrix_vectors: &[Vec<Vec<f64>>]) -> Vec<M> {
     matrix_vectors
         .iter()
         .map(|matrix| M::new(matrix.clone()))
         .collect()
 }
 
-fn benchmark_matrix_addition<M: Matrix>(matrices1: &Vec<M>, matrices2: &Vec<M>) -> BenchmarkData {
+fn benchmark_matrix_addition<M: Matrix>(matrices1: &[M], matrices2: &[M]) -> BenchmarkData {
     let mut sizes = Vec::new();
     let mut times = Vec::new();
 
@@ -57,10 +57,7 @@ fn benchmark_matrix_addition<M: Matrix>(matrices1: &Vec<M>, matrices2: &Vec<M>)
     BenchmarkData::new(sizes, times)
 }
 
-fn benchmark_matrix_subtraction<M: Matrix>(
-    matrices1: &Vec<M>,
-    matrices2: &Vec<M>,
-) -> BenchmarkData {
+fn benchmark_matrix_subtraction<M: Matrix>(matrices1: &[M], matrices2: &[M]) -> BenchmarkData {
     let mut sizes = Vec::new();
     let mut times = Vec::new();
 
@@ -79,10 +76,7 @@ fn benchmark_matrix_subtraction<M: Matrix>(
     BenchmarkData::new(sizes, times)
 }
 
-fn benchmark_matrix_multiplication<M: Matrix>(
-    matrices1: &Vec<M>,
-    matrices2: &Vec<M>,
-) -> BenchmarkData {
+fn benchmark_matrix_multiplication<M: Matrix>(matrices1: &[M], matrices2: &[M]) -> BenchmarkData {
     let mut sizes = Vec::new();
     let mut times = Vec::new();
 
@@ -101,16 +95,16 @@ fn benchmark_matrix_multiplication<M: Matrix>(
     BenchmarkData::new(sizes, times)
 }
 
-fn benchmark_scalar_multiplication<M: Matrix>(matrices1: &Vec<M>, scalar: f64) -> BenchmarkData {
+fn benchmark_scalar_multiplication<M: Matrix>(matrices1: &[M], scalar: f64) -> BenchmarkData {
     let mut sizes = Vec::new();
     let mut times = Vec::new();
 
-    for i in 0..matrices1.len() {
-        sizes.push(matrices1[i].num_rows());
+    for matrix in matrices1 {
+        sizes.push(matrix.num_rows());
 
         let time = benchmark_function(
             || {
-                let _ = matrices1[i].scalar_multiplication(scalar);
+                let _ = matrix.scalar_multiplication(scalar);
             },
             10,
         );
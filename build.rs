@@ -1,5 +1,35 @@
+use std::env;
+
+// Selects the CBLAS provider for `BlasMatrix` from the enabled features.
+//
+// - `blas-accelerate`: Apple's Accelerate framework (macOS only).
+// - `blas-openblas`: a system CBLAS. Links `openblas` by default; set `RMATRIX_CBLAS_LIB`
+//   (e.g. to `cblas`) to use another library and `RMATRIX_CBLAS_DIR` to add a search path.
+//   The library must also export the LAPACK routines (`dgetrf_` etc.), as OpenBLAS does.
+//
+// With neither, `BlasMatrix` uses the pure-Rust fallback in `src/blas/fallback.rs`.
 fn main() {
-    if !cfg!(target_os = "macos") {
-        panic!("Apple Accelerate framework is only available on macOS");
+    println!("cargo::rustc-check-cfg=cfg(blas_provider)");
+    println!("cargo::rerun-if-env-changed=RMATRIX_CBLAS_LIB");
+    println!("cargo::rerun-if-env-changed=RMATRIX_CBLAS_DIR");
+
+    let target_os = env::var("CARGO_CFG_TARGET_OS").unwrap_or_default();
+    let accelerate = env::var_os("CARGO_FEATURE_BLAS_ACCELERATE").is_some();
+    let openblas = env::var_os("CARGO_FEATURE_BLAS_OPENBLAS").is_some();
+
+    if accelerate && target_os == "macos" {
+        println!("cargo::rustc-link-lib=framework=Accelerate");
+        println!("cargo::rustc-cfg=blas_provider");
+    } else if openblas {
+        if let Ok(dir) = env::var("RMATRIX_CBLAS_DIR") {
+            println!("cargo::rustc-link-search=native={dir}");
+        }
+        let lib = env::var("RMATRIX_CBLAS_LIB").unwrap_or_else(|_| String::from("openblas"));
+        println!("cargo::rustc-link-lib={lib}");
+        println!("cargo::rustc-cfg=blas_provider");
+    } else if accelerate {
+        println!(
+            "cargo::warning=Accelerate is only available on macOS, using the pure-Rust BLAS fallback"
+        );
     }
 }
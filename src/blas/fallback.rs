@@ -0,0 +1,872 @@
+// Pure-Rust stand-ins for the CBLAS and LAPACK routines, used when no provider is linked.
+// They follow the reference CBLAS semantics so callers don't need to care which one they get.
+
+use super::{CBlasDiag, CBlasLayout, CBlasSide, CBlasTranspose, CBlasUplo};
+use crate::level1::{self, Givens};
+use crate::{Element, FloatElement};
+use num_complex::Complex;
+use std::borrow::Cow;
+use std::ffi::{c_char, c_int};
+use std::{ptr, slice};
+
+/// Computes `c = alpha * op(a) * op(b) + beta * c` for any element type.
+#[allow(clippy::too_many_arguments)]
+unsafe fn gemm<T: Element>(
+    layout: CBlasLayout,
+    transa: CBlasTranspose,
+    transb: CBlasTranspose,
+    m: i32,
+    n: i32,
+    k: i32,
+    alpha: T,
+    a: *const T,
+    lda: i32,
+    b: *const T,
+    ldb: i32,
+    beta: T,
+    c: *mut T,
+    ldc: i32,
+) {
+    if let CBlasLayout::CblasColMajor = layout {
+        // A column-major C is a row-major C^T = op(B)^T * op(A)^T.
+        unsafe {
+            gemm(
+                CBlasLayout::CblasRowMajor,
+                transb,
+                transa,
+                n,
+                m,
+                k,
+                alpha,
+                b,
+                ldb,
+                a,
+                lda,
+                beta,
+                c,
+                ldc,
+            );
+        }
+        return;
+    }
+
+    let (m, n, k) = (m as usize, n as usize, k as usize);
+    let (lda, ldb, ldc) = (lda as usize, ldb as usize, ldc as usize);
+    let a_transposed = !matches!(transa, CBlasTranspose::CblasNoTrans);
+    let b_transposed = !matches!(transb, CBlasTranspose::CblasNoTrans);
+    let a_conjugated = matches!(transa, CBlasTranspose::CblasConjTrans);
+    let b_conjugated = matches!(transb, CBlasTranspose::CblasConjTrans);
+
+    for i in 0..m {
+        let c_row = unsafe { slice::from_raw_parts_mut(c.add(i * ldc), n) };
+
+        if beta == T::zero() {
+            c_row.fill(T::zero());
+        } else if beta != T::one() {
+            c_row.iter_mut().for_each(|x| *x *= beta);
+        }
+
+        if alpha == T::zero() {
+            continue;
+        }
+
+        for p in 0..k {
+            let mut a_ip = unsafe {
+                if a_transposed {
+                    *a.add(p * lda + i)
+                } else {
+                    *a.add(i * lda + p)
+                }
+            };
+            if a_conjugated {
+                a_ip = a_ip.conj();
+            }
+            let a_ip = a_ip * alpha;
+
+            if b_transposed {
+                for (j, x) in c_row.iter_mut().enumerate() {
+                    let b_jp = unsafe { *b.add(j * ldb + p) };
+                    *x += a_ip * if b_conjugated { b_jp.conj() } else { b_jp };
+                }
+            } else {
+                let b_row = unsafe { slice::from_raw_parts(b.add(p * ldb), n) };
+                for (x, b_pj) in c_row.iter_mut().zip(b_row) {
+                    *x += a_ip * *b_pj;
+                }
+            }
+        }
+    }
+}
+
+macro_rules! cblas_gemm {
+    ($name:ident, $t:ty) => {
+        /// Computes `c = alpha * op(a) * op(b) + beta * c`.
+        ///
+        /// # Safety
+        ///
+        /// The pointers must be valid for the matrix sizes described by `m`, `n`, `k`
+        /// and the leading dimensions, exactly as required by the CBLAS routine of the same name.
+        #[allow(clippy::too_many_arguments)]
+        pub unsafe fn $name(
+            layout: CBlasLayout,
+            transa: CBlasTranspose,
+            transb: CBlasTranspose,
+            m: i32,
+            n: i32,
+            k: i32,
+            alpha: $t,
+            a: *const $t,
+            lda: i32,
+            b: *const $t,
+            ldb: i32,
+            beta: $t,
+            c: *mut $t,
+            ldc: i32,
+        ) {
+            unsafe {
+                gemm(
+                    layout, transa, transb, m, n, k, alpha, a, lda, b, ldb, beta, c, ldc,
+                );
+            }
+        }
+    };
+}
+
+cblas_gemm!(cblas_sgemm, f32);
+cblas_gemm!(cblas_dgemm, f64);
+
+// The complex routines take `alpha` and `beta` by pointer.
+macro_rules! cblas_complex_gemm {
+    ($name:ident, $t:ty) => {
+        /// Computes `c = alpha * op(a) * op(b) + beta * c`.
+        ///
+        /// # Safety
+        ///
+        /// The pointers must be valid for the matrix sizes described by `m`, `n`, `k`
+        /// and the leading dimensions, exactly as required by the CBLAS routine of the same name.
+        #[allow(clippy::too_many_arguments)]
+        pub unsafe fn $name(
+            layout: CBlasLayout,
+            transa: CBlasTranspose,
+            transb: CBlasTranspose,
+            m: i32,
+            n: i32,
+            k: i32,
+            alpha: *const Complex<$t>,
+            a: *const Complex<$t>,
+            lda: i32,
+            b: *const Complex<$t>,
+            ldb: i32,
+            beta: *const Complex<$t>,
+            c: *mut Complex<$t>,
+            ldc: i32,
+        ) {
+            unsafe {
+                gemm(
+                    layout, transa, transb, m, n, k, *alpha, a, lda, b, ldb, *beta, c, ldc,
+                );
+            }
+        }
+    };
+}
+
+cblas_complex_gemm!(cblas_cgemm, f32);
+cblas_complex_gemm!(cblas_zgemm, f64);
+
+/// Offset of the `i`-th of `len` entries with increment `inc`. Negative increments walk the vector
+/// backwards, as in the reference BLAS.
+fn offset(i: usize, len: usize, inc: i32) -> usize {
+    if inc >= 0 {
+        i * inc as usize
+    } else {
+        (len - 1 - i) * inc.unsigned_abs() as usize
+    }
+}
+
+/// Computes `y = alpha * op(a) * x + beta * y` for any element type.
+#[allow(clippy::too_many_arguments)]
+unsafe fn gemv<T: Element>(
+    layout: CBlasLayout,
+    trans: CBlasTranspose,
+    m: i32,
+    n: i32,
+    alpha: T,
+    a: *const T,
+    lda: i32,
+    x: *const T,
+    incx: i32,
+    beta: T,
+    y: *mut T,
+    incy: i32,
+) {
+    // A column-major A is a row-major A^T, so the transpose flag flips
+    let transposed = !matches!(trans, CBlasTranspose::CblasNoTrans)
+        != matches!(layout, CBlasLayout::CblasColMajor);
+    let conjugated = matches!(trans, CBlasTranspose::CblasConjTrans);
+    let (rows, cols) = match layout {
+        CBlasLayout::CblasRowMajor => (m as usize, n as usize),
+        CBlasLayout::CblasColMajor => (n as usize, m as usize),
+    };
+    let lda = lda as usize;
+    let (x_len, y_len) = if transposed {
+        (rows, cols)
+    } else {
+        (cols, rows)
+    };
+
+    let x_at = |i: usize| unsafe { *x.add(offset(i, x_len, incx)) };
+    let a_at = |i: usize, j: usize| {
+        let a_ij = unsafe { *a.add(i * lda + j) };
+        if conjugated { a_ij.conj() } else { a_ij }
+    };
+
+    for k in 0..y_len {
+        let y_k = unsafe { &mut *y.add(offset(k, y_len, incy)) };
+        let sum = if transposed {
+            (0..rows).fold(T::zero(), |sum, i| sum + a_at(i, k) * x_at(i))
+        } else {
+            (0..cols).fold(T::zero(), |sum, j| sum + a_at(k, j) * x_at(j))
+        };
+        *y_k = if beta == T::zero() {
+            alpha * sum
+        } else {
+            alpha * sum + beta * *y_k
+        };
+    }
+}
+
+macro_rules! cblas_gemv {
+    ($name:ident, $t:ty) => {
+        /// Computes `y = alpha * op(a) * x + beta * y`.
+        ///
+        /// # Safety
+        ///
+        /// The pointers must be valid for the sizes described by `m`, `n`, the leading dimension
+        /// and the increments, exactly as required by the CBLAS routine of the same name.
+        #[allow(clippy::too_many_arguments)]
+        pub unsafe fn $name(
+            layout: CBlasLayout,
+            trans: CBlasTranspose,
+            m: i32,
+            n: i32,
+            alpha: $t,
+            a: *const $t,
+            lda: i32,
+            x: *const $t,
+            incx: i32,
+            beta: $t,
+            y: *mut $t,
+            incy: i32,
+        ) {
+            unsafe { gemv(layout, trans, m, n, alpha, a, lda, x, incx, beta, y, incy) };
+        }
+    };
+}
+
+cblas_gemv!(cblas_sgemv, f32);
+cblas_gemv!(cblas_dgemv, f64);
+
+// The complex routines take `alpha` and `beta` by pointer.
+macro_rules! cblas_complex_gemv {
+    ($name:ident, $t:ty) => {
+        /// Computes `y = alpha * op(a) * x + beta * y`.
+        ///
+        /// # Safety
+        ///
+        /// The pointers must be valid for the sizes described by `m`, `n`, the leading dimension
+        /// and the increments, exactly as required by the CBLAS routine of the same name.
+        #[allow(clippy::too_many_arguments)]
+        pub unsafe fn $name(
+            layout: CBlasLayout,
+            trans: CBlasTranspose,
+            m: i32,
+            n: i32,
+            alpha: *const Complex<$t>,
+            a: *const Complex<$t>,
+            lda: i32,
+            x: *const Complex<$t>,
+            incx: i32,
+            beta: *const Complex<$t>,
+            y: *mut Complex<$t>,
+            incy: i32,
+        ) {
+            unsafe { gemv(layout, trans, m, n, *alpha, a, lda, x, incx, *beta, y, incy) };
+        }
+    };
+}
+
+cblas_complex_gemv!(cblas_cgemv, f32);
+cblas_complex_gemv!(cblas_zgemv, f64);
+
+/// Borrows the `n` entries of `x` with increment `inc`, copying them unless they are contiguous.
+unsafe fn strided<'a, T: Copy>(x: *const T, n: i32, inc: i32) -> Cow<'a, [T]> {
+    let n = n.max(0) as usize;
+    if n == 0 {
+        Cow::Borrowed(&[])
+    } else if inc == 1 {
+        Cow::Borrowed(unsafe { slice::from_raw_parts(x, n) })
+    } else {
+        Cow::Owned(
+            (0..n)
+                .map(|i| unsafe { *x.add(offset(i, n, inc)) })
+                .collect(),
+        )
+    }
+}
+
+/// Runs `f` on the `n` entries of `x` with increment `inc`, writing them back if they had to be
+/// copied.
+unsafe fn with_strided_mut<T: Copy>(x: *mut T, n: i32, inc: i32, f: impl FnOnce(&mut [T])) {
+    let n = n.max(0) as usize;
+    if n == 0 {
+        f(&mut [])
+    } else if inc == 1 {
+        f(unsafe { slice::from_raw_parts_mut(x, n) })
+    } else {
+        let mut values = unsafe { strided(x, n as i32, inc) }.into_owned();
+        f(&mut values);
+        for (i, value) in values.into_iter().enumerate() {
+            unsafe { *x.add(offset(i, n, inc)) = value };
+        }
+    }
+}
+
+macro_rules! cblas_level1 {
+    ($t:ty, $dot:ident, $axpy:ident, $nrm2:ident, $asum:ident, $iamax:ident, $scal:ident, $rotg:ident, $rot:ident) => {
+        /// Computes `x · y`.
+        ///
+        /// # Safety
+        ///
+        /// The pointers must be valid for `n` entries with the given increments, exactly as
+        /// required by the CBLAS routine of the same name.
+        pub unsafe fn $dot(n: i32, x: *const $t, incx: i32, y: *const $t, incy: i32) -> $t {
+            unsafe { level1::dot(&strided(x, n, incx), &strided(y, n, incy)) }
+        }
+
+        /// Computes `y += alpha * x`.
+        ///
+        /// # Safety
+        ///
+        /// The pointers must be valid for `n` entries with the given increments, exactly as
+        /// required by the CBLAS routine of the same name.
+        pub unsafe fn $axpy(n: i32, alpha: $t, x: *const $t, incx: i32, y: *mut $t, incy: i32) {
+            unsafe {
+                let x = strided(x, n, incx);
+                with_strided_mut(y, n, incy, |y| level1::axpy(alpha, &x, y));
+            }
+        }
+
+        /// The Euclidean norm of `x`, or zero if `incx` is not positive.
+        ///
+        /// # Safety
+        ///
+        /// `x` must be valid for `n` entries with increment `incx`, exactly as required by the
+        /// CBLAS routine of the same name.
+        pub unsafe fn $nrm2(n: i32, x: *const $t, incx: i32) -> $t {
+            if incx <= 0 {
+                return 0.0;
+            }
+            unsafe { level1::nrm2(&strided(x, n, incx)) }
+        }
+
+        /// The sum of the absolute values of `x`, or zero if `incx` is not positive.
+        ///
+        /// # Safety
+        ///
+        /// `x` must be valid for `n` entries with increment `incx`, exactly as required by the
+        /// CBLAS routine of the same name.
+        pub unsafe fn $asum(n: i32, x: *const $t, incx: i32) -> $t {
+            if incx <= 0 {
+                return 0.0;
+            }
+            unsafe { level1::asum(&strided(x, n, incx)) }
+        }
+
+        /// The 0-based index of the first entry of `x` with the largest absolute value, or zero if
+        /// `incx` is not positive.
+        ///
+        /// # Safety
+        ///
+        /// `x` must be valid for `n` entries with increment `incx`, exactly as required by the
+        /// CBLAS routine of the same name.
+        pub unsafe fn $iamax(n: i32, x: *const $t, incx: i32) -> c_int {
+            if incx <= 0 {
+                return 0;
+            }
+            unsafe { level1::iamax(&strided(x, n, incx)) as c_int }
+        }
+
+        /// Computes `x *= alpha`, doing nothing if `incx` is not positive.
+        ///
+        /// # Safety
+        ///
+        /// `x` must be valid for `n` entries with increment `incx`, exactly as required by the
+        /// CBLAS routine of the same name.
+        pub unsafe fn $scal(n: i32, alpha: $t, x: *mut $t, incx: i32) {
+            if incx <= 0 {
+                return;
+            }
+            unsafe { with_strided_mut(x, n, incx, |x| level1::scal(alpha, x)) };
+        }
+
+        /// Computes the rotation zeroing `b` in `[a; b]`, overwriting `a` with `r` and `b` with
+        /// the value `z` from which `c` and `s` can be recovered.
+        ///
+        /// # Safety
+        ///
+        /// The pointers must be valid for reads and writes of a single value.
+        pub unsafe fn $rotg(a: *mut $t, b: *mut $t, c: *mut $t, s: *mut $t) {
+            unsafe {
+                let (givens, r) = Givens::new(*a, *b);
+                *b = if (*a).abs() > (*b).abs() {
+                    givens.s
+                } else if givens.c != 0.0 {
+                    1.0 / givens.c
+                } else {
+                    1.0
+                };
+                *a = r;
+                *c = givens.c;
+                *s = givens.s;
+            }
+        }
+
+        /// Applies the rotation `[c s; -s c]` to every pair `(x_i, y_i)`.
+        ///
+        /// # Safety
+        ///
+        /// The pointers must be valid for `n` entries with the given increments, exactly as
+        /// required by the CBLAS routine of the same name.
+        pub unsafe fn $rot(n: i32, x: *mut $t, incx: i32, y: *mut $t, incy: i32, c: $t, s: $t) {
+            unsafe {
+                with_strided_mut(x, n, incx, |x| {
+                    with_strided_mut(y, n, incy, |y| level1::rot(x, y, Givens { c, s }))
+                });
+            }
+        }
+    };
+}
+
+cblas_level1!(
+    f32,
+    cblas_sdot,
+    cblas_saxpy,
+    cblas_snrm2,
+    cblas_sasum,
+    cblas_isamax,
+    cblas_sscal,
+    cblas_srotg,
+    cblas_srot
+);
+cblas_level1!(
+    f64,
+    cblas_ddot,
+    cblas_daxpy,
+    cblas_dnrm2,
+    cblas_dasum,
+    cblas_idamax,
+    cblas_dscal,
+    cblas_drotg,
+    cblas_drot
+);
+
+/// Solves `op(A) * X = alpha * B` or `X * op(A) = alpha * B` for any element type, overwriting
+/// `b` with `X`.
+#[allow(clippy::too_many_arguments)]
+unsafe fn trsm<T: Element>(
+    layout: CBlasLayout,
+    side: CBlasSide,
+    uplo: CBlasUplo,
+    transa: CBlasTranspose,
+    diag: CBlasDiag,
+    m: i32,
+    n: i32,
+    alpha: T,
+    a: *const T,
+    lda: i32,
+    b: *mut T,
+    ldb: i32,
+) {
+    if let CBlasLayout::CblasColMajor = layout {
+        // A column-major B is a row-major B^T, and transposing the equation swaps the side
+        // and, since a column-major A is a row-major A^T, the triangle.
+        let side = match side {
+            CBlasSide::CblasLeft => CBlasSide::CblasRight,
+            CBlasSide::CblasRight => CBlasSide::CblasLeft,
+        };
+        let uplo = match uplo {
+            CBlasUplo::CblasUpper => CBlasUplo::CblasLower,
+            CBlasUplo::CblasLower => CBlasUplo::CblasUpper,
+        };
+        unsafe {
+            trsm(
+                CBlasLayout::CblasRowMajor,
+                side,
+                uplo,
+                transa,
+                diag,
+                n,
+                m,
+                alpha,
+                a,
+                lda,
+                b,
+                ldb,
+            );
+        }
+        return;
+    }
+
+    let (m, n, lda, ldb) = (m as usize, n as usize, lda as usize, ldb as usize);
+    let transposed = !matches!(transa, CBlasTranspose::CblasNoTrans);
+    let conjugated = matches!(transa, CBlasTranspose::CblasConjTrans);
+    let unit = matches!(diag, CBlasDiag::CblasUnit);
+    // Whether op(A) is upper triangular
+    let upper = matches!(uplo, CBlasUplo::CblasUpper) != transposed;
+    let op_a = |i: usize, j: usize| {
+        let a_ij = unsafe {
+            if transposed {
+                *a.add(j * lda + i)
+            } else {
+                *a.add(i * lda + j)
+            }
+        };
+        if conjugated { a_ij.conj() } else { a_ij }
+    };
+
+    if alpha != T::one() {
+        for i in 0..m {
+            let row = unsafe { slice::from_raw_parts_mut(b.add(i * ldb), n) };
+            row.iter_mut().for_each(|x| *x *= alpha);
+        }
+    }
+
+    match side {
+        CBlasSide::CblasLeft => {
+            // Substitution over the rows of B, from the top if op(A) is lower triangular
+            let order = (0..m).map(|r| if upper { m - 1 - r } else { r });
+            for i in order {
+                let solved = if upper { i + 1..m } else { 0..i };
+                for k in solved {
+                    let a_ik = op_a(i, k);
+                    for j in 0..n {
+                        unsafe { *b.add(i * ldb + j) -= a_ik * *b.add(k * ldb + j) };
+                    }
+                }
+                if !unit {
+                    let a_ii = op_a(i, i);
+                    let row = unsafe { slice::from_raw_parts_mut(b.add(i * ldb), n) };
+                    row.iter_mut().for_each(|x| *x /= a_ii);
+                }
+            }
+        }
+        CBlasSide::CblasRight => {
+            // Each row x of X solves x * op(A) = b, from the left if op(A) is upper triangular
+            for r in 0..m {
+                let row = unsafe { slice::from_raw_parts_mut(b.add(r * ldb), n) };
+                let order = (0..n).map(|c| if upper { c } else { n - 1 - c });
+                for j in order {
+                    let solved = if upper { 0..j } else { j + 1..n };
+                    let mut x_j = row[j];
+                    for k in solved {
+                        x_j -= row[k] * op_a(k, j);
+                    }
+                    row[j] = if unit { x_j } else { x_j / op_a(j, j) };
+                }
+            }
+        }
+    }
+}
+
+macro_rules! cblas_trsm {
+    ($name:ident, $t:ty) => {
+        /// Solves `op(A) * X = alpha * B` or `X * op(A) = alpha * B`, overwriting `b` with `X`.
+        ///
+        /// # Safety
+        ///
+        /// The pointers must be valid for the matrix sizes described by `m`, `n` and the
+        /// leading dimensions, exactly as required by the CBLAS routine of the same name.
+        #[allow(clippy::too_many_arguments)]
+        pub unsafe fn $name(
+            layout: CBlasLayout,
+            side: CBlasSide,
+            uplo: CBlasUplo,
+            transa: CBlasTranspose,
+            diag: CBlasDiag,
+            m: i32,
+            n: i32,
+            alpha: $t,
+            a: *const $t,
+            lda: i32,
+            b: *mut $t,
+            ldb: i32,
+        ) {
+            unsafe {
+                trsm(
+                    layout, side, uplo, transa, diag, m, n, alpha, a, lda, b, ldb,
+                )
+            };
+        }
+    };
+}
+
+cblas_trsm!(cblas_strsm, f32);
+cblas_trsm!(cblas_dtrsm, f64);
+
+// The complex routines take `alpha` by pointer.
+macro_rules! cblas_complex_trsm {
+    ($name:ident, $t:ty) => {
+        /// Solves `op(A) * X = alpha * B` or `X * op(A) = alpha * B`, overwriting `b` with `X`.
+        ///
+        /// # Safety
+        ///
+        /// The pointers must be valid for the matrix sizes described by `m`, `n` and the
+        /// leading dimensions, exactly as required by the CBLAS routine of the same name.
+        #[allow(clippy::too_many_arguments)]
+        pub unsafe fn $name(
+            layout: CBlasLayout,
+            side: CBlasSide,
+            uplo: CBlasUplo,
+            transa: CBlasTranspose,
+            diag: CBlasDiag,
+            m: i32,
+            n: i32,
+            alpha: *const Complex<$t>,
+            a: *const Complex<$t>,
+            lda: i32,
+            b: *mut Complex<$t>,
+            ldb: i32,
+        ) {
+            unsafe {
+                trsm(
+                    layout, side, uplo, transa, diag, m, n, *alpha, a, lda, b, ldb,
+                )
+            };
+        }
+    };
+}
+
+cblas_complex_trsm!(cblas_ctrsm, f32);
+cblas_complex_trsm!(cblas_ztrsm, f64);
+
+/// Unblocked LU factorization with partial pivoting of the column-major `m x n` matrix `a`.
+/// Returns `info` like `?getrf`.
+unsafe fn getrf<T: FloatElement>(m: usize, n: usize, a: *mut T, lda: usize, ipiv: *mut i32) -> i32 {
+    let mut info = 0;
+
+    for k in 0..m.min(n) {
+        let column = unsafe { slice::from_raw_parts(a.add(k * lda), m) };
+        let mut pivot_row = k;
+        for i in k + 1..m {
+            if column[i].abs() > column[pivot_row].abs() {
+                pivot_row = i;
+            }
+        }
+        let pivot = column[pivot_row];
+        unsafe { *ipiv.add(k) = pivot_row as i32 + 1 };
+
+        if pivot == T::zero() {
+            if info == 0 {
+                info = k as i32 + 1;
+            }
+            continue;
+        }
+
+        if pivot_row != k {
+            for j in 0..n {
+                unsafe { ptr::swap(a.add(j * lda + k), a.add(j * lda + pivot_row)) };
+            }
+        }
+
+        let below = unsafe { slice::from_raw_parts_mut(a.add(k * lda + k + 1), m - k - 1) };
+        below.iter_mut().for_each(|l_ik| *l_ik /= pivot);
+
+        for j in k + 1..n {
+            let u_kj = unsafe { *a.add(j * lda + k) };
+            for i in k + 1..m {
+                unsafe { *a.add(j * lda + i) -= *a.add(k * lda + i) * u_kj };
+            }
+        }
+    }
+
+    info
+}
+
+/// Solves `op(A) * X = B` for the column-major `n x nrhs` matrix `b`, with the factors of `A`
+/// from `getrf`.
+#[allow(clippy::too_many_arguments)]
+unsafe fn getrs<T: FloatElement>(
+    transposed: bool,
+    n: usize,
+    nrhs: usize,
+    a: *const T,
+    lda: usize,
+    ipiv: *const i32,
+    b: *mut T,
+    ldb: usize,
+) {
+    let a = |i: usize, j: usize| unsafe { *a.add(j * lda + i) };
+    let pivot = |k: usize| unsafe { *ipiv.add(k) as usize - 1 };
+
+    for c in 0..nrhs {
+        let x = unsafe { slice::from_raw_parts_mut(b.add(c * ldb), n) };
+
+        if transposed {
+            // A^T = U^T * L^T * P
+            for i in 0..n {
+                let sum = (0..i).fold(x[i], |sum, k| sum - a(k, i) * x[k]);
+                x[i] = sum / a(i, i);
+            }
+            for i in (0..n).rev() {
+                x[i] = (i + 1..n).fold(x[i], |sum, k| sum - a(k, i) * x[k]);
+            }
+            for k in (0..n).rev() {
+                x.swap(k, pivot(k));
+            }
+        } else {
+            for k in 0..n {
+                x.swap(k, pivot(k));
+            }
+            for i in 0..n {
+                x[i] = (0..i).fold(x[i], |sum, k| sum - a(i, k) * x[k]);
+            }
+            for i in (0..n).rev() {
+                let sum = (i + 1..n).fold(x[i], |sum, k| sum - a(i, k) * x[k]);
+                x[i] = sum / a(i, i);
+            }
+        }
+    }
+}
+
+macro_rules! lapack_getrf {
+    ($name:ident, $t:ty) => {
+        /// LU factorization with partial pivoting, `A = P * L * U`.
+        ///
+        /// # Safety
+        ///
+        /// The pointers must be valid as required by the LAPACK routine of the same name.
+        pub unsafe fn $name(
+            m: *const i32,
+            n: *const i32,
+            a: *mut $t,
+            lda: *const i32,
+            ipiv: *mut i32,
+            info: *mut i32,
+        ) {
+            unsafe {
+                *info = getrf(*m as usize, *n as usize, a, *lda as usize, ipiv);
+            }
+        }
+    };
+}
+
+lapack_getrf!(sgetrf_, f32);
+lapack_getrf!(dgetrf_, f64);
+
+macro_rules! lapack_getrs {
+    ($name:ident, $t:ty) => {
+        /// Solves `op(A) * X = B` with the factors from `getrf`.
+        ///
+        /// # Safety
+        ///
+        /// The pointers must be valid as required by the LAPACK routine of the same name.
+        #[allow(clippy::too_many_arguments)]
+        pub unsafe fn $name(
+            trans: *const c_char,
+            n: *const i32,
+            nrhs: *const i32,
+            a: *const $t,
+            lda: *const i32,
+            ipiv: *const i32,
+            b: *mut $t,
+            ldb: *const i32,
+            info: *mut i32,
+            _trans_len: usize,
+        ) {
+            unsafe {
+                let transposed = matches!(*trans as u8, b'T' | b't' | b'C' | b'c');
+                getrs(
+                    transposed,
+                    *n as usize,
+                    *nrhs as usize,
+                    a,
+                    *lda as usize,
+                    ipiv,
+                    b,
+                    *ldb as usize,
+                );
+                *info = 0;
+            }
+        }
+    };
+}
+
+lapack_getrs!(sgetrs_, f32);
+lapack_getrs!(dgetrs_, f64);
+
+/// Unblocked Householder QR of the column-major `m x n` matrix `a`, like `?geqrf`.
+unsafe fn geqrf<T: FloatElement>(m: usize, n: usize, a: *mut T, lda: usize, tau: *mut T) {
+    for j in 0..m.min(n) {
+        let column = unsafe { slice::from_raw_parts_mut(a.add(j * lda + j), m - j) };
+        let alpha = column[0];
+        let tail_norm = column[1..].iter().fold(T::zero(), |sum, &x| sum.hypot(x));
+        if tail_norm == T::zero() {
+            unsafe { *tau.add(j) = T::zero() };
+            continue;
+        }
+
+        let beta = -alpha.signum() * alpha.hypot(tail_norm);
+        let tau_j = (beta - alpha) / beta;
+        let scale = T::one() / (alpha - beta);
+        column[1..].iter_mut().for_each(|v_i| *v_i *= scale);
+        column[0] = beta;
+        unsafe { *tau.add(j) = tau_j };
+
+        // Apply H_j = I - tau * v * v^T to the trailing columns
+        for c in j + 1..n {
+            let v = unsafe { slice::from_raw_parts(a.add(j * lda + j), m - j) };
+            let x = unsafe { slice::from_raw_parts_mut(a.add(c * lda + j), m - j) };
+            let w = v[1..]
+                .iter()
+                .zip(&x[1..])
+                .fold(x[0], |w, (&v_i, &x_i)| w + v_i * x_i);
+            x[0] -= tau_j * w;
+            for (x_i, &v_i) in x[1..].iter_mut().zip(&v[1..]) {
+                *x_i -= tau_j * w * v_i;
+            }
+        }
+    }
+}
+
+macro_rules! lapack_geqrf {
+    ($name:ident, $t:ty) => {
+        /// Householder QR factorization, `A = Q * R`. The workspace is not needed.
+        ///
+        /// # Safety
+        ///
+        /// The pointers must be valid as required by the LAPACK routine of the same name.
+        #[allow(clippy::too_many_arguments)]
+        pub unsafe fn $name(
+            m: *const i32,
+            n: *const i32,
+            a: *mut $t,
+            lda: *const i32,
+            tau: *mut $t,
+            work: *mut $t,
+            lwork: *const i32,
+            info: *mut i32,
+        ) {
+            unsafe {
+                *info = 0;
+                if *lwork == -1 {
+                    *work = 1.0;
+                    return;
+                }
+                geqrf(*m as usize, *n as usize, a, *lda as usize, tau);
+            }
+        }
+    };
+}
+
+lapack_geqrf!(sgeqrf_, f32);
+lapack_geqrf!(dgeqrf_, f64);
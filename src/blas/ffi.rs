@@ -0,0 +1,313 @@
+// Linked against the provider selected in `build.rs`.
+
+use super::{CBlasDiag, CBlasLayout, CBlasSide, CBlasTranspose, CBlasUplo};
+use num_complex::Complex;
+use std::ffi::{c_char, c_int};
+
+unsafe extern "C" {
+    pub unsafe fn cblas_sgemm(
+        layout: CBlasLayout,
+        transa: CBlasTranspose,
+        transb: CBlasTranspose,
+        m: i32,
+        n: i32,
+        k: i32,
+        alpha: f32,
+        a: *const f32,
+        lda: i32,
+        b: *const f32,
+        ldb: i32,
+        beta: f32,
+        c: *mut f32,
+        ldc: i32,
+    );
+
+    pub unsafe fn cblas_dgemm(
+        layout: CBlasLayout,
+        transa: CBlasTranspose,
+        transb: CBlasTranspose,
+        m: i32,
+        n: i32,
+        k: i32,
+        alpha: f64,
+        a: *const f64,
+        lda: i32,
+        b: *const f64,
+        ldb: i32,
+        beta: f64,
+        c: *mut f64,
+        ldc: i32,
+    );
+
+    pub unsafe fn cblas_cgemm(
+        layout: CBlasLayout,
+        transa: CBlasTranspose,
+        transb: CBlasTranspose,
+        m: i32,
+        n: i32,
+        k: i32,
+        alpha: *const Complex<f32>,
+        a: *const Complex<f32>,
+        lda: i32,
+        b: *const Complex<f32>,
+        ldb: i32,
+        beta: *const Complex<f32>,
+        c: *mut Complex<f32>,
+        ldc: i32,
+    );
+
+    pub unsafe fn cblas_zgemm(
+        layout: CBlasLayout,
+        transa: CBlasTranspose,
+        transb: CBlasTranspose,
+        m: i32,
+        n: i32,
+        k: i32,
+        alpha: *const Complex<f64>,
+        a: *const Complex<f64>,
+        lda: i32,
+        b: *const Complex<f64>,
+        ldb: i32,
+        beta: *const Complex<f64>,
+        c: *mut Complex<f64>,
+        ldc: i32,
+    );
+
+    pub unsafe fn cblas_sgemv(
+        layout: CBlasLayout,
+        trans: CBlasTranspose,
+        m: i32,
+        n: i32,
+        alpha: f32,
+        a: *const f32,
+        lda: i32,
+        x: *const f32,
+        incx: i32,
+        beta: f32,
+        y: *mut f32,
+        incy: i32,
+    );
+
+    pub unsafe fn cblas_dgemv(
+        layout: CBlasLayout,
+        trans: CBlasTranspose,
+        m: i32,
+        n: i32,
+        alpha: f64,
+        a: *const f64,
+        lda: i32,
+        x: *const f64,
+        incx: i32,
+        beta: f64,
+        y: *mut f64,
+        incy: i32,
+    );
+
+    pub unsafe fn cblas_cgemv(
+        layout: CBlasLayout,
+        trans: CBlasTranspose,
+        m: i32,
+        n: i32,
+        alpha: *const Complex<f32>,
+        a: *const Complex<f32>,
+        lda: i32,
+        x: *const Complex<f32>,
+        incx: i32,
+        beta: *const Complex<f32>,
+        y: *mut Complex<f32>,
+        incy: i32,
+    );
+
+    pub unsafe fn cblas_zgemv(
+        layout: CBlasLayout,
+        trans: CBlasTranspose,
+        m: i32,
+        n: i32,
+        alpha: *const Complex<f64>,
+        a: *const Complex<f64>,
+        lda: i32,
+        x: *const Complex<f64>,
+        incx: i32,
+        beta: *const Complex<f64>,
+        y: *mut Complex<f64>,
+        incy: i32,
+    );
+
+    pub unsafe fn cblas_sdot(n: i32, x: *const f32, incx: i32, y: *const f32, incy: i32) -> f32;
+
+    pub unsafe fn cblas_saxpy(n: i32, alpha: f32, x: *const f32, incx: i32, y: *mut f32, incy: i32);
+
+    pub unsafe fn cblas_snrm2(n: i32, x: *const f32, incx: i32) -> f32;
+
+    pub unsafe fn cblas_sasum(n: i32, x: *const f32, incx: i32) -> f32;
+
+    pub unsafe fn cblas_isamax(n: i32, x: *const f32, incx: i32) -> c_int;
+
+    pub unsafe fn cblas_sscal(n: i32, alpha: f32, x: *mut f32, incx: i32);
+
+    pub unsafe fn cblas_srotg(a: *mut f32, b: *mut f32, c: *mut f32, s: *mut f32);
+
+    pub unsafe fn cblas_srot(
+        n: i32,
+        x: *mut f32,
+        incx: i32,
+        y: *mut f32,
+        incy: i32,
+        c: f32,
+        s: f32,
+    );
+
+    pub unsafe fn cblas_ddot(n: i32, x: *const f64, incx: i32, y: *const f64, incy: i32) -> f64;
+
+    pub unsafe fn cblas_daxpy(n: i32, alpha: f64, x: *const f64, incx: i32, y: *mut f64, incy: i32);
+
+    pub unsafe fn cblas_dnrm2(n: i32, x: *const f64, incx: i32) -> f64;
+
+    pub unsafe fn cblas_dasum(n: i32, x: *const f64, incx: i32) -> f64;
+
+    pub unsafe fn cblas_idamax(n: i32, x: *const f64, incx: i32) -> c_int;
+
+    pub unsafe fn cblas_dscal(n: i32, alpha: f64, x: *mut f64, incx: i32);
+
+    pub unsafe fn cblas_drotg(a: *mut f64, b: *mut f64, c: *mut f64, s: *mut f64);
+
+    pub unsafe fn cblas_drot(
+        n: i32,
+        x: *mut f64,
+        incx: i32,
+        y: *mut f64,
+        incy: i32,
+        c: f64,
+        s: f64,
+    );
+
+    pub unsafe fn cblas_strsm(
+        layout: CBlasLayout,
+        side: CBlasSide,
+        uplo: CBlasUplo,
+        transa: CBlasTranspose,
+        diag: CBlasDiag,
+        m: i32,
+        n: i32,
+        alpha: f32,
+        a: *const f32,
+        lda: i32,
+        b: *mut f32,
+        ldb: i32,
+    );
+
+    pub unsafe fn cblas_dtrsm(
+        layout: CBlasLayout,
+        side: CBlasSide,
+        uplo: CBlasUplo,
+        transa: CBlasTranspose,
+        diag: CBlasDiag,
+        m: i32,
+        n: i32,
+        alpha: f64,
+        a: *const f64,
+        lda: i32,
+        b: *mut f64,
+        ldb: i32,
+    );
+
+    pub unsafe fn cblas_ctrsm(
+        layout: CBlasLayout,
+        side: CBlasSide,
+        uplo: CBlasUplo,
+        transa: CBlasTranspose,
+        diag: CBlasDiag,
+        m: i32,
+        n: i32,
+        alpha: *const Complex<f32>,
+        a: *const Complex<f32>,
+        lda: i32,
+        b: *mut Complex<f32>,
+        ldb: i32,
+    );
+
+    pub unsafe fn cblas_ztrsm(
+        layout: CBlasLayout,
+        side: CBlasSide,
+        uplo: CBlasUplo,
+        transa: CBlasTranspose,
+        diag: CBlasDiag,
+        m: i32,
+        n: i32,
+        alpha: *const Complex<f64>,
+        a: *const Complex<f64>,
+        lda: i32,
+        b: *mut Complex<f64>,
+        ldb: i32,
+    );
+}
+
+// LAPACK routines, called through the Fortran interface. Character arguments are followed by
+// their hidden lengths after the last argument, as gfortran passes them.
+unsafe extern "C" {
+    pub unsafe fn sgetrf_(
+        m: *const i32,
+        n: *const i32,
+        a: *mut f32,
+        lda: *const i32,
+        ipiv: *mut i32,
+        info: *mut i32,
+    );
+
+    pub unsafe fn dgetrf_(
+        m: *const i32,
+        n: *const i32,
+        a: *mut f64,
+        lda: *const i32,
+        ipiv: *mut i32,
+        info: *mut i32,
+    );
+
+    pub unsafe fn sgetrs_(
+        trans: *const c_char,
+        n: *const i32,
+        nrhs: *const i32,
+        a: *const f32,
+        lda: *const i32,
+        ipiv: *const i32,
+        b: *mut f32,
+        ldb: *const i32,
+        info: *mut i32,
+        trans_len: usize,
+    );
+
+    pub unsafe fn dgetrs_(
+        trans: *const c_char,
+        n: *const i32,
+        nrhs: *const i32,
+        a: *const f64,
+        lda: *const i32,
+        ipiv: *const i32,
+        b: *mut f64,
+        ldb: *const i32,
+        info: *mut i32,
+        trans_len: usize,
+    );
+
+    pub unsafe fn sgeqrf_(
+        m: *const i32,
+        n: *const i32,
+        a: *mut f32,
+        lda: *const i32,
+        tau: *mut f32,
+        work: *mut f32,
+        lwork: *const i32,
+        info: *mut i32,
+    );
+
+    pub unsafe fn dgeqrf_(
+        m: *const i32,
+        n: *const i32,
+        a: *mut f64,
+        lda: *const i32,
+        tau: *mut f64,
+        work: *mut f64,
+        lwork: *const i32,
+        info: *mut i32,
+    );
+}
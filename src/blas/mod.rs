@@ -0,0 +1,453 @@
+//! CBLAS and LAPACK bindings used by `BlasMatrix`.
+//!
+//! The provider is chosen by `build.rs` from the enabled cargo features:
+//! `blas-accelerate` links Apple's Accelerate framework (macOS only) and
+//! `blas-openblas` links a system CBLAS (OpenBLAS by default, see `build.rs`).
+//! Without a provider, a pure-Rust implementation with the same signatures is used.
+//!
+//! The LAPACK routines (`?getrf` and friends) use the Fortran interface, which every provider
+//! exports: arguments are passed by pointer and matrices are column-major.
+
+use crate::{Element, FloatElement};
+use num_complex::Complex;
+use std::ffi::c_char;
+
+#[cfg(not(blas_provider))]
+mod fallback;
+#[cfg(blas_provider)]
+mod ffi;
+
+#[cfg(not(blas_provider))]
+pub use fallback::*;
+#[cfg(blas_provider)]
+pub use ffi::*;
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub enum CBlasLayout {
+    CblasRowMajor = 101,
+    CblasColMajor = 102,
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub enum CBlasTranspose {
+    CblasNoTrans = 111,
+    CblasTrans = 112,
+    CblasConjTrans = 113,
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub enum CBlasUplo {
+    CblasUpper = 121,
+    CblasLower = 122,
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub enum CBlasDiag {
+    CblasNonUnit = 131,
+    CblasUnit = 132,
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub enum CBlasSide {
+    CblasLeft = 141,
+    CblasRight = 142,
+}
+
+/// Element types with CBLAS `gemm`, `gemv` and `trsm` routines, used by `BlasMatrix` to dispatch
+/// by type.
+///
+/// Implemented for `f32` (`sgemm`, ...), `f64` (`dgemm`, ...), `Complex<f32>` (`cgemm`, ...) and
+/// `Complex<f64>` (`zgemm`, ...).
+pub trait BlasElement: Element {
+    /// Computes `c = alpha * op(a) * op(b) + beta * c` with the matching `cblas_?gemm`.
+    ///
+    /// # Safety
+    ///
+    /// Same requirements as the underlying CBLAS routine.
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn gemm(
+        layout: CBlasLayout,
+        transa: CBlasTranspose,
+        transb: CBlasTranspose,
+        m: i32,
+        n: i32,
+        k: i32,
+        alpha: Self,
+        a: *const Self,
+        lda: i32,
+        b: *const Self,
+        ldb: i32,
+        beta: Self,
+        c: *mut Self,
+        ldc: i32,
+    );
+
+    /// Computes `y = alpha * op(a) * x + beta * y` with the matching `cblas_?gemv`, where `a` is
+    /// `m x n`.
+    ///
+    /// # Safety
+    ///
+    /// Same requirements as the underlying CBLAS routine.
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn gemv(
+        layout: CBlasLayout,
+        trans: CBlasTranspose,
+        m: i32,
+        n: i32,
+        alpha: Self,
+        a: *const Self,
+        lda: i32,
+        x: *const Self,
+        incx: i32,
+        beta: Self,
+        y: *mut Self,
+        incy: i32,
+    );
+
+    /// Solves `op(A) * X = alpha * B` (left) or `X * op(A) = alpha * B` (right) for the
+    /// triangular `A` with the matching `cblas_?trsm`, overwriting `b` with `X`.
+    ///
+    /// # Safety
+    ///
+    /// Same requirements as the underlying CBLAS routine.
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn trsm(
+        layout: CBlasLayout,
+        side: CBlasSide,
+        uplo: CBlasUplo,
+        transa: CBlasTranspose,
+        diag: CBlasDiag,
+        m: i32,
+        n: i32,
+        alpha: Self,
+        a: *const Self,
+        lda: i32,
+        b: *mut Self,
+        ldb: i32,
+    );
+}
+
+macro_rules! impl_blas_element {
+    ($t:ty, $gemm:ident, $gemv:ident, $trsm:ident $(, $by_ref:tt)?) => {
+        impl BlasElement for $t {
+            unsafe fn gemm(
+                layout: CBlasLayout,
+                transa: CBlasTranspose,
+                transb: CBlasTranspose,
+                m: i32,
+                n: i32,
+                k: i32,
+                alpha: Self,
+                a: *const Self,
+                lda: i32,
+                b: *const Self,
+                ldb: i32,
+                beta: Self,
+                c: *mut Self,
+                ldc: i32,
+            ) {
+                unsafe {
+                    $gemm(
+                        layout,
+                        transa,
+                        transb,
+                        m,
+                        n,
+                        k,
+                        $($by_ref)? alpha,
+                        a,
+                        lda,
+                        b,
+                        ldb,
+                        $($by_ref)? beta,
+                        c,
+                        ldc,
+                    );
+                }
+            }
+
+            unsafe fn gemv(
+                layout: CBlasLayout,
+                trans: CBlasTranspose,
+                m: i32,
+                n: i32,
+                alpha: Self,
+                a: *const Self,
+                lda: i32,
+                x: *const Self,
+                incx: i32,
+                beta: Self,
+                y: *mut Self,
+                incy: i32,
+            ) {
+                unsafe {
+                    $gemv(
+                        layout,
+                        trans,
+                        m,
+                        n,
+                        $($by_ref)? alpha,
+                        a,
+                        lda,
+                        x,
+                        incx,
+                        $($by_ref)? beta,
+                        y,
+                        incy,
+                    );
+                }
+            }
+
+            unsafe fn trsm(
+                layout: CBlasLayout,
+                side: CBlasSide,
+                uplo: CBlasUplo,
+                transa: CBlasTranspose,
+                diag: CBlasDiag,
+                m: i32,
+                n: i32,
+                alpha: Self,
+                a: *const Self,
+                lda: i32,
+                b: *mut Self,
+                ldb: i32,
+            ) {
+                unsafe {
+                    $trsm(
+                        layout,
+                        side,
+                        uplo,
+                        transa,
+                        diag,
+                        m,
+                        n,
+                        $($by_ref)? alpha,
+                        a,
+                        lda,
+                        b,
+                        ldb,
+                    );
+                }
+            }
+        }
+    };
+}
+
+impl_blas_element!(f32, cblas_sgemm, cblas_sgemv, cblas_strsm);
+impl_blas_element!(f64, cblas_dgemm, cblas_dgemv, cblas_dtrsm);
+impl_blas_element!(Complex<f32>, cblas_cgemm, cblas_cgemv, cblas_ctrsm, &);
+impl_blas_element!(Complex<f64>, cblas_zgemm, cblas_zgemv, cblas_ztrsm, &);
+
+/// Real element types with the CBLAS level-1 routines, used by `BlasMatrix` to dispatch by type.
+///
+/// Implemented for `f32` (`sdot`, ...) and `f64` (`ddot`, ...).
+pub trait BlasLevel1Element: BlasElement + FloatElement {
+    /// Computes `x · y` with `cblas_?dot`.
+    ///
+    /// # Safety
+    ///
+    /// Same requirements as the underlying CBLAS routine.
+    unsafe fn dot(n: i32, x: *const Self, incx: i32, y: *const Self, incy: i32) -> Self;
+
+    /// Computes `y += alpha * x` with `cblas_?axpy`.
+    ///
+    /// # Safety
+    ///
+    /// Same requirements as the underlying CBLAS routine.
+    unsafe fn axpy(n: i32, alpha: Self, x: *const Self, incx: i32, y: *mut Self, incy: i32);
+
+    /// The Euclidean norm of `x` with `cblas_?nrm2`.
+    ///
+    /// # Safety
+    ///
+    /// Same requirements as the underlying CBLAS routine.
+    unsafe fn nrm2(n: i32, x: *const Self, incx: i32) -> Self;
+
+    /// The sum of the absolute values of `x` with `cblas_?asum`.
+    ///
+    /// # Safety
+    ///
+    /// Same requirements as the underlying CBLAS routine.
+    unsafe fn asum(n: i32, x: *const Self, incx: i32) -> Self;
+
+    /// The 0-based index of the largest absolute value in `x` with `cblas_i?amax`.
+    ///
+    /// # Safety
+    ///
+    /// Same requirements as the underlying CBLAS routine.
+    unsafe fn iamax(n: i32, x: *const Self, incx: i32) -> usize;
+
+    /// Computes `x *= alpha` with `cblas_?scal`.
+    ///
+    /// # Safety
+    ///
+    /// Same requirements as the underlying CBLAS routine.
+    unsafe fn scal(n: i32, alpha: Self, x: *mut Self, incx: i32);
+
+    /// Applies the rotation `[c s; -s c]` to the pairs `(x_i, y_i)` with `cblas_?rot`.
+    ///
+    /// # Safety
+    ///
+    /// Same requirements as the underlying CBLAS routine.
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn rot(n: i32, x: *mut Self, incx: i32, y: *mut Self, incy: i32, c: Self, s: Self);
+}
+
+macro_rules! impl_blas_level1_element {
+    ($t:ty, $dot:ident, $axpy:ident, $nrm2:ident, $asum:ident, $iamax:ident, $scal:ident, $rot:ident) => {
+        impl BlasLevel1Element for $t {
+            unsafe fn dot(n: i32, x: *const Self, incx: i32, y: *const Self, incy: i32) -> Self {
+                unsafe { $dot(n, x, incx, y, incy) }
+            }
+
+            unsafe fn axpy(
+                n: i32,
+                alpha: Self,
+                x: *const Self,
+                incx: i32,
+                y: *mut Self,
+                incy: i32,
+            ) {
+                unsafe { $axpy(n, alpha, x, incx, y, incy) }
+            }
+
+            unsafe fn nrm2(n: i32, x: *const Self, incx: i32) -> Self {
+                unsafe { $nrm2(n, x, incx) }
+            }
+
+            unsafe fn asum(n: i32, x: *const Self, incx: i32) -> Self {
+                unsafe { $asum(n, x, incx) }
+            }
+
+            unsafe fn iamax(n: i32, x: *const Self, incx: i32) -> usize {
+                unsafe { $iamax(n, x, incx) as usize }
+            }
+
+            unsafe fn scal(n: i32, alpha: Self, x: *mut Self, incx: i32) {
+                unsafe { $scal(n, alpha, x, incx) }
+            }
+
+            unsafe fn rot(
+                n: i32,
+                x: *mut Self,
+                incx: i32,
+                y: *mut Self,
+                incy: i32,
+                c: Self,
+                s: Self,
+            ) {
+                unsafe { $rot(n, x, incx, y, incy, c, s) }
+            }
+        }
+    };
+}
+
+impl_blas_level1_element!(
+    f32,
+    cblas_sdot,
+    cblas_saxpy,
+    cblas_snrm2,
+    cblas_sasum,
+    cblas_isamax,
+    cblas_sscal,
+    cblas_srot
+);
+impl_blas_level1_element!(
+    f64,
+    cblas_ddot,
+    cblas_daxpy,
+    cblas_dnrm2,
+    cblas_dasum,
+    cblas_idamax,
+    cblas_dscal,
+    cblas_drot
+);
+
+/// Element types with LAPACK factorization routines, used by `BlasMatrix` to dispatch by type.
+///
+/// Implemented for `f32` (`sgetrf`, `sgetrs`, `sgeqrf`) and `f64` (`dgetrf`, `dgetrs`, `dgeqrf`).
+/// Matrices are column-major and the return value is LAPACK's `info`.
+pub trait LapackElement: BlasElement + FloatElement {
+    /// LU factorization with partial pivoting of the `m x n` matrix `a`, in place. A positive
+    /// result `i` means `U[i - 1][i - 1]` is exactly zero.
+    ///
+    /// # Safety
+    ///
+    /// Same requirements as the underlying LAPACK routine.
+    unsafe fn getrf(m: i32, n: i32, a: *mut Self, lda: i32, ipiv: *mut i32) -> i32;
+
+    /// Solves `op(A) * X = B` with the factors from `getrf`, overwriting `b`. `trans` is
+    /// `b'N'` or `b'T'`.
+    ///
+    /// # Safety
+    ///
+    /// Same requirements as the underlying LAPACK routine.
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn getrs(
+        trans: u8,
+        n: i32,
+        nrhs: i32,
+        a: *const Self,
+        lda: i32,
+        ipiv: *const i32,
+        b: *mut Self,
+        ldb: i32,
+    ) -> i32;
+
+    /// Householder QR factorization of the `m x n` matrix `a` in place, writing the scale of
+    /// each reflector to `tau`.
+    ///
+    /// # Safety
+    ///
+    /// Same requirements as the underlying LAPACK routine.
+    unsafe fn geqrf(m: i32, n: i32, a: *mut Self, lda: i32, tau: *mut Self) -> i32;
+}
+
+macro_rules! impl_lapack_element {
+    ($t:ty, $getrf:ident, $getrs:ident, $geqrf:ident) => {
+        impl LapackElement for $t {
+            unsafe fn getrf(m: i32, n: i32, a: *mut Self, lda: i32, ipiv: *mut i32) -> i32 {
+                let mut info = 0;
+                unsafe { $getrf(&m, &n, a, &lda, ipiv, &mut info) };
+                info
+            }
+
+            unsafe fn getrs(
+                trans: u8,
+                n: i32,
+                nrhs: i32,
+                a: *const Self,
+                lda: i32,
+                ipiv: *const i32,
+                b: *mut Self,
+                ldb: i32,
+            ) -> i32 {
+                let trans = trans as c_char;
+                let mut info = 0;
+                unsafe { $getrs(&trans, &n, &nrhs, a, &lda, ipiv, b, &ldb, &mut info, 1) };
+                info
+            }
+
+            unsafe fn geqrf(m: i32, n: i32, a: *mut Self, lda: i32, tau: *mut Self) -> i32 {
+                let mut info = 0;
+
+                // Query the optimal workspace size first
+                let mut size: $t = 0.0;
+                unsafe { $geqrf(&m, &n, a, &lda, tau, &mut size, &-1, &mut info) };
+                let lwork = (size as i32).max(1);
+                let mut work = vec![0.0; lwork as usize];
+
+                unsafe { $geqrf(&m, &n, a, &lda, tau, work.as_mut_ptr(), &lwork, &mut info) };
+                info
+            }
+        }
+    };
+}
+
+impl_lapack_element!(f32, sgetrf_, sgetrs_, sgeqrf_);
+impl_lapack_element!(f64, dgetrf_, dgetrs_, dgeqrf_);
@@ -0,0 +1,43 @@
+use num_complex::Complex;
+use num_traits::{Float, Num, NumAssign};
+use std::fmt::Debug;
+
+/// Numeric types that can be stored in a matrix.
+pub trait Element: Num + NumAssign + Copy + Debug + Send + Sync + 'static {
+    /// Complex conjugate. Real types are their own conjugate.
+    #[inline(always)]
+    fn conj(self) -> Self {
+        self
+    }
+}
+
+macro_rules! impl_element {
+    ($($t:ty),*) => {
+        $(impl Element for $t {})*
+    };
+}
+
+impl_element!(
+    f32, f64, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+);
+
+macro_rules! impl_complex_element {
+    ($($t:ty),*) => {
+        $(
+            impl Element for Complex<$t> {
+                #[inline(always)]
+                fn conj(self) -> Self {
+                    Complex::conj(&self)
+                }
+            }
+        )*
+    };
+}
+
+impl_complex_element!(f32, f64);
+
+/// Real floating-point elements, required by the factorizations in [`crate::linalg`].
+pub trait FloatElement: Element + Float {}
+
+impl FloatElement for f32 {}
+impl FloatElement for f64 {}
@@ -0,0 +1,227 @@
+use std::error::Error;
+use std::fmt;
+use std::ops::Range;
+
+/// Errors returned by the fallible `try_*` methods of [`Matrix`](crate::Matrix) and by the
+/// factorizations in [`linalg`](crate::linalg).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MatrixError {
+    /// The matrix would have no rows or no columns.
+    EmptyInput,
+    /// A row does not have the same length as the first row.
+    RaggedRows {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// The shapes of the two operands are incompatible for the operation.
+    DimensionMismatch {
+        left: (usize, usize),
+        right: (usize, usize),
+    },
+    /// The operation is only defined for square matrices.
+    NotSquare { shape: (usize, usize) },
+    /// The element index lies outside the matrix.
+    IndexOutOfBounds {
+        index: (usize, usize),
+        shape: (usize, usize),
+    },
+    /// The row or column range of a submatrix lies outside the matrix.
+    InvalidRange {
+        rows: Range<usize>,
+        cols: Range<usize>,
+        shape: (usize, usize),
+    },
+    /// The storage is too short for the requested shape and strides.
+    StorageTooSmall { required: usize, len: usize },
+    /// The matrix is singular (to working precision), so it has no inverse.
+    Singular,
+    /// The columns are linearly dependent (to working precision).
+    RankDeficient { rank: usize, expected: usize },
+    /// The matrix differs from its transpose, first at `index` in the lower triangle.
+    NotSymmetric { index: (usize, usize) },
+    /// A Cholesky pivot was not positive, so the matrix is not positive definite.
+    NotPositiveDefinite { pivot: usize },
+    /// An iterative algorithm did not converge within its iteration limit.
+    NotConverged,
+}
+
+impl fmt::Display for MatrixError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MatrixError::EmptyInput => write!(f, "matrix must have at least one row and column"),
+            MatrixError::RaggedRows {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row {} has {} columns, expected {}",
+                row, found, expected
+            ),
+            MatrixError::DimensionMismatch { left, right } => write!(
+                f,
+                "dimension mismatch: {}x{} and {}x{}",
+                left.0, left.1, right.0, right.1
+            ),
+            MatrixError::NotSquare { shape } => {
+                write!(f, "expected a square matrix, got {}x{}", shape.0, shape.1)
+            }
+            MatrixError::IndexOutOfBounds { index, shape } => write!(
+                f,
+                "index ({}, {}) out of bounds for a {}x{} matrix",
+                index.0, index.1, shape.0, shape.1
+            ),
+            MatrixError::InvalidRange { rows, cols, shape } => write!(
+                f,
+                "rows {:?} and columns {:?} are out of bounds for a {}x{} matrix",
+                rows, cols, shape.0, shape.1
+            ),
+            MatrixError::StorageTooSmall { required, len } => write!(
+                f,
+                "storage of length {} is too small, {} elements are required",
+                len, required
+            ),
+            MatrixError::Singular => write!(f, "matrix is singular"),
+            MatrixError::RankDeficient { rank, expected } => write!(
+                f,
+                "matrix is rank deficient: rank {}, expected {}",
+                rank, expected
+            ),
+            MatrixError::NotSymmetric { index } => write!(
+                f,
+                "matrix is not symmetric: entries ({}, {}) and ({}, {}) differ",
+                index.0, index.1, index.1, index.0
+            ),
+            MatrixError::NotPositiveDefinite { pivot } => write!(
+                f,
+                "matrix is not positive definite: pivot {} is not positive",
+                pivot
+            ),
+            MatrixError::NotConverged => write!(f, "iteration did not converge"),
+        }
+    }
+}
+
+impl Error for MatrixError {}
+
+/// Checks that `data` is non-empty and rectangular, returning its shape.
+pub(crate) fn validate_data<T>(data: &[Vec<T>]) -> Result<(usize, usize), MatrixError> {
+    let rows = data.len();
+    let cols = data.first().map_or(0, |row| row.len());
+    validate_shape(rows, cols)?;
+
+    for (row, values) in data.iter().enumerate() {
+        if values.len() != cols {
+            return Err(MatrixError::RaggedRows {
+                row,
+                expected: cols,
+                found: values.len(),
+            });
+        }
+    }
+
+    Ok((rows, cols))
+}
+
+/// Panicking counterpart of `validate_data`, used by the `new` constructors.
+pub(crate) fn assert_data<T>(data: &[Vec<T>]) -> (usize, usize) {
+    match validate_data(data) {
+        Ok(shape) => shape,
+        Err(e) => panic!("{}", e),
+    }
+}
+
+pub(crate) fn validate_shape(rows: usize, cols: usize) -> Result<(), MatrixError> {
+    if rows == 0 || cols == 0 {
+        return Err(MatrixError::EmptyInput);
+    }
+    Ok(())
+}
+
+pub(crate) fn check_same_shape(
+    left: (usize, usize),
+    right: (usize, usize),
+) -> Result<(), MatrixError> {
+    if left != right {
+        return Err(MatrixError::DimensionMismatch { left, right });
+    }
+    Ok(())
+}
+
+pub(crate) fn check_multipliable(
+    left: (usize, usize),
+    right: (usize, usize),
+) -> Result<(), MatrixError> {
+    if left.1 != right.0 {
+        return Err(MatrixError::DimensionMismatch { left, right });
+    }
+    Ok(())
+}
+
+pub(crate) fn check_same_rows(
+    left: (usize, usize),
+    right: (usize, usize),
+) -> Result<(), MatrixError> {
+    if left.0 != right.0 {
+        return Err(MatrixError::DimensionMismatch { left, right });
+    }
+    Ok(())
+}
+
+pub(crate) fn check_square(shape: (usize, usize)) -> Result<(), MatrixError> {
+    if shape.0 != shape.1 {
+        return Err(MatrixError::NotSquare { shape });
+    }
+    Ok(())
+}
+
+/// Checks that `a * b` is defined and has the shape of the output `c`.
+pub(crate) fn check_gemm(
+    a: (usize, usize),
+    b: (usize, usize),
+    c: (usize, usize),
+) -> Result<(), MatrixError> {
+    check_multipliable(a, b)?;
+    check_same_shape((a.0, b.1), c)
+}
+
+/// Checks that `matrix * x` is defined for a vector `x` of length `len`.
+pub(crate) fn check_matvec(matrix: (usize, usize), len: usize) -> Result<(), MatrixError> {
+    check_multipliable(matrix, (len, 1))
+}
+
+/// Checks that `x^T * matrix` is defined for a vector `x` of length `len`.
+pub(crate) fn check_vecmat(len: usize, matrix: (usize, usize)) -> Result<(), MatrixError> {
+    check_multipliable((1, len), matrix)
+}
+
+pub(crate) fn check_index(index: (usize, usize), shape: (usize, usize)) -> Result<(), MatrixError> {
+    if index.0 >= shape.0 || index.1 >= shape.1 {
+        return Err(MatrixError::IndexOutOfBounds { index, shape });
+    }
+    Ok(())
+}
+
+/// Panicking counterpart of `check_index`, used by element accessors and `Index` impls.
+#[inline(always)]
+pub(crate) fn assert_index(index: (usize, usize), shape: (usize, usize)) {
+    if let Err(e) = check_index(index, shape) {
+        panic!("{}", e);
+    }
+}
+
+pub(crate) fn check_range(
+    rows: &Range<usize>,
+    cols: &Range<usize>,
+    shape: (usize, usize),
+) -> Result<(), MatrixError> {
+    if rows.start > rows.end || rows.end > shape.0 || cols.start > cols.end || cols.end > shape.1 {
+        return Err(MatrixError::InvalidRange {
+            rows: rows.clone(),
+            cols: cols.clone(),
+            shape,
+        });
+    }
+    Ok(())
+}
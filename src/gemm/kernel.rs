@@ -0,0 +1,65 @@
+use crate::{Element, simd};
+use std::slice;
+
+/// Rows of `c` produced by the portable micro-kernel.
+const MR: usize = 4;
+/// Columns of `c` produced by the portable micro-kernel.
+const NR: usize = 8;
+
+/// The largest `mr * nr` tile of any micro-kernel.
+pub(crate) const MAX_TILE: usize = 256;
+
+/// A micro-kernel and the shape of the tile of `c` it computes.
+///
+/// `run(depth, a, b, ab)` multiplies a packed `mr`-row micro-panel of `a` by a packed `nr`-column
+/// micro-panel of `b`, both `depth` steps long, and writes the `mr x nr` product to `ab` in
+/// row-major order.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct MicroKernel<T> {
+    pub mr: usize,
+    pub nr: usize,
+    pub run: unsafe fn(depth: usize, a: *const T, b: *const T, ab: *mut T),
+}
+
+impl<T: Element> MicroKernel<T> {
+    pub(crate) fn new(
+        mr: usize,
+        nr: usize,
+        run: unsafe fn(depth: usize, a: *const T, b: *const T, ab: *mut T),
+    ) -> Self {
+        debug_assert!(mr * nr <= MAX_TILE);
+        Self { mr, nr, run }
+    }
+
+    /// The SIMD kernel of the active instruction set, or the portable one if it has none for `T`.
+    pub(crate) fn select() -> Self {
+        simd::gemm_kernel().unwrap_or_else(|| Self::new(MR, NR, portable_kernel::<T>))
+    }
+}
+
+/// The portable micro-kernel. Each step along the shared dimension is an outer product of `MR`
+/// entries of `a` with `NR` entries of `b`, read sequentially from the packed panels.
+///
+/// # Safety
+///
+/// `a` and `b` must hold `depth * MR` and `depth * NR` elements, and `ab` room for `MR * NR`.
+unsafe fn portable_kernel<T: Element>(depth: usize, a: *const T, b: *const T, ab: *mut T) {
+    let (a, b, ab) = unsafe {
+        (
+            slice::from_raw_parts(a, depth * MR),
+            slice::from_raw_parts(b, depth * NR),
+            slice::from_raw_parts_mut(ab, MR * NR),
+        )
+    };
+    let mut acc = [[T::zero(); NR]; MR];
+    for (a, b) in a.chunks_exact(MR).zip(b.chunks_exact(NR)) {
+        for (acc_i, &a_i) in acc.iter_mut().zip(a) {
+            for (acc_ij, &b_j) in acc_i.iter_mut().zip(b) {
+                *acc_ij += a_i * b_j;
+            }
+        }
+    }
+    for (ab_i, acc_i) in ab.chunks_exact_mut(NR).zip(&acc) {
+        ab_i.copy_from_slice(acc_i);
+    }
+}
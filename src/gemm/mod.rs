@@ -0,0 +1,229 @@
+//! Matrix multiplication in the five-loop structure of BLIS.
+//!
+//! [`packed_gemm`] walks `nc`-column slabs of `b` and `c`, then `kc`-deep slabs of the shared
+//! dimension, copying each `kc x nc` block of `b` into `nr`-column micro-panels. Inside, it walks
+//! `mc`-row blocks of `a`, copying each `mc x kc` block into `mr`-row micro-panels, and the
+//! macro-kernel multiplies the two packed blocks one `mr x nr` tile of `c` at a time with the
+//! [`MicroKernel`] of the active SIMD instruction set. The packed
+//! block of `a` stays in L2 and the micro-panel of `b` in L1 while the micro-kernel streams over
+//! them, and the strides of the views only matter while packing.
+
+mod kernel;
+
+pub(crate) use kernel::MicroKernel;
+
+use crate::tuning::BlockSizes;
+use crate::{Element, MatrixView, MatrixViewMut};
+use kernel::MAX_TILE;
+use std::alloc::{self, Layout};
+use std::ops::{Deref, DerefMut, Range};
+use std::ptr::NonNull;
+
+/// Alignment of the packed buffers, a cache line on most targets.
+const ALIGN: usize = 64;
+
+/// A zero-initialized buffer aligned to [`ALIGN`] bytes, so that packed panels start on a cache
+/// line.
+struct AlignedBuffer<T> {
+    ptr: NonNull<T>,
+    len: usize,
+}
+
+impl<T: Element> AlignedBuffer<T> {
+    fn zeroed(len: usize) -> Self {
+        if len == 0 || size_of::<T>() == 0 {
+            return Self {
+                ptr: NonNull::dangling(),
+                len,
+            };
+        }
+        let layout = Self::layout(len);
+        // SAFETY: the layout has a non-zero size
+        let ptr = unsafe { alloc::alloc(layout) }.cast::<T>();
+        let Some(ptr) = NonNull::new(ptr) else {
+            alloc::handle_alloc_error(layout)
+        };
+        for i in 0..len {
+            // SAFETY: the allocation holds `len` elements
+            unsafe { ptr.add(i).write(T::zero()) };
+        }
+        Self { ptr, len }
+    }
+}
+
+impl<T> AlignedBuffer<T> {
+    fn layout(len: usize) -> Layout {
+        Layout::array::<T>(len)
+            .and_then(|layout| layout.align_to(ALIGN))
+            .expect("packed buffer is too large")
+    }
+}
+
+impl<T> Deref for AlignedBuffer<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        // SAFETY: `ptr` points to `len` initialized elements, or is dangling with nothing to read
+        unsafe { std::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<T> DerefMut for AlignedBuffer<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        // SAFETY: as in `deref`, and `self` is borrowed mutably
+        unsafe { std::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<T> Drop for AlignedBuffer<T> {
+    fn drop(&mut self) {
+        if self.len != 0 && size_of::<T>() != 0 {
+            // SAFETY: allocated in `zeroed` with the same layout, and the elements are `Copy`
+            unsafe { alloc::dealloc(self.ptr.as_ptr().cast(), Self::layout(self.len)) };
+        }
+    }
+}
+
+// SAFETY: the buffer owns its elements like a `Vec<T>`
+unsafe impl<T: Send> Send for AlignedBuffer<T> {}
+unsafe impl<T: Sync> Sync for AlignedBuffer<T> {}
+
+/// Copies `a[rows, depth]` into `mr`-row micro-panels, each stored one column at a time, padding
+/// the last panel with zeroes.
+fn pack_a<T: Element>(
+    a: &MatrixView<T>,
+    rows: Range<usize>,
+    depth: Range<usize>,
+    mr: usize,
+    packed: &mut [T],
+) {
+    let panels = packed.chunks_exact_mut(mr * depth.len());
+    for (panel, i0) in panels.zip(rows.clone().step_by(mr)) {
+        let panel_rows = mr.min(rows.end - i0);
+        for (column, p) in panel.chunks_exact_mut(mr).zip(depth.clone()) {
+            for (i, dst) in column.iter_mut().enumerate() {
+                *dst = if i < panel_rows {
+                    a.at(i0 + i, p)
+                } else {
+                    T::zero()
+                };
+            }
+        }
+    }
+}
+
+/// Copies `b[depth, cols]` into `nr`-column micro-panels, each stored one row at a time, padding
+/// the last panel with zeroes.
+fn pack_b<T: Element>(
+    b: &MatrixView<T>,
+    depth: Range<usize>,
+    cols: Range<usize>,
+    nr: usize,
+    packed: &mut [T],
+) {
+    let panels = packed.chunks_exact_mut(nr * depth.len());
+    for (panel, j0) in panels.zip(cols.clone().step_by(nr)) {
+        let panel_cols = nr.min(cols.end - j0);
+        for (row, p) in panel.chunks_exact_mut(nr).zip(depth.clone()) {
+            for (j, dst) in row.iter_mut().enumerate() {
+                *dst = if j < panel_cols {
+                    b.at(p, j0 + j)
+                } else {
+                    T::zero()
+                };
+            }
+        }
+    }
+}
+
+/// Adds `alpha` times the product of the packed blocks to `c[rows, cols]`, one micro-kernel tile
+/// at a time.
+#[allow(clippy::too_many_arguments)]
+fn macro_kernel<T: Element>(
+    kernel: MicroKernel<T>,
+    alpha: T,
+    a_packed: &[T],
+    b_packed: &[T],
+    depth: usize,
+    c: &mut MatrixViewMut<T>,
+    rows: Range<usize>,
+    cols: Range<usize>,
+) {
+    let MicroKernel { mr, nr, run } = kernel;
+    let mut ab = [T::zero(); MAX_TILE];
+    let b_panels = b_packed.chunks_exact(nr * depth);
+    for (b_panel, j0) in b_panels.zip(cols.clone().step_by(nr)) {
+        let tile_cols = nr.min(cols.end - j0);
+        let a_panels = a_packed.chunks_exact(mr * depth);
+        for (a_panel, i0) in a_panels.zip(rows.clone().step_by(mr)) {
+            let tile_rows = mr.min(rows.end - i0);
+            // SAFETY: the panels hold `mr * depth` and `nr * depth` elements, `ab` holds
+            // `MAX_TILE >= mr * nr`, and `select` only hands out kernels the CPU supports
+            unsafe { run(depth, a_panel.as_ptr(), b_panel.as_ptr(), ab.as_mut_ptr()) };
+            for (i, ab_i) in ab.chunks_exact(nr).take(tile_rows).enumerate() {
+                for (j, &ab_ij) in ab_i.iter().take(tile_cols).enumerate() {
+                    *c.at_mut(i0 + i, j0 + j) += alpha * ab_ij;
+                }
+            }
+        }
+    }
+}
+
+/// Computes `c = alpha * a * b + beta * c`. The shapes must already be checked.
+///
+/// As in BLAS, `c` is not read when `beta` is zero, and `a` and `b` are not read when `alpha` is
+/// zero.
+pub(crate) fn packed_gemm<T: Element>(
+    alpha: T,
+    a: &MatrixView<T>,
+    b: &MatrixView<T>,
+    beta: T,
+    c: &mut MatrixViewMut<T>,
+    blocks: BlockSizes,
+) {
+    let (m, k) = a.shape();
+    let n = b.num_cols();
+
+    if beta == T::zero() {
+        c.fill(T::zero());
+    } else if beta != T::one() {
+        for i in 0..m {
+            for j in 0..n {
+                *c.at_mut(i, j) *= beta;
+            }
+        }
+    }
+    if alpha == T::zero() || m == 0 || n == 0 || k == 0 {
+        return;
+    }
+
+    let kernel = MicroKernel::select();
+    let (mr, nr) = (kernel.mr, kernel.nr);
+    let mc = blocks.mc.max(1).next_multiple_of(mr);
+    let kc = blocks.kc.max(1);
+    let nc = blocks.nc.max(1).next_multiple_of(nr);
+    let mut a_packed = AlignedBuffer::zeroed(mc.min(m.next_multiple_of(mr)) * kc.min(k));
+    let mut b_packed = AlignedBuffer::zeroed(kc.min(k) * nc.min(n.next_multiple_of(nr)));
+
+    for jc in (0..n).step_by(nc) {
+        let cols = jc..n.min(jc + nc);
+        for pc in (0..k).step_by(kc) {
+            let depth = pc..k.min(pc + kc);
+            pack_b(b, depth.clone(), cols.clone(), nr, &mut b_packed);
+            for ic in (0..m).step_by(mc) {
+                let rows = ic..m.min(ic + mc);
+                pack_a(a, rows.clone(), depth.clone(), mr, &mut a_packed);
+                macro_kernel(
+                    kernel,
+                    alpha,
+                    &a_packed,
+                    &b_packed,
+                    depth.len(),
+                    c,
+                    rows.clone(),
+                    cols.clone(),
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,197 @@
+//! BLAS level-1 operations: dot products, `axpy`, norms, scaling and plane rotations.
+//!
+//! The functions here are the portable kernels on contiguous slices. They keep independent
+//! accumulators in fixed-size lanes so the compiler can vectorize them. [`Level1`] applies them
+//! to the storage of the flat matrices, and `BlasMatrix` overrides it with the `cblas_?dot`
+//! family.
+
+use crate::error::check_same_shape;
+use crate::{FlatMatrix, FloatElement, MatrixError};
+
+/// Number of independent accumulators in the reductions.
+const LANES: usize = 8;
+
+/// A plane (Givens) rotation `G = [c s; -s c]`, applied to pairs `(x_i, y_i)` by [`rot`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Givens<T> {
+    pub c: T,
+    pub s: T,
+}
+
+impl<T: FloatElement> Givens<T> {
+    /// The rotation with `G * [a; b] = [r; 0]`, returned together with `r`. The sign of `r` follows
+    /// BLAS `rotg`: it is the sign of whichever of `a` and `b` is larger in magnitude.
+    pub fn new(a: T, b: T) -> (Self, T) {
+        if a == T::zero() && b == T::zero() {
+            return (
+                Self {
+                    c: T::one(),
+                    s: T::zero(),
+                },
+                T::zero(),
+            );
+        }
+        let larger = if a.abs() > b.abs() { a } else { b };
+        let r = a.hypot(b);
+        let r = if larger < T::zero() { -r } else { r };
+        (Self { c: a / r, s: b / r }, r)
+    }
+}
+
+/// Sums `f(x_i)` over `x`.
+fn sum_lanes<T: FloatElement>(x: &[T], f: impl Fn(T) -> T) -> T {
+    let mut lanes = [T::zero(); LANES];
+    let chunks = x.chunks_exact(LANES);
+    let tail = chunks
+        .remainder()
+        .iter()
+        .fold(T::zero(), |sum, &x| sum + f(x));
+    for chunk in chunks {
+        for (lane, &x) in lanes.iter_mut().zip(chunk) {
+            *lane += f(x);
+        }
+    }
+    lanes.iter().fold(tail, |sum, &lane| sum + lane)
+}
+
+/// Computes `x · y`, panicking if the lengths differ.
+pub fn dot<T: FloatElement>(x: &[T], y: &[T]) -> T {
+    assert_eq!(x.len(), y.len(), "vectors must have the same length");
+    let mut lanes = [T::zero(); LANES];
+    let (x_chunks, y_chunks) = (x.chunks_exact(LANES), y.chunks_exact(LANES));
+    let tail = x_chunks
+        .remainder()
+        .iter()
+        .zip(y_chunks.remainder())
+        .fold(T::zero(), |sum, (&x, &y)| sum + x * y);
+    for (x, y) in x_chunks.zip(y_chunks) {
+        for ((lane, &x), &y) in lanes.iter_mut().zip(x).zip(y) {
+            *lane += x * y;
+        }
+    }
+    lanes.iter().fold(tail, |sum, &lane| sum + lane)
+}
+
+/// Computes `y += alpha * x`, panicking if the lengths differ.
+pub fn axpy<T: FloatElement>(alpha: T, x: &[T], y: &mut [T]) {
+    assert_eq!(x.len(), y.len(), "vectors must have the same length");
+    for (y, &x) in y.iter_mut().zip(x) {
+        *y += alpha * x;
+    }
+}
+
+/// The Euclidean norm of `x`, scaled so that squaring the entries can't overflow or underflow.
+pub fn nrm2<T: FloatElement>(x: &[T]) -> T {
+    let scale = x.iter().fold(T::zero(), |max, &x| max.max(x.abs()));
+    if scale == T::zero() || scale.is_infinite() {
+        return scale;
+    }
+    scale * sum_lanes(x, |x| (x / scale).powi(2)).sqrt()
+}
+
+/// The sum of the absolute values of `x`.
+pub fn asum<T: FloatElement>(x: &[T]) -> T {
+    sum_lanes(x, |x| x.abs())
+}
+
+/// The index of the first entry of `x` with the largest absolute value, or 0 if `x` is empty.
+pub fn iamax<T: FloatElement>(x: &[T]) -> usize {
+    let mut best = (0, T::neg_infinity());
+    for (i, &x) in x.iter().enumerate() {
+        if x.abs() > best.1 {
+            best = (i, x.abs());
+        }
+    }
+    best.0
+}
+
+/// Computes `x *= alpha`.
+pub fn scal<T: FloatElement>(alpha: T, x: &mut [T]) {
+    for x in x {
+        *x *= alpha;
+    }
+}
+
+/// Applies `givens` to every pair: `(x_i, y_i) = (c * x_i + s * y_i, c * y_i - s * x_i)`,
+/// panicking if the lengths differ.
+pub fn rot<T: FloatElement>(x: &mut [T], y: &mut [T], givens: Givens<T>) {
+    assert_eq!(x.len(), y.len(), "vectors must have the same length");
+    let Givens { c, s } = givens;
+    for (x, y) in x.iter_mut().zip(y) {
+        let (x_i, y_i) = (*x, *y);
+        *x = c * x_i + s * y_i;
+        *y = c * y_i - s * x_i;
+    }
+}
+
+fn storage<T: FloatElement, M: FlatMatrix<T>>(matrix: &M) -> &[T] {
+    matrix.as_slice().expect("flat matrices are contiguous")
+}
+
+/// BLAS level-1 operations on the flat matrices, treating the storage as one long vector.
+///
+/// The binary operations need both matrices to have the same shape.
+pub trait Level1<T: FloatElement = f64>: FlatMatrix<T> {
+    /// The sum of the element-wise products, panicking if the shapes differ.
+    fn dot(&self, other: &Self) -> T {
+        if let Err(e) = check_same_shape(self.shape(), other.shape()) {
+            panic!("{}", e);
+        }
+        dot(storage(self), storage(other))
+    }
+
+    /// Computes `self += alpha * x`, panicking if the shapes differ.
+    fn axpy(&mut self, alpha: T, x: &Self) {
+        if let Err(e) = check_same_shape(self.shape(), x.shape()) {
+            panic!("{}", e);
+        }
+        axpy(alpha, storage(x), self.as_mut_slice());
+    }
+
+    /// The Euclidean norm of the entries, i.e. the Frobenius norm of the matrix.
+    fn nrm2(&self) -> T {
+        nrm2(storage(self))
+    }
+
+    /// The sum of the absolute values of the entries.
+    fn asum(&self) -> T {
+        asum(storage(self))
+    }
+
+    /// The position of the first entry with the largest absolute value, in row-major order.
+    fn iamax(&self) -> (usize, usize) {
+        let i = iamax(storage(self));
+        (i / self.num_cols(), i % self.num_cols())
+    }
+
+    /// Computes `self *= alpha`.
+    fn scal(&mut self, alpha: T) {
+        scal(alpha, self.as_mut_slice());
+    }
+
+    /// Applies `givens` to the pairs of entries of `self` and `other` at the same position,
+    /// panicking if the shapes differ.
+    fn rot(&mut self, other: &mut Self, givens: Givens<T>) {
+        if let Err(e) = check_same_shape(self.shape(), other.shape()) {
+            panic!("{}", e);
+        }
+        rot(self.as_mut_slice(), other.as_mut_slice(), givens);
+    }
+
+    fn try_dot(&self, other: &Self) -> Result<T, MatrixError> {
+        check_same_shape(self.shape(), other.shape())?;
+        Ok(self.dot(other))
+    }
+
+    fn try_axpy(&mut self, alpha: T, x: &Self) -> Result<(), MatrixError> {
+        check_same_shape(self.shape(), x.shape())?;
+        self.axpy(alpha, x);
+        Ok(())
+    }
+
+    fn try_rot(&mut self, other: &mut Self, givens: Givens<T>) -> Result<(), MatrixError> {
+        check_same_shape(self.shape(), other.shape())?;
+        self.rot(other, givens);
+        Ok(())
+    }
+}
@@ -1,5 +1,28 @@
-pub trait Matrix {
-    fn new(data: Vec<Vec<f64>>) -> Self;
+use crate::error::{
+    check_gemm, check_index, check_matvec, check_multipliable, check_same_shape, check_vecmat,
+    validate_data, validate_shape,
+};
+use crate::reductions::{add_columns, sum};
+use crate::strassen::strassen_multiply;
+use crate::tuning::TuningConfig;
+use crate::view::{gemm_views, map_view, multiply_views, zip_map_views};
+use std::ops::{Neg, Range};
+
+pub use crate::element::{Element, FloatElement};
+pub use crate::error::MatrixError;
+pub use crate::level1::{Givens, Level1};
+pub use crate::linalg::{
+    Cholesky, Eigen, LinearAlgebra, LowerTriangular, Lu, PivotedQr, Qr, Svd, SymmetricEigen,
+    Triangle, UpperTriangular,
+};
+pub use crate::reductions::Reductions;
+pub use crate::vector::Vector;
+pub use crate::view::{MatrixView, MatrixViewMut, Transposed};
+pub use num_complex::Complex;
+
+pub trait Matrix<T: Element = f64>: Sized {
+    /// Builds a matrix from its rows. Panics if `data` is empty or its rows differ in length.
+    fn new(data: Vec<Vec<T>>) -> Self;
 
     fn shape(&self) -> (usize, usize);
 
@@ -7,7 +30,37 @@ pub trait Matrix {
 
     fn num_cols(&self) -> usize;
 
-    fn get_data(&self) -> Vec<Vec<f64>>;
+    fn get_data(&self) -> Vec<Vec<T>>;
+
+    /// Borrows the elements in row-major order, if the backend stores them contiguously.
+    fn as_slice(&self) -> Option<&[T]>;
+
+    /// Borrows a single row.
+    fn row(&self, row: usize) -> &[T];
+
+    /// Iterates over the rows without copying them.
+    fn row_iter(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.num_rows()).map(move |i| self.row(i))
+    }
+
+    /// Consumes the matrix, returning its elements in row-major order.
+    fn into_vec(self) -> Vec<T>;
+
+    /// Consumes the matrix, returning its rows.
+    fn into_rows(self) -> Vec<Vec<T>> {
+        self.get_data()
+    }
+
+    /// Returns the element at `(row, col)`, panicking if it is out of bounds.
+    fn get(&self, row: usize, col: usize) -> T;
+
+    /// Returns a mutable reference to the element at `(row, col)`, panicking if it is out of bounds.
+    fn get_mut(&mut self, row: usize, col: usize) -> &mut T;
+
+    /// Overwrites the element at `(row, col)`, panicking if it is out of bounds.
+    fn set(&mut self, row: usize, col: usize, value: T) {
+        *self.get_mut(row, col) = value;
+    }
 
     fn zeroes(rows: usize, cols: usize) -> Self;
 
@@ -19,7 +72,476 @@ pub trait Matrix {
 
     fn matrix_multiplication(&self, other: &Self) -> Self;
 
-    fn scalar_multiplication(&self, scalar: f64) -> Self;
+    fn scalar_multiplication(&self, scalar: T) -> Self;
+
+    /// Adds `other` to this matrix in place.
+    fn add_assign(&mut self, other: &Self) {
+        if let Err(e) = check_same_shape(self.shape(), other.shape()) {
+            panic!("{}", e);
+        }
+        let (rows, cols) = self.shape();
+        for i in 0..rows {
+            for j in 0..cols {
+                *self.get_mut(i, j) += other.get(i, j);
+            }
+        }
+    }
+
+    /// Subtracts `other` from this matrix in place.
+    fn sub_assign(&mut self, other: &Self) {
+        if let Err(e) = check_same_shape(self.shape(), other.shape()) {
+            panic!("{}", e);
+        }
+        let (rows, cols) = self.shape();
+        for i in 0..rows {
+            for j in 0..cols {
+                *self.get_mut(i, j) -= other.get(i, j);
+            }
+        }
+    }
+
+    /// Multiplies every element by `scalar` in place.
+    fn scale_in_place(&mut self, scalar: T) {
+        let (rows, cols) = self.shape();
+        for i in 0..rows {
+            for j in 0..cols {
+                *self.get_mut(i, j) *= scalar;
+            }
+        }
+    }
+
+    /// Applies `f` to every element, returning the results as a new matrix.
+    fn map(&self, f: impl Fn(T) -> T + Sync) -> Self {
+        Self::new(
+            self.row_iter()
+                .map(|row| row.iter().map(|&x| f(x)).collect())
+                .collect(),
+        )
+    }
+
+    /// Applies `f` to the pairs of elements at the same position, panicking if the shapes differ.
+    fn zip_map(&self, other: &Self, f: impl Fn(T, T) -> T + Sync) -> Self {
+        if let Err(e) = check_same_shape(self.shape(), other.shape()) {
+            panic!("{}", e);
+        }
+        Self::new(
+            self.row_iter()
+                .zip(other.row_iter())
+                .map(|(a, b)| a.iter().zip(b).map(|(&a, &b)| f(a, b)).collect())
+                .collect(),
+        )
+    }
+
+    /// The element-wise product, panicking if the shapes differ.
+    fn hadamard_product(&self, other: &Self) -> Self {
+        self.zip_map(other, |a, b| a * b)
+    }
+
+    /// The element-wise quotient `self / other`, panicking if the shapes differ.
+    fn elementwise_division(&self, other: &Self) -> Self {
+        self.zip_map(other, |a, b| a / b)
+    }
+
+    /// The exponential of every element.
+    fn exp(&self) -> Self
+    where
+        T: FloatElement,
+    {
+        self.map(T::exp)
+    }
+
+    /// The natural logarithm of every element.
+    fn ln(&self) -> Self
+    where
+        T: FloatElement,
+    {
+        self.map(T::ln)
+    }
+
+    /// The square root of every element.
+    fn sqrt(&self) -> Self
+    where
+        T: FloatElement,
+    {
+        self.map(T::sqrt)
+    }
+
+    /// The absolute value of every element.
+    fn abs(&self) -> Self
+    where
+        T: FloatElement,
+    {
+        self.map(T::abs)
+    }
+
+    /// Every element raised to the integer power `n`.
+    fn powi(&self, n: i32) -> Self
+    where
+        T: FloatElement,
+    {
+        self.map(|x| x.powi(n))
+    }
+
+    /// Limits every element to `[min, max]`. NaNs stay NaN.
+    fn clamp(&self, min: T, max: T) -> Self
+    where
+        T: FloatElement,
+    {
+        self.map(|x| if x.is_nan() { x } else { x.max(min).min(max) })
+    }
+
+    /// Writes `a * b` into `c`, reusing its storage instead of allocating a new matrix.
+    fn matmul_into(a: &Self, b: &Self, c: &mut Self) {
+        Self::gemm(T::one(), a, b, T::zero(), c);
+    }
+
+    /// Computes `c = alpha * a * b + beta * c` in place.
+    ///
+    /// As with BLAS, `c` is not read when `beta` is zero, so it may hold anything (even NaNs).
+    fn gemm(alpha: T, a: &Self, b: &Self, beta: T, c: &mut Self) {
+        if let Err(e) = check_gemm(a.shape(), b.shape(), c.shape()) {
+            panic!("{}", e);
+        }
+        let (rows, inner) = a.shape();
+        let cols = b.num_cols();
+        for i in 0..rows {
+            for j in 0..cols {
+                let mut sum = T::zero();
+                for p in 0..inner {
+                    sum += a.get(i, p) * b.get(p, j);
+                }
+                let c_ij = c.get_mut(i, j);
+                *c_ij = if beta == T::zero() {
+                    alpha * sum
+                } else {
+                    alpha * sum + beta * *c_ij
+                };
+            }
+        }
+    }
+
+    /// Computes the matrix-vector product `self * x`, panicking if `x` has the wrong length.
+    fn matvec(&self, x: &Vector<T>) -> Vector<T> {
+        if let Err(e) = check_matvec(self.shape(), x.len()) {
+            panic!("{}", e);
+        }
+        self.row_iter()
+            .map(|row| {
+                row.iter()
+                    .zip(x.iter())
+                    .fold(T::zero(), |sum, (&a, &x)| sum + a * x)
+            })
+            .collect::<Vec<_>>()
+            .into()
+    }
+
+    /// Computes the vector-matrix product `x^T * self`, panicking if `x` has the wrong length.
+    fn vecmat(&self, x: &Vector<T>) -> Vector<T> {
+        if let Err(e) = check_vecmat(x.len(), self.shape()) {
+            panic!("{}", e);
+        }
+        let mut y = Vector::zeroes(self.num_cols());
+        for (row, &x_i) in self.row_iter().zip(x.iter()) {
+            for (y_j, &a_ij) in y.as_mut_slice().iter_mut().zip(row) {
+                *y_j += x_i * a_ij;
+            }
+        }
+        y
+    }
+
+    /// The sum of the main diagonal, which for a non-square matrix has `min(rows, cols)` entries.
+    fn trace(&self) -> T {
+        let (rows, cols) = self.shape();
+        (0..rows.min(cols)).fold(T::zero(), |trace, i| trace + self.get(i, i))
+    }
+
+    /// The sum of every entry.
+    fn sum(&self) -> T {
+        self.row_iter()
+            .fold(T::zero(), |total, row| total + sum(row))
+    }
+
+    fn row_sums(&self) -> Vector<T> {
+        self.row_iter().map(sum).collect::<Vec<_>>().into()
+    }
+
+    fn col_sums(&self) -> Vector<T> {
+        let mut sums = vec![T::zero(); self.num_cols()];
+        add_columns(&mut sums, self.row_iter(), |_, a| a);
+        sums.into()
+    }
+
+    /// Returns the transpose as a new matrix.
+    fn transpose(&self) -> Self {
+        let (rows, cols) = self.shape();
+        Self::new(
+            (0..cols)
+                .map(|j| (0..rows).map(|i| self.get(i, j)).collect())
+                .collect(),
+        )
+    }
+
+    /// Returns a transposed view that borrows this matrix instead of copying it.
+    fn transposed_view(&self) -> Transposed<'_, T, Self> {
+        Transposed::new(self)
+    }
+
+    /// Returns the conjugate transpose, which is the plain transpose for real element types.
+    fn conjugate_transpose(&self) -> Self {
+        let (rows, cols) = self.shape();
+        Self::new(
+            (0..cols)
+                .map(|j| (0..rows).map(|i| self.get(i, j).conj()).collect())
+                .collect(),
+        )
+    }
+
+    /// Like `new`, but rejects empty and ragged input instead of panicking.
+    fn try_new(data: Vec<Vec<T>>) -> Result<Self, MatrixError> {
+        validate_data(&data)?;
+        Ok(Self::new(data))
+    }
+
+    /// Like `get`, but fails if the index is out of bounds.
+    fn try_get(&self, row: usize, col: usize) -> Result<T, MatrixError> {
+        check_index((row, col), self.shape())?;
+        Ok(self.get(row, col))
+    }
+
+    /// Like `set`, but fails if the index is out of bounds.
+    fn try_set(&mut self, row: usize, col: usize, value: T) -> Result<(), MatrixError> {
+        check_index((row, col), self.shape())?;
+        self.set(row, col, value);
+        Ok(())
+    }
+
+    /// Like `zeroes`, but rejects a zero number of rows or columns.
+    fn try_zeroes(rows: usize, cols: usize) -> Result<Self, MatrixError> {
+        validate_shape(rows, cols)?;
+        Ok(Self::zeroes(rows, cols))
+    }
+
+    /// Like `identity`, but rejects a size of zero.
+    fn try_identity(size: usize) -> Result<Self, MatrixError> {
+        validate_shape(size, size)?;
+        Ok(Self::identity(size))
+    }
+
+    /// Like `matrix_addition`, but fails if the shapes differ.
+    fn try_matrix_addition(&self, other: &Self) -> Result<Self, MatrixError> {
+        check_same_shape(self.shape(), other.shape())?;
+        Ok(self.matrix_addition(other))
+    }
+
+    /// Like `matrix_subtraction`, but fails if the shapes differ.
+    fn try_matrix_subtraction(&self, other: &Self) -> Result<Self, MatrixError> {
+        check_same_shape(self.shape(), other.shape())?;
+        Ok(self.matrix_subtraction(other))
+    }
+
+    /// Like `matrix_multiplication`, but fails if the inner dimensions differ.
+    fn try_matrix_multiplication(&self, other: &Self) -> Result<Self, MatrixError> {
+        check_multipliable(self.shape(), other.shape())?;
+        Ok(self.matrix_multiplication(other))
+    }
+
+    /// Like `add_assign`, but fails if the shapes differ.
+    fn try_add_assign(&mut self, other: &Self) -> Result<(), MatrixError> {
+        check_same_shape(self.shape(), other.shape())?;
+        self.add_assign(other);
+        Ok(())
+    }
+
+    /// Like `sub_assign`, but fails if the shapes differ.
+    fn try_sub_assign(&mut self, other: &Self) -> Result<(), MatrixError> {
+        check_same_shape(self.shape(), other.shape())?;
+        self.sub_assign(other);
+        Ok(())
+    }
+
+    /// Like `matmul_into`, but fails if `a` and `b` can't be multiplied or `c` has the wrong shape.
+    fn try_matmul_into(a: &Self, b: &Self, c: &mut Self) -> Result<(), MatrixError> {
+        check_gemm(a.shape(), b.shape(), c.shape())?;
+        Self::matmul_into(a, b, c);
+        Ok(())
+    }
+
+    /// Like `gemm`, but fails if `a` and `b` can't be multiplied or `c` has the wrong shape.
+    fn try_gemm(alpha: T, a: &Self, b: &Self, beta: T, c: &mut Self) -> Result<(), MatrixError> {
+        check_gemm(a.shape(), b.shape(), c.shape())?;
+        Self::gemm(alpha, a, b, beta, c);
+        Ok(())
+    }
+
+    /// Like `matvec`, but fails if `x` has the wrong length.
+    fn try_matvec(&self, x: &Vector<T>) -> Result<Vector<T>, MatrixError> {
+        check_matvec(self.shape(), x.len())?;
+        Ok(self.matvec(x))
+    }
+
+    /// Like `vecmat`, but fails if `x` has the wrong length.
+    fn try_vecmat(&self, x: &Vector<T>) -> Result<Vector<T>, MatrixError> {
+        check_vecmat(x.len(), self.shape())?;
+        Ok(self.vecmat(x))
+    }
+
+    /// Like `zip_map`, but fails if the shapes differ.
+    fn try_zip_map(&self, other: &Self, f: impl Fn(T, T) -> T + Sync) -> Result<Self, MatrixError> {
+        check_same_shape(self.shape(), other.shape())?;
+        Ok(self.zip_map(other, f))
+    }
+
+    /// Like `hadamard_product`, but fails if the shapes differ.
+    fn try_hadamard_product(&self, other: &Self) -> Result<Self, MatrixError> {
+        check_same_shape(self.shape(), other.shape())?;
+        Ok(self.hadamard_product(other))
+    }
+
+    /// Like `elementwise_division`, but fails if the shapes differ.
+    fn try_elementwise_division(&self, other: &Self) -> Result<Self, MatrixError> {
+        check_same_shape(self.shape(), other.shape())?;
+        Ok(self.elementwise_division(other))
+    }
+
+    /// Scalar multiplication cannot fail; provided for symmetry with the other `try_*` methods.
+    fn try_scalar_multiplication(&self, scalar: T) -> Result<Self, MatrixError> {
+        Ok(self.scalar_multiplication(scalar))
+    }
+}
+
+/// Matrices stored as a single row-major `Vec`, whose storage can be borrowed as strided views.
+///
+/// The `view_*` arithmetic methods work on any views, such as sub-blocks of larger matrices,
+/// and panic with the corresponding [`MatrixError`] message if the shapes don't match.
+pub trait FlatMatrix<T: Element = f64>: Matrix<T> {
+    /// Wraps row-major `data`, panicking if its length is not `rows * cols`.
+    fn from_vec(data: Vec<T>, rows: usize, cols: usize) -> Self;
+
+    fn as_mut_slice(&mut self) -> &mut [T];
+
+    /// The block sizes and thread count of this matrix's operations. The blocked, multithread
+    /// and packed matrices can be given their own, the others use [`tuning::config`].
+    fn tuning(&self) -> TuningConfig {
+        tuning::config()
+    }
+
+    fn view(&self) -> MatrixView<'_, T> {
+        let data = self.as_slice().expect("flat matrices are contiguous");
+        MatrixView::from_row_major(data, self.num_rows(), self.num_cols())
+    }
+
+    fn view_mut(&mut self) -> MatrixViewMut<'_, T> {
+        let (rows, cols) = self.shape();
+        MatrixViewMut::from_row_major(self.as_mut_slice(), rows, cols)
+    }
+
+    /// Borrows the block covering `rows` x `cols` without copying it.
+    fn submatrix(&self, rows: Range<usize>, cols: Range<usize>) -> MatrixView<'_, T> {
+        self.view().submatrix(rows, cols)
+    }
+
+    fn submatrix_mut(&mut self, rows: Range<usize>, cols: Range<usize>) -> MatrixViewMut<'_, T> {
+        self.view_mut().into_submatrix(rows, cols)
+    }
+
+    /// Copies a view into a new matrix.
+    fn from_view(view: &MatrixView<T>) -> Self {
+        Self::from_vec(view.to_vec(), view.num_rows(), view.num_cols())
+    }
+
+    fn view_addition(a: &MatrixView<T>, b: &MatrixView<T>) -> Self {
+        if let Err(e) = check_same_shape(a.shape(), b.shape()) {
+            panic!("{}", e);
+        }
+        Self::from_vec(
+            zip_map_views(a, b, |x, y| x + y),
+            a.num_rows(),
+            a.num_cols(),
+        )
+    }
+
+    fn view_subtraction(a: &MatrixView<T>, b: &MatrixView<T>) -> Self {
+        if let Err(e) = check_same_shape(a.shape(), b.shape()) {
+            panic!("{}", e);
+        }
+        Self::from_vec(
+            zip_map_views(a, b, |x, y| x - y),
+            a.num_rows(),
+            a.num_cols(),
+        )
+    }
+
+    fn view_multiplication(a: &MatrixView<T>, b: &MatrixView<T>) -> Self {
+        if let Err(e) = check_multipliable(a.shape(), b.shape()) {
+            panic!("{}", e);
+        }
+        Self::from_vec(multiply_views(a, b), a.num_rows(), b.num_cols())
+    }
+
+    /// Computes `c = alpha * a * b + beta * c` on views, so the output can be a block of a larger matrix.
+    fn view_gemm(
+        alpha: T,
+        a: &MatrixView<T>,
+        b: &MatrixView<T>,
+        beta: T,
+        c: &mut MatrixViewMut<T>,
+    ) {
+        if let Err(e) = check_gemm(a.shape(), b.shape(), c.shape()) {
+            panic!("{}", e);
+        }
+        gemm_views(alpha, a, b, beta, c);
+    }
+
+    fn view_scalar_multiplication(a: &MatrixView<T>, scalar: T) -> Self {
+        Self::from_vec(map_view(a, |x| x * scalar), a.num_rows(), a.num_cols())
+    }
+
+    /// Multiplies with Strassen's algorithm in Winograd's variant, which takes about `n^2.81`
+    /// multiply-adds instead of `n^3` at the cost of some accuracy. It recurses on quadrants while
+    /// every dimension is above the [`TuningConfig::strassen_cutoff`] of this matrix's
+    /// [`FlatMatrix::tuning`], then uses the blocked kernel. Needs signed elements, since it
+    /// subtracts entries.
+    fn strassen_multiplication(&self, other: &Self) -> Self
+    where
+        T: Neg<Output = T>,
+    {
+        if let Err(e) = check_multipliable(self.shape(), other.shape()) {
+            panic!("{}", e);
+        }
+        Self::from_vec(
+            strassen_multiply(&self.view(), &other.view(), &self.tuning()),
+            self.num_rows(),
+            other.num_cols(),
+        )
+    }
+
+    /// Like `strassen_multiplication`, with [`tuning::config`].
+    fn view_strassen_multiplication(a: &MatrixView<T>, b: &MatrixView<T>) -> Self
+    where
+        T: Neg<Output = T>,
+    {
+        if let Err(e) = check_multipliable(a.shape(), b.shape()) {
+            panic!("{}", e);
+        }
+        Self::from_vec(
+            strassen_multiply(a, b, &tuning::config()),
+            a.num_rows(),
+            b.num_cols(),
+        )
+    }
 }
 
+pub mod blas;
+mod element;
+mod error;
+mod gemm;
+pub mod level1;
+pub mod linalg;
 pub mod matrices;
+pub mod parallel;
+mod reductions;
+pub mod simd;
+mod strassen;
+pub mod tuning;
+mod vector;
+mod view;
@@ -0,0 +1,209 @@
+use crate::error::{check_multipliable, check_square};
+use crate::linalg::check_symmetric;
+use crate::parallel;
+use crate::{FlatMatrix, FloatElement, MatrixError};
+use std::cmp::min;
+use std::marker::PhantomData;
+
+/// Cholesky factorization of a symmetric positive-definite matrix, `A = L * L^T`.
+#[derive(Debug)]
+pub struct Cholesky<T, M> {
+    l: M,
+    _element: PhantomData<T>,
+}
+
+impl<T: FloatElement, M: FlatMatrix<T>> Cholesky<T, M> {
+    /// Factors a row-major copy of `matrix` in place with `factor`, after checking that it is
+    /// square and symmetric.
+    pub(crate) fn factor_with(
+        matrix: &M,
+        factor: impl FnOnce(&mut [T], usize) -> Result<(), MatrixError>,
+    ) -> Result<Self, MatrixError> {
+        check_square(matrix.shape())?;
+        let n = matrix.num_rows();
+        let mut data = matrix.row_iter().flatten().copied().collect::<Vec<_>>();
+        check_symmetric(&data, n)?;
+        factor(&mut data, n)?;
+
+        // Only the lower triangle is written, so clear the copy of A above it
+        for i in 0..n {
+            data[i * n + i + 1..(i + 1) * n].fill(T::zero());
+        }
+
+        Ok(Self {
+            l: M::from_vec(data, n, n),
+            _element: PhantomData,
+        })
+    }
+
+    /// The lower triangular factor.
+    pub fn l(&self) -> &M {
+        &self.l
+    }
+
+    pub fn into_l(self) -> M {
+        self.l
+    }
+
+    /// Solves `A * x = b` for `x`, where `b` can have several columns.
+    pub fn solve(&self, b: &M) -> Result<M, MatrixError> {
+        check_multipliable(self.l.shape(), b.shape())?;
+        let (n, cols) = b.shape();
+        let l = self.l.as_slice().expect("flat matrices are contiguous");
+        let mut x = b.row_iter().flatten().copied().collect::<Vec<_>>();
+
+        // L * y = b
+        for i in 0..n {
+            let (solved, rest) = x.split_at_mut(i * cols);
+            let row = &mut rest[..cols];
+            for k in 0..i {
+                let l_ik = l[i * n + k];
+                for (x_ij, &x_kj) in row.iter_mut().zip(&solved[k * cols..(k + 1) * cols]) {
+                    *x_ij -= l_ik * x_kj;
+                }
+            }
+            let l_ii = l[i * n + i];
+            row.iter_mut().for_each(|x_ij| *x_ij /= l_ii);
+        }
+
+        // L^T * x = y, going through the rows of L instead of its columns
+        for i in (0..n).rev() {
+            let (head, rest) = x.split_at_mut(i * cols);
+            let row = &mut rest[..cols];
+            let l_ii = l[i * n + i];
+            row.iter_mut().for_each(|x_ij| *x_ij /= l_ii);
+            for k in 0..i {
+                let l_ik = l[i * n + k];
+                for (x_kj, &x_ij) in head[k * cols..(k + 1) * cols].iter_mut().zip(row.iter()) {
+                    *x_kj -= l_ik * x_ij;
+                }
+            }
+        }
+
+        Ok(M::from_vec(x, n, cols))
+    }
+
+    pub fn determinant(&self) -> T {
+        (0..self.l.num_rows()).fold(T::one(), |det, i| {
+            let l_ii = self.l.get(i, i);
+            det * l_ii * l_ii
+        })
+    }
+
+    /// `ln(det(A))`, which stays finite where the determinant itself would under- or overflow.
+    pub fn log_determinant(&self) -> T {
+        let sum = (0..self.l.num_rows()).fold(T::zero(), |sum, i| sum + self.l.get(i, i).ln());
+        sum + sum
+    }
+}
+
+/// Unblocked Cholesky of the lower triangle of the row-major `n x n` matrix `a`, in place. Each
+/// entry is a dot product of two row prefixes, so the inner loops are sequential.
+pub(crate) fn cholesky_in_place<T: FloatElement>(a: &mut [T], n: usize) -> Result<(), MatrixError> {
+    factor_diagonal_block(a, n, 0, n)
+}
+
+/// Right-looking blocked Cholesky of the lower triangle of the row-major `n x n` matrix `a`.
+///
+/// For each block column, the diagonal block is factored, the rows below it are solved against
+/// it, and the trailing matrix is updated with the new panel. The last two steps work row by row
+/// and are split across `num_threads` threads.
+pub(crate) fn blocked_cholesky_in_place<T: FloatElement>(
+    a: &mut [T],
+    n: usize,
+    block_size: usize,
+    num_threads: usize,
+) -> Result<(), MatrixError> {
+    for kb in (0..n).step_by(block_size) {
+        let kb_end = min(kb + block_size, n);
+        factor_diagonal_block(a, n, kb, kb_end)?;
+
+        let (head, tail) = a.split_at_mut(kb_end * n);
+        if tail.is_empty() {
+            break;
+        }
+
+        // L21 = A21 * L11^-T
+        let l11 = &head[kb * n..];
+        for_row_chunks(tail, n, num_threads, |_, rows| {
+            for row in rows.chunks_mut(n) {
+                for j in kb..kb_end {
+                    let l_j = &l11[(j - kb) * n..(j - kb) * n + j];
+                    let dot = (kb..j).fold(T::zero(), |dot, k| dot + row[k] * l_j[k]);
+                    row[j] = (row[j] - dot) / l11[(j - kb) * n + j];
+                }
+            }
+        });
+
+        // A22 -= L21 * L21^T, lower triangle only. The panel is copied so every thread can read
+        // all of it while updating its own rows.
+        let width = kb_end - kb;
+        let panel = tail
+            .chunks(n)
+            .flat_map(|row| row[kb..kb_end].iter().copied())
+            .collect::<Vec<_>>();
+        for_row_chunks(tail, n, num_threads, |first_row, rows| {
+            for (r, row) in rows.chunks_mut(n).enumerate() {
+                let i = first_row + r;
+                let l_i = &panel[i * width..(i + 1) * width];
+                for (j, a_ij) in row[kb_end..=kb_end + i].iter_mut().enumerate() {
+                    let l_j = &panel[j * width..(j + 1) * width];
+                    let dot = l_i
+                        .iter()
+                        .zip(l_j)
+                        .fold(T::zero(), |dot, (&x, &y)| dot + x * y);
+                    *a_ij -= dot;
+                }
+            }
+        });
+    }
+
+    Ok(())
+}
+
+/// Factors the diagonal block covering rows and columns `start..end`, assuming the updates from
+/// the columns before `start` have already been applied.
+fn factor_diagonal_block<T: FloatElement>(
+    a: &mut [T],
+    n: usize,
+    start: usize,
+    end: usize,
+) -> Result<(), MatrixError> {
+    for j in start..end {
+        let dot = (start..j).fold(T::zero(), |dot, k| dot + a[j * n + k] * a[j * n + k]);
+        let d = a[j * n + j] - dot;
+        if d.is_nan() || d <= T::zero() {
+            return Err(MatrixError::NotPositiveDefinite { pivot: j });
+        }
+        let l_jj = d.sqrt();
+        a[j * n + j] = l_jj;
+
+        for i in j + 1..end {
+            let dot = (start..j).fold(T::zero(), |dot, k| dot + a[i * n + k] * a[j * n + k]);
+            a[i * n + j] = (a[i * n + j] - dot) / l_jj;
+        }
+    }
+    Ok(())
+}
+
+/// Calls `f` on `num_threads` bands of whole rows of the row-major matrix `rows`, on the shared
+/// pool of that many threads. `f` also gets the index of the band's first row.
+fn for_row_chunks<T: Send>(
+    rows: &mut [T],
+    n: usize,
+    num_threads: usize,
+    f: impl Fn(usize, &mut [T]) + Sync,
+) {
+    let num_rows = rows.len() / n;
+    if num_threads <= 1 || num_rows <= 1 {
+        f(0, rows);
+        return;
+    }
+
+    let rows_per_chunk = num_rows.div_ceil(num_threads);
+    let parallel = parallel::config().with_threads(num_threads);
+    parallel.for_each(
+        rows.chunks_mut(rows_per_chunk * n).enumerate(),
+        |(chunk_index, chunk)| f(chunk_index * rows_per_chunk, chunk),
+    );
+}
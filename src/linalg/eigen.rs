@@ -0,0 +1,430 @@
+use crate::error::check_square;
+use crate::linalg::check_symmetric;
+use crate::linalg::svd::rotate_rows;
+use crate::{Complex, FloatElement, Matrix, MatrixError};
+use std::cmp::Ordering;
+use std::marker::PhantomData;
+
+/// QR iterations allowed per eigenvalue before giving up, as in EISPACK.
+const MAX_ITERATIONS: usize = 30;
+
+/// Eigendecomposition of a real symmetric matrix, `A = Q * Λ * Q^T`.
+#[derive(Debug)]
+pub struct SymmetricEigen<T, M> {
+    eigenvalues: Vec<T>,
+    eigenvectors: M,
+    _element: PhantomData<T>,
+}
+
+impl<T: FloatElement, M: Matrix<T>> SymmetricEigen<T, M> {
+    /// The eigenvalues, in increasing order.
+    pub fn eigenvalues(&self) -> &[T] {
+        &self.eigenvalues
+    }
+
+    /// The orthogonal matrix `Q`, whose column `i` is the eigenvector of `eigenvalues()[i]`.
+    pub fn eigenvectors(&self) -> &M {
+        &self.eigenvectors
+    }
+
+    pub fn into_parts(self) -> (Vec<T>, M) {
+        (self.eigenvalues, self.eigenvectors)
+    }
+}
+
+/// Eigenvalue solvers, implemented for every backend by copying the elements into row-major
+/// storage.
+pub trait Eigen<T: FloatElement = f64>: Matrix<T> {
+    /// Eigenvalues and eigenvectors of a symmetric matrix, by Householder tridiagonalization and
+    /// implicit QR with Wilkinson shifts. Fails unless the matrix is square and symmetric.
+    fn symmetric_eigen(&self) -> Result<SymmetricEigen<T, Self>, MatrixError> {
+        check_square(self.shape())?;
+        let n = self.num_rows();
+        let mut a = self.row_iter().flatten().copied().collect::<Vec<_>>();
+        check_symmetric(&a, n)?;
+
+        // Z = Q^T, so that the rotations act on its rows
+        let mut z = vec![T::zero(); n * n];
+        for i in 0..n {
+            z[i * n + i] = T::one();
+        }
+        let (mut d, mut e) = tridiagonalize(&mut a, &mut z, n);
+        tridiagonal_qr(&mut d, &mut e, &mut z, n)?;
+
+        let mut order = (0..n).collect::<Vec<_>>();
+        order.sort_by(|&i, &j| d[i].partial_cmp(&d[j]).unwrap_or(Ordering::Equal));
+        let eigenvalues = order.iter().map(|&i| d[i]).collect();
+        let eigenvectors = (0..n)
+            .map(|row| order.iter().map(|&i| z[i * n + row]).collect())
+            .collect();
+
+        Ok(SymmetricEigen {
+            eigenvalues,
+            eigenvectors: Self::new(eigenvectors),
+            _element: PhantomData,
+        })
+    }
+
+    /// Eigenvalues of a general square matrix, by Householder reduction to Hessenberg form and
+    /// Francis double-shift QR.
+    ///
+    /// Complex eigenvalues come in adjacent conjugate pairs, the one with positive imaginary part
+    /// first. Fails if the matrix is not square or the iteration does not converge.
+    fn eigenvalues(&self) -> Result<Vec<Complex<T>>, MatrixError> {
+        check_square(self.shape())?;
+        let n = self.num_rows();
+        let mut a = self.row_iter().flatten().copied().collect::<Vec<_>>();
+        hessenberg(&mut a, n);
+        hessenberg_qr(&mut a, n)
+    }
+}
+
+impl<T: FloatElement, M: Matrix<T>> Eigen<T> for M {}
+
+/// Householder vector of `x`: `v` with `v[0] = 1` and `tau` such that
+/// `(I - tau * v * v^T) * x = beta * e_0`. `tau` is zero if `x` is already a multiple of `e_0`.
+fn householder_vector<T: FloatElement>(x: &[T]) -> (Vec<T>, T, T) {
+    let alpha = x[0];
+    let tail_norm = x[1..].iter().fold(T::zero(), |sum, &x_i| sum.hypot(x_i));
+    let mut v = vec![T::zero(); x.len()];
+    v[0] = T::one();
+    if tail_norm == T::zero() {
+        return (v, T::zero(), alpha);
+    }
+
+    let beta = -alpha.signum() * alpha.hypot(tail_norm);
+    let scale = T::one() / (alpha - beta);
+    for (v_i, &x_i) in v[1..].iter_mut().zip(&x[1..]) {
+        *v_i = x_i * scale;
+    }
+    (v, (beta - alpha) / beta, beta)
+}
+
+/// Reduces the symmetric row-major `a` to tridiagonal form `Q^T * A * Q`, applying each reflector
+/// to the rows of `z` as well. Returns the diagonal and the subdiagonal.
+fn tridiagonalize<T: FloatElement>(a: &mut [T], z: &mut [T], n: usize) -> (Vec<T>, Vec<T>) {
+    let at = |i: usize, j: usize| i * n + j;
+    let mut e = vec![T::zero(); n.saturating_sub(1)];
+
+    for k in 0..n.saturating_sub(2) {
+        let x = (k + 1..n).map(|i| a[at(i, k)]).collect::<Vec<_>>();
+        let (v, tau, beta) = householder_vector(&x);
+        e[k] = beta;
+        if tau == T::zero() {
+            continue;
+        }
+
+        // S = H * S * H for the trailing block S, as S - v * w^T - w * v^T with
+        // p = tau * S * v and w = p - (tau / 2) * (p^T * v) * v
+        let size = n - k - 1;
+        let mut p = (0..size)
+            .map(|i| {
+                let row = &a[at(k + 1 + i, k + 1)..at(k + 1 + i, n)];
+                tau * row
+                    .iter()
+                    .zip(&v)
+                    .fold(T::zero(), |sum, (&s, &v_j)| sum + s * v_j)
+            })
+            .collect::<Vec<_>>();
+        let half = tau
+            * p.iter()
+                .zip(&v)
+                .fold(T::zero(), |sum, (&p_i, &v_i)| sum + p_i * v_i)
+            / (T::one() + T::one());
+        for (p_i, &v_i) in p.iter_mut().zip(&v) {
+            *p_i -= half * v_i;
+        }
+        for i in 0..size {
+            for j in 0..size {
+                a[at(k + 1 + i, k + 1 + j)] -= v[i] * p[j] + p[i] * v[j];
+            }
+        }
+
+        // Z = H * Z on rows k + 1..
+        for c in 0..n {
+            let dot = (0..size).fold(T::zero(), |sum, i| sum + v[i] * z[at(k + 1 + i, c)]);
+            let scale = tau * dot;
+            for i in 0..size {
+                z[at(k + 1 + i, c)] -= scale * v[i];
+            }
+        }
+    }
+    if n >= 2 {
+        e[n - 2] = a[at(n - 1, n - 2)];
+    }
+
+    ((0..n).map(|i| a[at(i, i)]).collect(), e)
+}
+
+/// Implicit symmetric QR on the tridiagonal matrix with diagonal `d` and subdiagonal `e`, until
+/// it is diagonal. The Givens rotations are applied to the rows of the `n x n` matrix `z`.
+fn tridiagonal_qr<T: FloatElement>(
+    d: &mut [T],
+    e: &mut [T],
+    z: &mut [T],
+    n: usize,
+) -> Result<(), MatrixError> {
+    let negligible = |e_i: T, d_i: T, d_j: T| {
+        e_i.abs() <= T::epsilon() * (d_i.abs() + d_j.abs()) || e_i.abs() < T::min_positive_value()
+    };
+
+    let mut iterations = 0;
+    let mut m = n.saturating_sub(1);
+    while m > 0 {
+        if negligible(e[m - 1], d[m - 1], d[m]) {
+            e[m - 1] = T::zero();
+            m -= 1;
+            continue;
+        }
+        iterations += 1;
+        if iterations > MAX_ITERATIONS * n {
+            return Err(MatrixError::NotConverged);
+        }
+
+        // The unreduced block l..=m
+        let mut l = m - 1;
+        while l > 0 && !negligible(e[l - 1], d[l - 1], d[l]) {
+            l -= 1;
+        }
+
+        // Wilkinson shift, the eigenvalue of the trailing 2x2 block closer to d[m]
+        let delta = (d[m - 1] - d[m]) / (T::one() + T::one());
+        let e_m = e[m - 1];
+        let mu = d[m] - e_m * e_m / (delta + delta.signum() * delta.hypot(e_m));
+
+        // Chase the bulge created by the first rotation down to the bottom of the block
+        let mut x = d[l] - mu;
+        let mut bulge = e[l];
+        for k in l..m {
+            let r = x.hypot(bulge);
+            let (c, s) = if r == T::zero() {
+                (T::one(), T::zero())
+            } else {
+                (x / r, bulge / r)
+            };
+            if k > l {
+                e[k - 1] = r;
+            }
+
+            let (a, b, dd) = (d[k], e[k], d[k + 1]);
+            let cs = c * s;
+            d[k] = c * c * a + (cs + cs) * b + s * s * dd;
+            d[k + 1] = s * s * a - (cs + cs) * b + c * c * dd;
+            e[k] = cs * (dd - a) + (c * c - s * s) * b;
+            if k + 1 < m {
+                bulge = s * e[k + 1];
+                e[k + 1] = c * e[k + 1];
+                x = e[k];
+            }
+            rotate_rows(z, n, k, k + 1, c, -s);
+        }
+    }
+    Ok(())
+}
+
+/// Reduces the row-major `a` to upper Hessenberg form `Q^T * A * Q` in place.
+fn hessenberg<T: FloatElement>(a: &mut [T], n: usize) {
+    let at = |i: usize, j: usize| i * n + j;
+    for k in 0..n.saturating_sub(2) {
+        let x = (k + 1..n).map(|i| a[at(i, k)]).collect::<Vec<_>>();
+        let (v, tau, beta) = householder_vector(&x);
+        if tau == T::zero() {
+            continue;
+        }
+        a[at(k + 1, k)] = beta;
+        for i in k + 2..n {
+            a[at(i, k)] = T::zero();
+        }
+
+        // A = H * A on rows k + 1.., then A = A * H on columns k + 1..
+        for c in k + 1..n {
+            let dot = (0..v.len()).fold(T::zero(), |sum, i| sum + v[i] * a[at(k + 1 + i, c)]);
+            let scale = tau * dot;
+            for (i, &v_i) in v.iter().enumerate() {
+                a[at(k + 1 + i, c)] -= scale * v_i;
+            }
+        }
+        for row in a.chunks_mut(n) {
+            let tail = &mut row[k + 1..];
+            let scale = tau
+                * tail
+                    .iter()
+                    .zip(&v)
+                    .fold(T::zero(), |sum, (&a_j, &v_j)| sum + a_j * v_j);
+            for (a_j, &v_j) in tail.iter_mut().zip(&v) {
+                *a_j -= scale * v_j;
+            }
+        }
+    }
+}
+
+/// Eigenvalues of the upper Hessenberg row-major `a` by Francis double-shift QR, deflating 1x1 and
+/// 2x2 blocks off the bottom, following EISPACK's `hqr`.
+fn hessenberg_qr<T: FloatElement>(a: &mut [T], n: usize) -> Result<Vec<Complex<T>>, MatrixError> {
+    let at = |i: usize, j: usize| i * n + j;
+    let two = T::one() + T::one();
+    let sign = |x: T, sign_of: T| {
+        if sign_of >= T::zero() {
+            x.abs()
+        } else {
+            -x.abs()
+        }
+    };
+
+    let mut eigenvalues = vec![Complex::new(T::zero(), T::zero()); n];
+    let norm = (0..n)
+        .flat_map(|i| (i.saturating_sub(1)..n).map(move |j| at(i, j)))
+        .fold(T::zero(), |sum, index| sum + a[index].abs());
+    // Accumulated exceptional shifts
+    let mut shift = T::zero();
+    let mut iterations = 0;
+    let mut hi = n;
+
+    while hi > 0 {
+        let nn = hi - 1;
+
+        // Look for a negligible subdiagonal entry, splitting off the block l..=nn
+        let mut l = nn;
+        while l > 0 {
+            let mut s = a[at(l - 1, l - 1)].abs() + a[at(l, l)].abs();
+            if s == T::zero() {
+                s = norm;
+            }
+            if a[at(l, l - 1)].abs() <= T::epsilon() * s {
+                a[at(l, l - 1)] = T::zero();
+                break;
+            }
+            l -= 1;
+        }
+
+        let mut x = a[at(nn, nn)];
+        if l == nn {
+            eigenvalues[nn] = Complex::new(x + shift, T::zero());
+            hi -= 1;
+            iterations = 0;
+            continue;
+        }
+
+        let mut y = a[at(nn - 1, nn - 1)];
+        let mut w = a[at(nn, nn - 1)] * a[at(nn - 1, nn)];
+        if l == nn - 1 {
+            // The eigenvalues of the trailing 2x2 block
+            let p = (y - x) / two;
+            let q = p * p + w;
+            let z = q.abs().sqrt();
+            x += shift;
+            if q >= T::zero() {
+                let z = p + sign(z, p);
+                let lower = if z != T::zero() { x - w / z } else { x + z };
+                eigenvalues[nn - 1] = Complex::new(x + z, T::zero());
+                eigenvalues[nn] = Complex::new(lower, T::zero());
+            } else {
+                eigenvalues[nn - 1] = Complex::new(x + p, z);
+                eigenvalues[nn] = Complex::new(x + p, -z);
+            }
+            hi -= 2;
+            iterations = 0;
+            continue;
+        }
+
+        if iterations == MAX_ITERATIONS {
+            return Err(MatrixError::NotConverged);
+        }
+        if iterations == 10 || iterations == 20 {
+            // Exceptional shift, to break cycles
+            shift += x;
+            for i in 0..=nn {
+                a[at(i, i)] -= x;
+            }
+            let s = a[at(nn, nn - 1)].abs() + a[at(nn - 1, nn - 2)].abs();
+            x = T::from(0.75).unwrap() * s;
+            y = x;
+            w = T::from(-0.4375).unwrap() * s * s;
+        }
+        iterations += 1;
+
+        // Look for two consecutive small subdiagonal entries, to start the step at row m
+        let (mut p, mut q, mut r);
+        let mut m = nn - 2;
+        loop {
+            let z = a[at(m, m)];
+            let (rx, sy) = (x - z, y - z);
+            p = (rx * sy - w) / a[at(m + 1, m)] + a[at(m, m + 1)];
+            q = a[at(m + 1, m + 1)] - z - rx - sy;
+            r = a[at(m + 2, m + 1)];
+            let s = p.abs() + q.abs() + r.abs();
+            p /= s;
+            q /= s;
+            r /= s;
+            if m == l {
+                break;
+            }
+            let u = a[at(m, m - 1)].abs() * (q.abs() + r.abs());
+            let v = p.abs() * (a[at(m - 1, m - 1)].abs() + z.abs() + a[at(m + 1, m + 1)].abs());
+            if u <= T::epsilon() * v {
+                break;
+            }
+            m -= 1;
+        }
+        for i in m + 2..=nn {
+            a[at(i, i - 2)] = T::zero();
+            if i != m + 2 {
+                a[at(i, i - 3)] = T::zero();
+            }
+        }
+
+        // Double-shift QR step on rows and columns l..=nn, chasing the bulge with 3x3 reflectors
+        for k in m..nn {
+            let last = k == nn - 1;
+            if k != m {
+                p = a[at(k, k - 1)];
+                q = a[at(k + 1, k - 1)];
+                r = if last { T::zero() } else { a[at(k + 2, k - 1)] };
+                x = p.abs() + q.abs() + r.abs();
+                if x != T::zero() {
+                    p /= x;
+                    q /= x;
+                    r /= x;
+                }
+            }
+            let s = sign((p * p + q * q + r * r).sqrt(), p);
+            if s == T::zero() {
+                continue;
+            }
+            if k == m {
+                if l != m {
+                    a[at(k, k - 1)] = -a[at(k, k - 1)];
+                }
+            } else {
+                a[at(k, k - 1)] = -s * x;
+            }
+            p += s;
+            x = p / s;
+            y = q / s;
+            let z = r / s;
+            q /= p;
+            r /= p;
+
+            for j in k..=nn {
+                let mut p = a[at(k, j)] + q * a[at(k + 1, j)];
+                if !last {
+                    p += r * a[at(k + 2, j)];
+                    a[at(k + 2, j)] -= p * z;
+                }
+                a[at(k + 1, j)] -= p * y;
+                a[at(k, j)] -= p * x;
+            }
+            for i in l..=nn.min(k + 3) {
+                let mut p = x * a[at(i, k)] + y * a[at(i, k + 1)];
+                if !last {
+                    p += z * a[at(i, k + 2)];
+                    a[at(i, k + 2)] -= p * r;
+                }
+                a[at(i, k + 1)] -= p * q;
+                a[at(i, k)] -= p;
+            }
+        }
+    }
+
+    Ok(eigenvalues)
+}
@@ -0,0 +1,260 @@
+use crate::error::{check_multipliable, check_square};
+use crate::{FlatMatrix, FloatElement, MatrixError};
+use std::cmp::min;
+use std::marker::PhantomData;
+
+/// LU factorization with partial pivoting, `P * A = L * U`.
+///
+/// `L` (unit lower triangular) and `U` are packed into a single matrix. Row `i` of `P * A`
+/// is row `permutation()[i]` of `A`.
+#[derive(Debug)]
+pub struct Lu<T, M> {
+    lu: M,
+    permutation: Vec<usize>,
+    swaps: usize,
+    _element: PhantomData<T>,
+}
+
+impl<T: FloatElement, M: FlatMatrix<T>> Lu<T, M> {
+    /// Factors a row-major copy of `matrix` in place with `factor`, which records the row order
+    /// in its last argument and returns the number of row swaps.
+    pub(crate) fn factor_with(
+        matrix: &M,
+        factor: impl FnOnce(&mut [T], usize, &mut [usize]) -> usize,
+    ) -> Result<Self, MatrixError> {
+        check_square(matrix.shape())?;
+        let n = matrix.num_rows();
+        let mut data = matrix.row_iter().flatten().copied().collect::<Vec<_>>();
+        let mut permutation = (0..n).collect::<Vec<_>>();
+        let swaps = factor(&mut data, n, &mut permutation);
+
+        Ok(Self::new(M::from_vec(data, n, n), permutation, swaps))
+    }
+
+    /// Wraps packed row-major factors.
+    pub(crate) fn new(lu: M, permutation: Vec<usize>, swaps: usize) -> Self {
+        Self {
+            lu,
+            permutation,
+            swaps,
+            _element: PhantomData,
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.lu.num_rows()
+    }
+
+    /// The unit lower triangular factor.
+    pub fn l(&self) -> M {
+        let n = self.size();
+        let mut data = vec![T::zero(); n * n];
+        for (i, row) in self.lu.row_iter().enumerate() {
+            data[i * n..i * n + i].copy_from_slice(&row[..i]);
+            data[i * n + i] = T::one();
+        }
+        M::from_vec(data, n, n)
+    }
+
+    /// The upper triangular factor.
+    pub fn u(&self) -> M {
+        let n = self.size();
+        let mut data = vec![T::zero(); n * n];
+        for (i, row) in self.lu.row_iter().enumerate() {
+            data[i * n + i..(i + 1) * n].copy_from_slice(&row[i..]);
+        }
+        M::from_vec(data, n, n)
+    }
+
+    pub fn permutation(&self) -> &[usize] {
+        &self.permutation
+    }
+
+    /// The permutation as a matrix `P`, so that `P * A = L * U`.
+    pub fn permutation_matrix(&self) -> M {
+        let n = self.size();
+        let mut data = vec![T::zero(); n * n];
+        for (i, &row) in self.permutation.iter().enumerate() {
+            data[i * n + row] = T::one();
+        }
+        M::from_vec(data, n, n)
+    }
+
+    /// Whether a pivot is zero to working precision, relative to the largest pivot.
+    pub fn is_singular(&self) -> bool {
+        let lu = self.lu.as_slice().expect("flat matrices are contiguous");
+        has_negligible_pivot(lu, self.size())
+    }
+
+    pub fn determinant(&self) -> T {
+        let sign = if self.swaps.is_multiple_of(2) {
+            T::one()
+        } else {
+            -T::one()
+        };
+        (0..self.size()).fold(sign, |det, i| det * self.lu.get(i, i))
+    }
+
+    /// Solves `A * x = b` for `x`, where `b` can have several columns.
+    pub fn solve(&self, b: &M) -> Result<M, MatrixError> {
+        check_multipliable(self.lu.shape(), b.shape())?;
+        if self.is_singular() {
+            return Err(MatrixError::Singular);
+        }
+
+        let (n, cols) = b.shape();
+        let mut x = self
+            .permutation
+            .iter()
+            .flat_map(|&row| b.row(row).iter().copied())
+            .collect::<Vec<_>>();
+        let lu = self.lu.as_slice().expect("flat matrices are contiguous");
+        lu_solve_in_place(lu, n, &mut x, cols);
+
+        Ok(M::from_vec(x, n, cols))
+    }
+
+    pub fn inverse(&self) -> Result<M, MatrixError> {
+        self.solve(&M::identity(self.size()))
+    }
+}
+
+/// Unblocked LU with partial pivoting of the row-major `n x n` matrix `a`, in place.
+pub(crate) fn lu_in_place<T: FloatElement>(
+    a: &mut [T],
+    n: usize,
+    permutation: &mut [usize],
+) -> usize {
+    factor_panel(a, n, 0, n, permutation)
+}
+
+/// Right-looking blocked LU: each panel of `block_size` columns is factored unblocked, then the
+/// rest of its rows are solved against it and the trailing matrix is updated tile by tile.
+pub(crate) fn blocked_lu_in_place<T: FloatElement>(
+    a: &mut [T],
+    n: usize,
+    block_size: usize,
+    permutation: &mut [usize],
+) -> usize {
+    let mut swaps = 0;
+
+    for kb in (0..n).step_by(block_size) {
+        let kb_end = min(kb + block_size, n);
+        swaps += factor_panel(a, n, kb, kb_end, permutation);
+
+        // U12 = L11^-1 * A12
+        for k in kb..kb_end {
+            for i in k + 1..kb_end {
+                let l_ik = a[i * n + k];
+                for j in kb_end..n {
+                    let u_kj = a[k * n + j];
+                    a[i * n + j] -= l_ik * u_kj;
+                }
+            }
+        }
+
+        // A22 -= L21 * U12
+        for bj in (kb_end..n).step_by(block_size) {
+            let bj_end = min(bj + block_size, n);
+            for i in kb_end..n {
+                for k in kb..kb_end {
+                    let l_ik = a[i * n + k];
+                    for j in bj..bj_end {
+                        let u_kj = a[k * n + j];
+                        a[i * n + j] -= l_ik * u_kj;
+                    }
+                }
+            }
+        }
+    }
+
+    swaps
+}
+
+/// Factors columns `start..end` of the row-major `n x n` matrix `a`, swapping whole rows but only
+/// updating the columns of the panel. Returns the number of row swaps.
+fn factor_panel<T: FloatElement>(
+    a: &mut [T],
+    n: usize,
+    start: usize,
+    end: usize,
+    permutation: &mut [usize],
+) -> usize {
+    let mut swaps = 0;
+
+    for k in start..end {
+        let mut pivot_row = k;
+        for i in k + 1..n {
+            if a[i * n + k].abs() > a[pivot_row * n + k].abs() {
+                pivot_row = i;
+            }
+        }
+        if pivot_row != k {
+            swap_rows(a, n, k, pivot_row);
+            permutation.swap(k, pivot_row);
+            swaps += 1;
+        }
+
+        // The rest of the column is zero too, so there is nothing to eliminate
+        let pivot = a[k * n + k];
+        if pivot == T::zero() {
+            continue;
+        }
+
+        for i in k + 1..n {
+            let l_ik = a[i * n + k] / pivot;
+            a[i * n + k] = l_ik;
+            for j in k + 1..end {
+                let u_kj = a[k * n + j];
+                a[i * n + j] -= l_ik * u_kj;
+            }
+        }
+    }
+
+    swaps
+}
+
+fn swap_rows<T>(a: &mut [T], n: usize, i: usize, j: usize) {
+    let (first, second) = (min(i, j), i.max(j));
+    let (head, tail) = a.split_at_mut(second * n);
+    head[first * n..(first + 1) * n].swap_with_slice(&mut tail[..n]);
+}
+
+/// Overwrites the row-major `n x cols` matrix `x`, holding the permuted right-hand sides, with the
+/// solution of `L * U * x = x` for packed row-major factors `lu`.
+pub(crate) fn lu_solve_in_place<T: FloatElement>(lu: &[T], n: usize, x: &mut [T], cols: usize) {
+    // Forward substitution with the unit lower triangle
+    for i in 1..n {
+        let (solved, rest) = x.split_at_mut(i * cols);
+        let row = &mut rest[..cols];
+        for k in 0..i {
+            let l_ik = lu[i * n + k];
+            for (x_ij, &x_kj) in row.iter_mut().zip(&solved[k * cols..(k + 1) * cols]) {
+                *x_ij -= l_ik * x_kj;
+            }
+        }
+    }
+
+    // Back substitution with the upper triangle
+    for i in (0..n).rev() {
+        let (head, solved) = x.split_at_mut((i + 1) * cols);
+        let row = &mut head[i * cols..];
+        for k in i + 1..n {
+            let u_ik = lu[i * n + k];
+            let x_k = &solved[(k - i - 1) * cols..(k - i) * cols];
+            for (x_ij, &x_kj) in row.iter_mut().zip(x_k) {
+                *x_ij -= u_ik * x_kj;
+            }
+        }
+        let u_ii = lu[i * n + i];
+        row.iter_mut().for_each(|x_ij| *x_ij /= u_ii);
+    }
+}
+
+/// Whether a diagonal entry of the `n x n` factor `lu` is negligible next to the largest one.
+/// The diagonal is in the same place for row-major and column-major storage.
+pub(crate) fn has_negligible_pivot<T: FloatElement>(lu: &[T], n: usize) -> bool {
+    let largest = (0..n).fold(T::zero(), |max, i| max.max(lu[i * n + i].abs()));
+    let tolerance = largest * T::epsilon() * T::from(n).unwrap();
+    (0..n).any(|i| lu[i * n + i].abs() <= tolerance)
+}
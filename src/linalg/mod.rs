@@ -0,0 +1,150 @@
+//! Factorizations of the flat matrices and the operations built on them.
+//!
+//! [`LinearAlgebra`] provides portable implementations working on the row-major storage, which
+//! backends override where their layout allows something faster, e.g. the blocked LU of
+//! `BlockedMatrix` or LAPACK's `getrf` and `geqrf` for `BlasMatrix`. The eigenvalue solvers of
+//! [`Eigen`] work on a row-major copy, so they are available for every backend.
+
+mod cholesky;
+mod eigen;
+mod lu;
+mod qr;
+mod svd;
+mod triangular;
+
+pub use cholesky::Cholesky;
+pub use eigen::{Eigen, SymmetricEigen};
+pub use lu::Lu;
+pub use qr::{PivotedQr, Qr};
+pub use svd::Svd;
+pub use triangular::{LowerTriangular, Triangle, UpperTriangular};
+
+pub(crate) use cholesky::blocked_cholesky_in_place;
+pub(crate) use lu::{blocked_lu_in_place, has_negligible_pivot};
+pub(crate) use triangular::has_zero_on_diagonal;
+
+use crate::error::{check_multipliable, check_square};
+use crate::{FlatMatrix, FloatElement, MatrixError};
+use cholesky::cholesky_in_place;
+use lu::lu_in_place;
+use triangular::substitute_in_place;
+
+/// Factorizations and solvers for the flat matrices with real floating-point elements.
+pub trait LinearAlgebra<T: FloatElement = f64>: FlatMatrix<T> {
+    /// LU factorization with partial pivoting, failing if the matrix is not square.
+    fn lu(&self) -> Result<Lu<T, Self>, MatrixError> {
+        Lu::factor_with(self, lu_in_place)
+    }
+
+    /// Fails only if the matrix is not square; a singular matrix has a determinant of zero.
+    fn determinant(&self) -> Result<T, MatrixError> {
+        Ok(self.lu()?.determinant())
+    }
+
+    fn inverse(&self) -> Result<Self, MatrixError> {
+        self.lu()?.inverse()
+    }
+
+    /// Solves `self * x = b` for `x`, where `b` can have several columns.
+    fn solve(&self, b: &Self) -> Result<Self, MatrixError> {
+        self.lu()?.solve(b)
+    }
+
+    /// Solves `T * x = b` for `x` by substitution, where `T` is the given triangle of `self` and
+    /// the other entries are ignored. Fails if `T` has a zero on its diagonal.
+    fn solve_triangular(&self, triangle: Triangle, b: &Self) -> Result<Self, MatrixError> {
+        check_square(self.shape())?;
+        check_multipliable(self.shape(), b.shape())?;
+        let (n, cols) = b.shape();
+        let a = self.as_slice().expect("flat matrices are contiguous");
+        if has_zero_on_diagonal(a, n) {
+            return Err(MatrixError::Singular);
+        }
+
+        let mut x = b.row_iter().flatten().copied().collect::<Vec<_>>();
+        substitute_in_place(a, n, triangle, &mut x, cols);
+        Ok(Self::from_vec(x, n, cols))
+    }
+
+    /// Householder QR factorization, `self = Q * R`.
+    fn qr(&self) -> Qr<T, Self> {
+        Qr::factor(self)
+    }
+
+    /// Householder QR factorization with column pivoting, `self * P = Q * R`.
+    fn qr_with_pivoting(&self) -> PivotedQr<T, Self> {
+        PivotedQr::factor(self)
+    }
+
+    /// Numerical rank, from QR with column pivoting.
+    fn rank(&self) -> usize {
+        self.qr_with_pivoting().rank()
+    }
+
+    /// Minimizes `||self * x - b||` for `x`, failing unless `self` has full column rank.
+    fn least_squares(&self, b: &Self) -> Result<Self, MatrixError> {
+        self.qr().least_squares(b)
+    }
+
+    /// Cholesky factorization `self = L * L^T`, failing unless the matrix is symmetric positive
+    /// definite.
+    fn cholesky(&self) -> Result<Cholesky<T, Self>, MatrixError> {
+        Cholesky::factor_with(self, cholesky_in_place)
+    }
+
+    /// Solves `self * x = b` for a symmetric positive-definite `self`, at about half the cost of
+    /// `solve`.
+    fn cholesky_solve(&self, b: &Self) -> Result<Self, MatrixError> {
+        self.cholesky()?.solve(b)
+    }
+
+    /// `ln(det(self))` of a symmetric positive-definite matrix, from its Cholesky factor.
+    fn log_determinant(&self) -> Result<T, MatrixError> {
+        Ok(self.cholesky()?.log_determinant())
+    }
+
+    /// Full singular value decomposition, with square `U` and `V^T`.
+    fn svd(&self) -> Svd<T, Self> {
+        Svd::factor(self, true)
+    }
+
+    /// Thin singular value decomposition, keeping only the `min(m, n)` singular vectors on each
+    /// side that have a singular value.
+    fn thin_svd(&self) -> Svd<T, Self> {
+        Svd::factor(self, false)
+    }
+
+    /// The `min(m, n)` singular values, largest first.
+    fn singular_values(&self) -> Vec<T> {
+        self.thin_svd().singular_values().to_vec()
+    }
+
+    /// Moore-Penrose pseudo-inverse, from the thin SVD.
+    fn pinv(&self) -> Self {
+        self.thin_svd().pinv()
+    }
+
+    /// Spectral norm, the largest singular value.
+    fn norm_2(&self) -> T {
+        self.thin_svd().norm_2()
+    }
+
+    /// 2-norm condition number, the ratio of the largest to the smallest singular value.
+    fn condition_number(&self) -> T {
+        self.thin_svd().condition_number()
+    }
+}
+
+/// Checks that `a` equals its transpose, up to rounding in how the two halves were computed.
+pub(crate) fn check_symmetric<T: FloatElement>(a: &[T], n: usize) -> Result<(), MatrixError> {
+    let tolerance = T::epsilon().sqrt();
+    for i in 0..n {
+        for j in 0..i {
+            let (a_ij, a_ji) = (a[i * n + j], a[j * n + i]);
+            if (a_ij - a_ji).abs() > tolerance * a_ij.abs().max(a_ji.abs()) {
+                return Err(MatrixError::NotSymmetric { index: (i, j) });
+            }
+        }
+    }
+    Ok(())
+}
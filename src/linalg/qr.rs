@@ -0,0 +1,311 @@
+use crate::error::check_same_rows;
+use crate::{FlatMatrix, FloatElement, MatrixError};
+use std::marker::PhantomData;
+
+/// Householder QR factorization, `A = Q * R`.
+///
+/// Stored in LAPACK's compact form: `R` on and above the diagonal and the Householder vectors,
+/// with an implicit leading one, below it. `H_k = I - tau_k * v_k * v_k^T` and `Q = H_0 * H_1 * ...`.
+#[derive(Debug)]
+pub struct Qr<T, M> {
+    qr: M,
+    tau: Vec<T>,
+    _element: PhantomData<T>,
+}
+
+/// Householder QR factorization with column pivoting, `A * P = Q * R`.
+///
+/// The diagonal of `R` is non-increasing in magnitude, which reveals the numerical rank. Column
+/// `j` of `A * P` is column `permutation()[j]` of `A`.
+#[derive(Debug)]
+pub struct PivotedQr<T, M> {
+    qr: Qr<T, M>,
+    permutation: Vec<usize>,
+}
+
+impl<T: FloatElement, M: FlatMatrix<T>> Qr<T, M> {
+    pub(crate) fn factor(matrix: &M) -> Self {
+        let (rows, cols) = matrix.shape();
+        let mut data = matrix.row_iter().flatten().copied().collect::<Vec<_>>();
+        let tau = householder_in_place(&mut data, rows, cols, None);
+        Self::new(M::from_vec(data, rows, cols), tau)
+    }
+
+    /// Wraps row-major factors in compact form.
+    pub(crate) fn new(qr: M, tau: Vec<T>) -> Self {
+        Self {
+            qr,
+            tau,
+            _element: PhantomData,
+        }
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        self.qr.shape()
+    }
+
+    /// The `m x k` orthonormal factor of the thin factorization, where `k = min(m, n)`.
+    pub fn q(&self) -> M {
+        let (rows, cols) = self.shape();
+        let k = rows.min(cols);
+        let mut q = vec![T::zero(); rows * k];
+        for i in 0..k {
+            q[i * k + i] = T::one();
+        }
+
+        // Q = H_0 * ... * H_{k-1} * I, applying the last reflector first
+        for j in (0..k).rev() {
+            self.apply_reflector(j, &mut q, k);
+        }
+        M::from_vec(q, rows, k)
+    }
+
+    /// The `k x n` upper triangular factor of the thin factorization, where `k = min(m, n)`.
+    pub fn r(&self) -> M {
+        let (rows, cols) = self.shape();
+        let k = rows.min(cols);
+        let mut r = vec![T::zero(); k * cols];
+        for (i, row) in self.qr.row_iter().take(k).enumerate() {
+            r[i * cols + i..(i + 1) * cols].copy_from_slice(&row[i..]);
+        }
+        M::from_vec(r, k, cols)
+    }
+
+    /// Minimizes `||A * x - b||` for `x`, where `b` can have several columns. Fails unless `A`
+    /// has full column rank; use [`PivotedQr::least_squares`] otherwise.
+    pub fn least_squares(&self, b: &M) -> Result<M, MatrixError> {
+        let cols = self.shape().1;
+        let rank = diagonal_rank(&self.qr);
+        if rank < cols {
+            return Err(MatrixError::RankDeficient {
+                rank,
+                expected: cols,
+            });
+        }
+        self.solve_leading(b, cols)
+    }
+
+    /// Applies `Q^T` to `b`, then solves the leading `rank x rank` block of `R` for the first
+    /// `rank` unknowns, leaving the others zero.
+    fn solve_leading(&self, b: &M, rank: usize) -> Result<M, MatrixError> {
+        check_same_rows(self.shape(), b.shape())?;
+        let (rows, cols) = self.shape();
+        let nrhs = b.num_cols();
+
+        let mut c = b.row_iter().flatten().copied().collect::<Vec<_>>();
+        for j in 0..rows.min(cols) {
+            self.apply_reflector(j, &mut c, nrhs);
+        }
+
+        let mut x = vec![T::zero(); cols * nrhs];
+        for i in (0..rank).rev() {
+            let r = self.qr.row(i);
+            for j in 0..nrhs {
+                let sum =
+                    (i + 1..rank).fold(c[i * nrhs + j], |sum, k| sum - r[k] * x[k * nrhs + j]);
+                x[i * nrhs + j] = sum / r[i];
+            }
+        }
+
+        Ok(M::from_vec(x, cols, nrhs))
+    }
+
+    /// Applies `H_j` to the rows `j..` of the row-major `m x cols` matrix `x`.
+    fn apply_reflector(&self, j: usize, x: &mut [T], cols: usize) {
+        let qr = self.qr.as_slice().expect("flat matrices are contiguous");
+        apply_reflector(qr, self.shape().1, j, self.tau[j], x, cols);
+    }
+}
+
+impl<T: FloatElement, M: FlatMatrix<T>> PivotedQr<T, M> {
+    pub(crate) fn factor(matrix: &M) -> Self {
+        let (rows, cols) = matrix.shape();
+        let mut data = matrix.row_iter().flatten().copied().collect::<Vec<_>>();
+        let mut permutation = (0..cols).collect::<Vec<_>>();
+        let tau = householder_in_place(&mut data, rows, cols, Some(&mut permutation));
+
+        Self {
+            qr: Qr::new(M::from_vec(data, rows, cols), tau),
+            permutation,
+        }
+    }
+
+    pub fn q(&self) -> M {
+        self.qr.q()
+    }
+
+    pub fn r(&self) -> M {
+        self.qr.r()
+    }
+
+    pub fn permutation(&self) -> &[usize] {
+        &self.permutation
+    }
+
+    /// The permutation as a matrix `P`, so that `A * P = Q * R`.
+    pub fn permutation_matrix(&self) -> M {
+        let n = self.permutation.len();
+        let mut data = vec![T::zero(); n * n];
+        for (j, &col) in self.permutation.iter().enumerate() {
+            data[col * n + j] = T::one();
+        }
+        M::from_vec(data, n, n)
+    }
+
+    /// Number of diagonal entries of `R` that are not negligible next to the largest one.
+    pub fn rank(&self) -> usize {
+        diagonal_rank(&self.qr.qr)
+    }
+
+    /// Minimizes `||A * x - b||` for `x`. If `A` is rank deficient, this is the basic solution
+    /// with zeros for the unknowns of the dependent columns.
+    pub fn least_squares(&self, b: &M) -> Result<M, MatrixError> {
+        let y = self.qr.solve_leading(b, self.rank())?;
+        let (cols, nrhs) = y.shape();
+
+        let mut x = vec![T::zero(); cols * nrhs];
+        for (row, &col) in y.row_iter().zip(&self.permutation) {
+            x[col * nrhs..(col + 1) * nrhs].copy_from_slice(row);
+        }
+        Ok(M::from_vec(x, cols, nrhs))
+    }
+}
+
+/// Householder QR of the row-major `rows x cols` matrix `a` in place, returning the `tau` of each
+/// reflector. With `permutation`, the remaining column of largest norm is moved into place first.
+pub(crate) fn householder_in_place<T: FloatElement>(
+    a: &mut [T],
+    rows: usize,
+    cols: usize,
+    mut permutation: Option<&mut [usize]>,
+) -> Vec<T> {
+    let k = rows.min(cols);
+    let mut tau = vec![T::zero(); k];
+
+    for j in 0..k {
+        if let Some(permutation) = permutation.as_deref_mut() {
+            // Norms are recomputed rather than downdated, which can lose all precision
+            let column_norm =
+                |c: usize| (j..rows).fold(T::zero(), |sum, i| sum.hypot(a[i * cols + c]));
+            let mut pivot = j;
+            let mut pivot_norm = column_norm(j);
+            for c in j + 1..cols {
+                let norm = column_norm(c);
+                if norm > pivot_norm {
+                    pivot = c;
+                    pivot_norm = norm;
+                }
+            }
+            if pivot != j {
+                for i in 0..rows {
+                    a.swap(i * cols + j, i * cols + pivot);
+                }
+                permutation.swap(j, pivot);
+            }
+        }
+
+        let alpha = a[j * cols + j];
+        let tail_norm = (j + 1..rows).fold(T::zero(), |sum, i| sum.hypot(a[i * cols + j]));
+        if tail_norm == T::zero() {
+            continue;
+        }
+
+        let beta = -alpha.signum() * alpha.hypot(tail_norm);
+        tau[j] = (beta - alpha) / beta;
+        let scale = T::one() / (alpha - beta);
+        for i in j + 1..rows {
+            a[i * cols + j] *= scale;
+        }
+        a[j * cols + j] = beta;
+
+        // Apply H_j to the trailing columns: w = v^T * A, A -= tau * v * w
+        let mut w = a[j * cols + j + 1..(j + 1) * cols].to_vec();
+        for i in j + 1..rows {
+            let v_i = a[i * cols + j];
+            for (w_c, &a_ic) in w.iter_mut().zip(&a[i * cols + j + 1..(i + 1) * cols]) {
+                *w_c += v_i * a_ic;
+            }
+        }
+        for i in j..rows {
+            let v_i = if i == j { T::one() } else { a[i * cols + j] };
+            let scale = tau[j] * v_i;
+            for (a_ic, &w_c) in a[i * cols + j + 1..(i + 1) * cols].iter_mut().zip(&w) {
+                *a_ic -= scale * w_c;
+            }
+        }
+    }
+
+    tau
+}
+
+/// Applies the reflector `H_j = I - tau * v_j * v_j^T`, stored in column `j` of the row-major
+/// compact factorization `qr` with `qr_cols` columns, to the rows `j..` of the matrix `x`.
+fn apply_reflector<T: FloatElement>(
+    qr: &[T],
+    qr_cols: usize,
+    j: usize,
+    tau: T,
+    x: &mut [T],
+    cols: usize,
+) {
+    if tau == T::zero() {
+        return;
+    }
+    let rows = qr.len() / qr_cols;
+    let v = |i: usize| {
+        if i == j {
+            T::one()
+        } else {
+            qr[i * qr_cols + j]
+        }
+    };
+
+    // w = v^T * x, then x -= tau * v * w
+    let mut w = x[j * cols..(j + 1) * cols].to_vec();
+    for i in j + 1..rows {
+        let v_i = v(i);
+        for (w_c, &x_ic) in w.iter_mut().zip(&x[i * cols..(i + 1) * cols]) {
+            *w_c += v_i * x_ic;
+        }
+    }
+    for i in j..rows {
+        let scale = tau * v(i);
+        for (x_ic, &w_c) in x[i * cols..(i + 1) * cols].iter_mut().zip(&w) {
+            *x_ic -= scale * w_c;
+        }
+    }
+}
+
+/// Orthonormal basis of the complement of the span of the `count` orthonormal columns of the
+/// row-major `len x count` matrix `basis`, as the columns of a `len x (len - count)` matrix.
+///
+/// These are the trailing columns of the full `Q` from a Householder QR of `basis`.
+pub(crate) fn orthogonal_complement<T: FloatElement>(
+    mut basis: Vec<T>,
+    len: usize,
+    count: usize,
+) -> Vec<T> {
+    let extra = len - count;
+    let mut complement = vec![T::zero(); len * extra];
+    for c in 0..extra {
+        complement[(count + c) * extra + c] = T::one();
+    }
+    if count == 0 {
+        return complement;
+    }
+
+    let tau = householder_in_place(&mut basis, len, count, None);
+    for j in (0..count).rev() {
+        apply_reflector(&basis, count, j, tau[j], &mut complement, extra);
+    }
+    complement
+}
+
+/// Number of diagonal entries of the packed `R` in `qr` above the rank-revealing tolerance.
+fn diagonal_rank<T: FloatElement, M: FlatMatrix<T>>(qr: &M) -> usize {
+    let (rows, cols) = qr.shape();
+    let diagonal = (0..rows.min(cols)).map(|i| qr.get(i, i).abs());
+    let largest = diagonal.clone().fold(T::zero(), T::max);
+    let tolerance = largest * T::epsilon() * T::from(rows.max(cols)).unwrap();
+    diagonal.filter(|&d| d > tolerance).count()
+}
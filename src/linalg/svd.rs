@@ -0,0 +1,230 @@
+use crate::level1::{self, Givens};
+use crate::linalg::qr::orthogonal_complement;
+use crate::matrices::transpose::transpose;
+use crate::{FlatMatrix, FloatElement};
+use std::cmp::Ordering;
+use std::marker::PhantomData;
+
+/// Sweeps of one-sided Jacobi after which the iteration gives up. Convergence is quadratic, so
+/// well under 20 are needed in practice.
+const MAX_SWEEPS: usize = 60;
+
+/// Singular value decomposition, `A = U * Σ * V^T`, with the singular values in decreasing order.
+///
+/// For an `m x n` matrix with `k = min(m, n)`, the thin factorization has an `m x k` `U` and a
+/// `k x n` `V^T`, and the full one has a square `m x m` `U` and `n x n` `V^T`. Either way there
+/// are `k` singular values, and `U` and `V` have orthonormal columns.
+#[derive(Debug)]
+pub struct Svd<T, M> {
+    u: M,
+    singular_values: Vec<T>,
+    vt: M,
+    _element: PhantomData<T>,
+}
+
+impl<T: FloatElement, M: FlatMatrix<T>> Svd<T, M> {
+    /// One-sided (Hestenes) Jacobi: rotates pairs of columns of `A`, or of rows if it is wide,
+    /// until they are orthogonal. Their norms are then the singular values, and normalizing them
+    /// gives the singular vectors on one side, while the accumulated rotations are the other side.
+    pub(crate) fn factor(matrix: &M, full: bool) -> Self {
+        let (rows, cols) = matrix.shape();
+        let tall = rows >= cols;
+        let (k, len) = if tall { (cols, rows) } else { (rows, cols) };
+
+        // The k vectors to orthogonalize, stored as rows: W = A^T if A is tall, otherwise W = A
+        let data = matrix.row_iter().flatten().copied().collect::<Vec<_>>();
+        let mut w = if tall {
+            transpose(&data, rows, cols)
+        } else {
+            data
+        };
+        let mut rotations = vec![T::zero(); k * k];
+        for i in 0..k {
+            rotations[i * k + i] = T::one();
+        }
+        one_sided_jacobi(&mut w, &mut rotations, k, len);
+
+        let norms = w
+            .chunks(len)
+            .map(|row| row.iter().fold(T::zero(), |norm, &x| norm.hypot(x)))
+            .collect::<Vec<_>>();
+        let mut order = (0..k).collect::<Vec<_>>();
+        order.sort_by(|&a, &b| norms[b].partial_cmp(&norms[a]).unwrap_or(Ordering::Equal));
+
+        let singular_values = order.iter().map(|&i| norms[i]).collect::<Vec<_>>();
+        let mut rotated = Vec::with_capacity(k * k);
+        let mut vectors = Vec::with_capacity(k * len);
+        for &i in &order {
+            rotated.extend_from_slice(&rotations[i * k..(i + 1) * k]);
+            let norm = norms[i];
+            if norm > T::zero() {
+                vectors.extend(w[i * len..(i + 1) * len].iter().map(|&x| x / norm));
+            } else {
+                vectors.extend(std::iter::repeat_n(T::zero(), len));
+            }
+        }
+        let count = if full { len } else { k };
+        complete_orthonormal_rows(&mut vectors, &singular_values, len, count);
+
+        // W = Y * A^T = Σ * N for a tall A, so A = N^T * Σ * Y, and A = Y^T * Σ * N otherwise
+        let (u, vt) = if tall {
+            (
+                M::from_vec(transpose(&vectors, count, len), rows, count),
+                M::from_vec(rotated, k, k),
+            )
+        } else {
+            (
+                M::from_vec(transpose(&rotated, k, k), k, k),
+                M::from_vec(vectors, count, cols),
+            )
+        };
+
+        Self {
+            u,
+            singular_values,
+            vt,
+            _element: PhantomData,
+        }
+    }
+
+    pub fn u(&self) -> &M {
+        &self.u
+    }
+
+    /// The singular values, largest first.
+    pub fn singular_values(&self) -> &[T] {
+        &self.singular_values
+    }
+
+    pub fn vt(&self) -> &M {
+        &self.vt
+    }
+
+    /// Number of singular values that are not negligible next to the largest one.
+    pub fn rank(&self) -> usize {
+        let tolerance = self.tolerance();
+        self.singular_values
+            .iter()
+            .filter(|&&s| s > tolerance)
+            .count()
+    }
+
+    /// The largest singular value.
+    pub fn norm_2(&self) -> T {
+        self.singular_values[0]
+    }
+
+    /// The ratio of the largest to the smallest singular value, infinite if `A` is rank
+    /// deficient.
+    pub fn condition_number(&self) -> T {
+        let smallest = self.singular_values[self.singular_values.len() - 1];
+        if smallest == T::zero() {
+            return T::infinity();
+        }
+        self.singular_values[0] / smallest
+    }
+
+    /// Moore-Penrose pseudo-inverse `V * Σ^+ * U^T`, inverting only the singular values above the
+    /// rank tolerance.
+    pub fn pinv(&self) -> M {
+        let (rows, cols) = (self.u.num_rows(), self.vt.num_cols());
+        let tolerance = self.tolerance();
+
+        let mut pinv = vec![T::zero(); cols * rows];
+        for (k, &s) in self.singular_values.iter().enumerate() {
+            if s <= tolerance {
+                break;
+            }
+            let v_k = self.vt.row(k);
+            for (i, pinv_row) in pinv.chunks_mut(rows).enumerate() {
+                let scale = v_k[i] / s;
+                for (j, p_ij) in pinv_row.iter_mut().enumerate() {
+                    *p_ij += scale * self.u.get(j, k);
+                }
+            }
+        }
+        M::from_vec(pinv, cols, rows)
+    }
+
+    fn tolerance(&self) -> T {
+        let size = self.u.num_rows().max(self.vt.num_cols());
+        self.singular_values[0] * T::epsilon() * T::from(size).unwrap()
+    }
+}
+
+/// Rotates pairs of the `k` rows of length `len` in `w` until they are mutually orthogonal,
+/// applying the same rotations to the rows of the `k x k` matrix `rotations`.
+fn one_sided_jacobi<T: FloatElement>(w: &mut [T], rotations: &mut [T], k: usize, len: usize) {
+    for _ in 0..MAX_SWEEPS {
+        let mut rotated = false;
+        for p in 0..k {
+            for q in p + 1..k {
+                let (alpha, beta, gamma) = {
+                    let (w_p, w_q) = (&w[p * len..(p + 1) * len], &w[q * len..(q + 1) * len]);
+                    w_p.iter().zip(w_q).fold(
+                        (T::zero(), T::zero(), T::zero()),
+                        |(alpha, beta, gamma), (&x, &y)| {
+                            (alpha + x * x, beta + y * y, gamma + x * y)
+                        },
+                    )
+                };
+                if gamma.abs() <= T::epsilon() * (alpha * beta).sqrt() {
+                    continue;
+                }
+                rotated = true;
+
+                // The rotation that zeroes the off-diagonal entry of [[alpha, gamma], [gamma, beta]]
+                let zeta = (beta - alpha) / (gamma + gamma);
+                let t = zeta.signum() / (zeta.abs() + (T::one() + zeta * zeta).sqrt());
+                let c = T::one() / (T::one() + t * t).sqrt();
+                let s = c * t;
+                rotate_rows(w, len, p, q, c, s);
+                rotate_rows(rotations, k, p, q, c, s);
+            }
+        }
+        if !rotated {
+            break;
+        }
+    }
+}
+
+/// `(x_p, x_q) = (c * x_p - s * x_q, s * x_p + c * x_q)` for rows `p < q` of the row-major `x`.
+pub(crate) fn rotate_rows<T: FloatElement>(
+    x: &mut [T],
+    cols: usize,
+    p: usize,
+    q: usize,
+    c: T,
+    s: T,
+) {
+    let (head, tail) = x.split_at_mut(q * cols);
+    let x_p = &mut head[p * cols..(p + 1) * cols];
+    let x_q = &mut tail[..cols];
+    level1::rot(x_p, x_q, Givens { c, s: -s });
+}
+
+/// Replaces the rows of `vectors` whose singular value is zero, and appends rows until there are
+/// `count`, so that the rows form an orthonormal set.
+fn complete_orthonormal_rows<T: FloatElement>(
+    vectors: &mut Vec<T>,
+    singular_values: &[T],
+    len: usize,
+    count: usize,
+) {
+    let known = singular_values
+        .iter()
+        .take_while(|&&s| s > T::zero())
+        .count();
+    if known == count {
+        return;
+    }
+
+    // Singular values are sorted, so the known vectors are the leading rows
+    let basis = transpose(&vectors[..known * len], known, len);
+    let complement = orthogonal_complement(basis, len, known);
+    let extra = len - known;
+    vectors.truncate(known * len);
+    for c in 0..count - known {
+        vectors.extend((0..len).map(|i| complement[i * extra + c]));
+    }
+}
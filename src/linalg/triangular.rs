@@ -0,0 +1,213 @@
+use crate::error::{check_matvec, check_multipliable, check_square};
+use crate::{FloatElement, LinearAlgebra, MatrixError, Vector};
+use std::marker::PhantomData;
+
+/// The triangle of a square matrix that holds the entries of a triangular matrix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Triangle {
+    Upper,
+    Lower,
+}
+
+impl Triangle {
+    /// Columns of row `i` inside the triangle, for a matrix with `cols` columns.
+    fn columns(self, i: usize, cols: usize) -> std::ops::Range<usize> {
+        match self {
+            Triangle::Upper => i..cols,
+            Triangle::Lower => 0..(i + 1).min(cols),
+        }
+    }
+}
+
+macro_rules! triangular_matrix {
+    ($name:ident, $triangle:expr, $transpose:ident, $doc:literal) => {
+        #[doc = $doc]
+        ///
+        /// Solves use substitution instead of a factorization, and products skip the zero triangle.
+        #[derive(Debug)]
+        pub struct $name<T, M> {
+            matrix: M,
+            _element: PhantomData<T>,
+        }
+
+        impl<T: FloatElement, M: LinearAlgebra<T>> $name<T, M> {
+            /// Keeps the triangle of `matrix`, setting the entries outside it to zero. Fails if
+            /// `matrix` is not square.
+            pub fn new(mut matrix: M) -> Result<Self, MatrixError> {
+                check_square(matrix.shape())?;
+                let n = matrix.num_rows();
+                for (i, row) in matrix.as_mut_slice().chunks_mut(n).enumerate() {
+                    let inside = $triangle.columns(i, n);
+                    row[..inside.start].fill(T::zero());
+                    row[inside.end..].fill(T::zero());
+                }
+                Ok(Self {
+                    matrix,
+                    _element: PhantomData,
+                })
+            }
+
+            pub fn matrix(&self) -> &M {
+                &self.matrix
+            }
+
+            pub fn into_inner(self) -> M {
+                self.matrix
+            }
+
+            pub fn size(&self) -> usize {
+                self.matrix.num_rows()
+            }
+
+            /// The product of the diagonal.
+            pub fn determinant(&self) -> T {
+                (0..self.size()).fold(T::one(), |det, i| det * self.matrix.get(i, i))
+            }
+
+            pub fn transpose(&self) -> $transpose<T, M> {
+                $transpose {
+                    matrix: self.matrix.transpose(),
+                    _element: PhantomData,
+                }
+            }
+
+            /// Solves `self * X = B` for `X` by substitution, where `B` can have several columns.
+            /// Fails if a diagonal entry is zero.
+            pub fn solve(&self, b: &M) -> Result<M, MatrixError> {
+                self.matrix.solve_triangular($triangle, b)
+            }
+
+            /// Solves `self * x = b` for a single right-hand side.
+            pub fn solve_vector(&self, b: &Vector<T>) -> Result<Vector<T>, MatrixError> {
+                check_matvec(self.matrix.shape(), b.len())?;
+                let b = M::from_vec(b.as_slice().to_vec(), b.len(), 1);
+                Ok(Vector::new(self.solve(&b)?.into_vec()))
+            }
+
+            /// Computes `self * other`, panicking if the dimensions don't match.
+            pub fn matrix_multiplication(&self, other: &M) -> M {
+                match self.try_matrix_multiplication(other) {
+                    Ok(product) => product,
+                    Err(e) => panic!("{}", e),
+                }
+            }
+
+            pub fn try_matrix_multiplication(&self, other: &M) -> Result<M, MatrixError> {
+                check_multipliable(self.matrix.shape(), other.shape())?;
+                let cols = other.num_cols();
+                let product = triangular_multiply(
+                    self.slice(),
+                    self.size(),
+                    $triangle,
+                    &other.row_iter().flatten().copied().collect::<Vec<_>>(),
+                    cols,
+                    false,
+                );
+                Ok(M::from_vec(product, self.size(), cols))
+            }
+
+            /// The product of two triangular matrices of the same kind, which is again one,
+            /// panicking if their sizes differ.
+            pub fn triangular_multiplication(&self, other: &Self) -> Self {
+                if let Err(e) = check_multipliable(self.matrix.shape(), other.matrix.shape()) {
+                    panic!("{}", e);
+                }
+                let n = self.size();
+                let product =
+                    triangular_multiply(self.slice(), n, $triangle, other.slice(), n, true);
+                Self {
+                    matrix: M::from_vec(product, n, n),
+                    _element: PhantomData,
+                }
+            }
+
+            fn slice(&self) -> &[T] {
+                self.matrix
+                    .as_slice()
+                    .expect("flat matrices are contiguous")
+            }
+        }
+    };
+}
+
+triangular_matrix!(
+    UpperTriangular,
+    Triangle::Upper,
+    LowerTriangular,
+    "A square matrix that is zero below its diagonal."
+);
+triangular_matrix!(
+    LowerTriangular,
+    Triangle::Lower,
+    UpperTriangular,
+    "A square matrix that is zero above its diagonal."
+);
+
+/// Solves `T * X = B` in place for the given triangle `T` of the row-major `n x n` matrix `a`,
+/// where `x` holds the row-major `n x cols` matrix `B`. The entries outside the triangle are never
+/// read.
+pub(crate) fn substitute_in_place<T: FloatElement>(
+    a: &[T],
+    n: usize,
+    triangle: Triangle,
+    x: &mut [T],
+    cols: usize,
+) {
+    let order = (0..n).map(|r| match triangle {
+        Triangle::Upper => n - 1 - r,
+        Triangle::Lower => r,
+    });
+    for i in order {
+        // Everything in the triangle except the diagonal is already solved
+        let solved = triangle.columns(i, n);
+        let solved = match triangle {
+            Triangle::Upper => solved.start + 1..solved.end,
+            Triangle::Lower => solved.start..solved.end - 1,
+        };
+        let mut row = x[i * cols..(i + 1) * cols].to_vec();
+        for k in solved {
+            let a_ik = a[i * n + k];
+            for (x_ij, &x_kj) in row.iter_mut().zip(&x[k * cols..(k + 1) * cols]) {
+                *x_ij -= a_ik * x_kj;
+            }
+        }
+        let a_ii = a[i * n + i];
+        for (x_ij, r) in x[i * cols..(i + 1) * cols].iter_mut().zip(row) {
+            *x_ij = r / a_ii;
+        }
+    }
+}
+
+/// Whether the diagonal of the row-major `n x n` matrix `a` has an exact zero.
+pub(crate) fn has_zero_on_diagonal<T: FloatElement>(a: &[T], n: usize) -> bool {
+    (0..n).any(|i| a[i * n + i] == T::zero())
+}
+
+/// Computes `T * B` for the given triangle `T` of the row-major `n x n` matrix `a` and the
+/// row-major `n x cols` matrix `b`. If `b` is itself triangular of the same kind, only its
+/// triangle is read.
+fn triangular_multiply<T: FloatElement>(
+    a: &[T],
+    n: usize,
+    triangle: Triangle,
+    b: &[T],
+    cols: usize,
+    b_triangular: bool,
+) -> Vec<T> {
+    let mut c = vec![T::zero(); n * cols];
+    for (i, c_row) in c.chunks_mut(cols).enumerate() {
+        for k in triangle.columns(i, n) {
+            let a_ik = a[i * n + k];
+            let b_cols = if b_triangular {
+                triangle.columns(k, cols)
+            } else {
+                0..cols
+            };
+            let b_row = &b[k * cols..(k + 1) * cols];
+            for (c_ij, &b_kj) in c_row[b_cols.clone()].iter_mut().zip(&b_row[b_cols]) {
+                *c_ij += a_ik * b_kj;
+            }
+        }
+    }
+    c
+}
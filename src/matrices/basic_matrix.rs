@@ -1,15 +1,17 @@
-use crate::Matrix;
+use crate::error::{assert_data, assert_index, check_same_shape};
+use crate::{Element, FloatElement, Matrix, Reductions};
+use std::ops::{Index, IndexMut};
 
 /// This is a naive implementation of a matrix with no optimizations.
 #[derive(Debug)]
-pub struct BasicMatrix {
-    data: Vec<Vec<f64>>,
+pub struct BasicMatrix<T = f64> {
+    data: Vec<Vec<T>>,
     shape: (usize, usize),
 }
 
-impl Matrix for BasicMatrix {
-    fn new(data: Vec<Vec<f64>>) -> Self {
-        let shape = (data.len(), data[0].len());
+impl<T: Element> Matrix<T> for BasicMatrix<T> {
+    fn new(data: Vec<Vec<T>>) -> Self {
+        let shape = assert_data(&data);
         Self { data, shape }
     }
 
@@ -25,21 +27,47 @@ impl Matrix for BasicMatrix {
         self.shape.1
     }
 
-    fn get_data(&self) -> Vec<Vec<f64>> {
+    fn get_data(&self) -> Vec<Vec<T>> {
         self.data.clone()
     }
 
+    fn as_slice(&self) -> Option<&[T]> {
+        None
+    }
+
+    fn row(&self, row: usize) -> &[T] {
+        &self.data[row]
+    }
+
+    fn into_vec(self) -> Vec<T> {
+        self.data.into_iter().flatten().collect()
+    }
+
+    fn into_rows(self) -> Vec<Vec<T>> {
+        self.data
+    }
+
+    fn get(&self, row: usize, col: usize) -> T {
+        assert_index((row, col), self.shape);
+        self.data[row][col]
+    }
+
+    fn get_mut(&mut self, row: usize, col: usize) -> &mut T {
+        assert_index((row, col), self.shape);
+        &mut self.data[row][col]
+    }
+
     fn zeroes(rows: usize, cols: usize) -> Self {
         Self {
-            data: vec![vec![0.0; cols]; rows],
+            data: vec![vec![T::zero(); cols]; rows],
             shape: (rows, cols),
         }
     }
 
     fn identity(size: usize) -> Self {
-        let mut data = vec![vec![0.0; size]; size];
-        for i in 0..size {
-            data[i][i] = 1.0;
+        let mut data = vec![vec![T::zero(); size]; size];
+        for (i, row) in data.iter_mut().enumerate() {
+            row[i] = T::one();
         }
         Self {
             data,
@@ -48,6 +76,9 @@ impl Matrix for BasicMatrix {
     }
 
     fn matrix_addition(&self, other: &Self) -> Self {
+        if let Err(e) = check_same_shape(self.shape(), other.shape()) {
+            panic!("{}", e);
+        }
         let self_rows = self.num_rows();
         let self_cols = self.num_cols();
 
@@ -63,6 +94,9 @@ impl Matrix for BasicMatrix {
     }
 
     fn matrix_subtraction(&self, other: &Self) -> Self {
+        if let Err(e) = check_same_shape(self.shape(), other.shape()) {
+            panic!("{}", e);
+        }
         let self_rows = self.num_rows();
         let self_cols = self.num_cols();
 
@@ -95,7 +129,7 @@ impl Matrix for BasicMatrix {
         res
     }
 
-    fn scalar_multiplication(&self, scalar: f64) -> Self {
+    fn scalar_multiplication(&self, scalar: T) -> Self {
         let self_rows = self.num_rows();
         let self_cols = self.num_cols();
 
@@ -110,3 +144,20 @@ impl Matrix for BasicMatrix {
         res
     }
 }
+
+impl<T: FloatElement> Reductions<T> for BasicMatrix<T> {}
+
+impl<T: Element> Index<(usize, usize)> for BasicMatrix<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        assert_index((row, col), self.shape);
+        &self.data[row][col]
+    }
+}
+
+impl<T: Element> IndexMut<(usize, usize)> for BasicMatrix<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        self.get_mut(row, col)
+    }
+}
@@ -1,70 +1,212 @@
-// Using Apple's Accelerate framework
+// Using the CBLAS provider selected at build time (see `crate::blas`)
 
-use crate::Matrix;
+use crate::error::{
+    assert_data, assert_index, check_gemm, check_matvec, check_multipliable, check_same_rows,
+    check_same_shape, check_square, check_vecmat,
+};
+use crate::linalg::{has_negligible_pivot, has_zero_on_diagonal};
+use crate::matrices::transpose::{conjugate_transpose, transpose};
+use crate::{
+    FlatMatrix, Givens, Level1, LinearAlgebra, Lu, Matrix, MatrixError, MatrixView, MatrixViewMut,
+    Qr, Reductions, Triangle, Vector,
+};
+use std::ops::{Index, IndexMut};
 
-#[repr(C)]
-#[derive(Debug, Copy, Clone)]
-pub enum CBlasLayout {
-    CblasRowMajor = 101,
-    CblasColMajor = 102,
-}
+pub use crate::blas::{
+    BlasElement, BlasLevel1Element, CBlasDiag, CBlasLayout, CBlasSide, CBlasTranspose, CBlasUplo,
+    LapackElement, cblas_dasum, cblas_daxpy, cblas_ddot, cblas_dgemm, cblas_dgemv, cblas_dnrm2,
+    cblas_drot, cblas_drotg, cblas_dscal, cblas_dtrsm, cblas_idamax, cblas_isamax, cblas_sasum,
+    cblas_saxpy, cblas_sdot, cblas_sgemm, cblas_sgemv, cblas_snrm2, cblas_srot, cblas_srotg,
+    cblas_sscal, cblas_strsm,
+};
+
+/// Transpose flag and leading dimension that describe `view` to gemm, if it has a unit stride.
+fn gemm_layout<T: BlasElement>(view: &MatrixView<T>) -> Option<(CBlasTranspose, usize)> {
+    let (rows, cols) = view.shape();
+    let (row_stride, col_stride) = (view.row_stride(), view.col_stride());
 
-#[repr(C)]
-#[derive(Debug, Copy, Clone)]
-pub enum CBlasTranspose {
-    CblasNoTrans = 111,
-    CblasTrans = 112,
-    CblasConjTrans = 113,
+    if col_stride == 1 && (rows <= 1 || row_stride >= cols) {
+        Some((CBlasTranspose::CblasNoTrans, row_stride.max(cols).max(1)))
+    } else if row_stride == 1 && (cols <= 1 || col_stride >= rows) {
+        // Stored as the row-major transpose
+        Some((CBlasTranspose::CblasTrans, col_stride.max(rows).max(1)))
+    } else {
+        None
+    }
 }
 
-#[link(name = "Accelerate", kind = "framework")]
-unsafe extern "C" {
-    pub unsafe fn cblas_dgemm(
-        layout: CBlasLayout,
-        transa: CBlasTranspose,
-        transb: CBlasTranspose,
-        m: i32,
-        n: i32,
-        k: i32,
-        alpha: f64,
-        a: *const f64,
-        lda: i32,
-        b: *const f64,
-        ldb: i32,
-        beta: f64,
-        c: *mut f64,
-        ldc: i32,
-    );
+/// Computes `c = alpha * a * b + beta * c` with gemm, where `c` is row-major with leading dimension `ldc`.
+///
+/// # Safety
+///
+/// `c` must be valid for writes of an `a.num_rows() x b.num_cols()` matrix with leading dimension `ldc`.
+unsafe fn gemm_into<T: BlasElement>(
+    alpha: T,
+    a: &MatrixView<T>,
+    b: &MatrixView<T>,
+    beta: T,
+    c: *mut T,
+    ldc: usize,
+) {
+    let (self_rows, self_cols) = a.shape();
+    let other_cols = b.num_cols();
+
+    // gemm needs one unit stride per operand, anything else is packed into a contiguous copy
+    let a_packed;
+    let (a_ptr, a_trans, lda) = match gemm_layout(a) {
+        Some((trans, ld)) => (a.storage().as_ptr(), trans, ld),
+        None => {
+            a_packed = a.to_vec();
+            (
+                a_packed.as_ptr(),
+                CBlasTranspose::CblasNoTrans,
+                self_cols.max(1),
+            )
+        }
+    };
+    let b_packed;
+    let (b_ptr, b_trans, ldb) = match gemm_layout(b) {
+        Some((trans, ld)) => (b.storage().as_ptr(), trans, ld),
+        None => {
+            b_packed = b.to_vec();
+            (
+                b_packed.as_ptr(),
+                CBlasTranspose::CblasNoTrans,
+                other_cols.max(1),
+            )
+        }
+    };
+
+    unsafe {
+        T::gemm(
+            CBlasLayout::CblasRowMajor,
+            a_trans,
+            b_trans,
+            self_rows as i32,
+            other_cols as i32,
+            self_cols as i32,
+            alpha,
+            a_ptr,
+            lda as i32,
+            b_ptr,
+            ldb as i32,
+            beta,
+            c,
+            ldc as i32,
+        );
+    }
 }
 
 #[derive(Debug)]
-pub struct BlasMatrix {
-    data: Vec<f64>,
+pub struct BlasMatrix<T = f64> {
+    data: Vec<T>,
     shape: (usize, usize),
 }
 
-impl BlasMatrix {
-    fn new_from_vec(data: Vec<f64>, rows: usize, cols: usize) -> Self {
+impl<T: BlasElement> BlasMatrix<T> {
+    fn new_from_vec(data: Vec<T>, rows: usize, cols: usize) -> Self {
         Self {
             data,
             shape: (rows, cols),
         }
     }
 
-    pub fn get_data_vec(&self) -> &Vec<f64> {
+    pub fn get_data_vec(&self) -> &Vec<T> {
         &self.data
     }
 
     #[inline(always)]
-    pub fn get(&self, row: usize, col: usize) -> f64 {
+    pub fn get(&self, row: usize, col: usize) -> T {
         self.data[row * self.num_cols() + col]
     }
+
+    /// Computes `self^H * other` in a single `gemm` call without materializing `self^H`,
+    /// panicking unless both have the same number of rows.
+    pub fn conjugate_transpose_multiplication(&self, other: &Self) -> Self {
+        if let Err(e) = check_same_rows(self.shape(), other.shape()) {
+            panic!("{}", e);
+        }
+        let self_rows = self.num_rows();
+        let self_cols = self.num_cols();
+        let other_cols = other.num_cols();
+
+        let mut res = vec![T::zero(); self_cols * other_cols];
+
+        unsafe {
+            T::gemm(
+                CBlasLayout::CblasRowMajor,
+                CBlasTranspose::CblasConjTrans,
+                CBlasTranspose::CblasNoTrans,
+                self_cols as i32,
+                other_cols as i32,
+                self_rows as i32,
+                T::one(),
+                self.data.as_ptr(),
+                self_cols as i32,
+                other.data.as_ptr(),
+                other_cols as i32,
+                T::zero(),
+                res.as_mut_ptr(),
+                other_cols as i32,
+            );
+        }
+
+        Self::new_from_vec(res, self_cols, other_cols)
+    }
+
+    /// Number of entries, as the `n` of the level-1 routines.
+    fn len(&self) -> i32 {
+        self.data.len() as i32
+    }
+
+    /// Computes `op(self) * x` into `y` with a single `gemv` call.
+    fn gemv_into(&self, trans: CBlasTranspose, x: &Vector<T>, y: &mut Vector<T>) {
+        let (rows, cols) = (self.num_rows(), self.num_cols());
+        unsafe {
+            T::gemv(
+                CBlasLayout::CblasRowMajor,
+                trans,
+                rows as i32,
+                cols as i32,
+                T::one(),
+                self.data.as_ptr(),
+                cols.max(1) as i32,
+                x.as_slice().as_ptr(),
+                1,
+                T::zero(),
+                y.as_mut_slice().as_mut_ptr(),
+                1,
+            );
+        }
+    }
 }
 
-impl Matrix for BlasMatrix {
-    fn new(data: Vec<Vec<f64>>) -> Self {
-        let rows = data.len();
-        let cols = data[0].len();
+impl<T: LapackElement> BlasMatrix<T> {
+    /// Column-major LU factors and pivots from `getrf`, since LAPACK is column-major.
+    fn getrf(&self) -> Result<(Vec<T>, Vec<i32>), MatrixError> {
+        check_square(self.shape)?;
+        let n = self.num_rows();
+        let mut a = transpose(&self.data, n, n);
+        let mut ipiv = vec![0; n];
+
+        // A positive `info` flags an exactly zero pivot, which `has_negligible_pivot` also catches
+        unsafe {
+            T::getrf(
+                n as i32,
+                n as i32,
+                a.as_mut_ptr(),
+                n.max(1) as i32,
+                ipiv.as_mut_ptr(),
+            );
+        }
+
+        Ok((a, ipiv))
+    }
+}
+
+impl<T: BlasElement> Matrix<T> for BlasMatrix<T> {
+    fn new(data: Vec<Vec<T>>) -> Self {
+        let (rows, cols) = assert_data(&data);
         let data = data.into_iter().flatten().collect();
         Self::new_from_vec(data, rows, cols)
     }
@@ -81,81 +223,354 @@ impl Matrix for BlasMatrix {
         self.shape.1
     }
 
-    fn get_data(&self) -> Vec<Vec<f64>> {
+    fn get_data(&self) -> Vec<Vec<T>> {
         self.data
             .chunks(self.num_cols())
             .map(|chunk| chunk.to_vec())
             .collect()
     }
 
+    fn as_slice(&self) -> Option<&[T]> {
+        Some(&self.data)
+    }
+
+    fn row(&self, row: usize) -> &[T] {
+        let cols = self.num_cols();
+        &self.data[row * cols..(row + 1) * cols]
+    }
+
+    fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
+    fn get(&self, row: usize, col: usize) -> T {
+        assert_index((row, col), self.shape);
+        self.data[row * self.num_cols() + col]
+    }
+
+    fn get_mut(&mut self, row: usize, col: usize) -> &mut T {
+        assert_index((row, col), self.shape);
+        let cols = self.num_cols();
+        &mut self.data[row * cols + col]
+    }
+
     fn zeroes(rows: usize, cols: usize) -> Self {
         Self {
-            data: vec![0.0; rows * cols],
+            data: vec![T::zero(); rows * cols],
             shape: (rows, cols),
         }
     }
 
     fn identity(size: usize) -> Self {
-        let mut data = vec![0.0; size * size];
+        let mut data = vec![T::zero(); size * size];
 
         for i in 0..size {
-            data[i * size + i] = 1.0;
+            data[i * size + i] = T::one();
         }
 
         BlasMatrix::new_from_vec(data, size, size)
     }
 
     fn matrix_addition(&self, other: &Self) -> Self {
+        if let Err(e) = check_same_shape(self.shape(), other.shape()) {
+            panic!("{}", e);
+        }
         let data = self
             .data
             .iter()
             .zip(other.data.iter())
-            .map(|(a, b)| a + b)
+            .map(|(&a, &b)| a + b)
             .collect();
         Self::new_from_vec(data, self.num_rows(), self.num_cols())
     }
 
     fn matrix_subtraction(&self, other: &Self) -> Self {
+        if let Err(e) = check_same_shape(self.shape(), other.shape()) {
+            panic!("{}", e);
+        }
         let data = self
             .data
             .iter()
             .zip(other.data.iter())
-            .map(|(a, b)| a - b)
+            .map(|(&a, &b)| a - b)
             .collect();
         Self::new_from_vec(data, self.num_rows(), self.num_cols())
     }
 
     fn matrix_multiplication(&self, other: &Self) -> Self {
-        let self_rows = self.num_rows();
-        let self_cols = self.num_cols();
-        let other_cols = other.num_cols();
+        Self::view_multiplication(&self.view(), &other.view())
+    }
+
+    fn scalar_multiplication(&self, scalar: T) -> Self {
+        let data = self.data.iter().map(|&a| a * scalar).collect();
+        Self::new_from_vec(data, self.num_rows(), self.num_cols())
+    }
+
+    fn add_assign(&mut self, other: &Self) {
+        if let Err(e) = check_same_shape(self.shape(), other.shape()) {
+            panic!("{}", e);
+        }
+        for (a, &b) in self.data.iter_mut().zip(other.data.iter()) {
+            *a += b;
+        }
+    }
+
+    fn sub_assign(&mut self, other: &Self) {
+        if let Err(e) = check_same_shape(self.shape(), other.shape()) {
+            panic!("{}", e);
+        }
+        for (a, &b) in self.data.iter_mut().zip(other.data.iter()) {
+            *a -= b;
+        }
+    }
+
+    fn scale_in_place(&mut self, scalar: T) {
+        self.data.iter_mut().for_each(|a| *a *= scalar);
+    }
+
+    fn gemm(alpha: T, a: &Self, b: &Self, beta: T, c: &mut Self) {
+        Self::view_gemm(alpha, &a.view(), &b.view(), beta, &mut c.view_mut());
+    }
+
+    fn matvec(&self, x: &Vector<T>) -> Vector<T> {
+        if let Err(e) = check_matvec(self.shape, x.len()) {
+            panic!("{}", e);
+        }
+        let mut y = Vector::zeroes(self.num_rows());
+        self.gemv_into(CBlasTranspose::CblasNoTrans, x, &mut y);
+        y
+    }
+
+    fn vecmat(&self, x: &Vector<T>) -> Vector<T> {
+        if let Err(e) = check_vecmat(x.len(), self.shape) {
+            panic!("{}", e);
+        }
+        // x^T * A is A^T * x, which `gemv` reads straight from the row-major A
+        let mut y = Vector::zeroes(self.num_cols());
+        self.gemv_into(CBlasTranspose::CblasTrans, x, &mut y);
+        y
+    }
+
+    fn transpose(&self) -> Self {
+        let data = transpose(&self.data, self.num_rows(), self.num_cols());
+        Self::new_from_vec(data, self.num_cols(), self.num_rows())
+    }
+
+    fn conjugate_transpose(&self) -> Self {
+        let data = conjugate_transpose(&self.data, self.num_rows(), self.num_cols());
+        Self::new_from_vec(data, self.num_cols(), self.num_rows())
+    }
+}
+
+impl<T: BlasElement> FlatMatrix<T> for BlasMatrix<T> {
+    fn from_vec(data: Vec<T>, rows: usize, cols: usize) -> Self {
+        assert_eq!(data.len(), rows * cols, "data length must be rows * cols");
+        Self::new_from_vec(data, rows, cols)
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.data
+    }
+
+    fn view_multiplication(a: &MatrixView<T>, b: &MatrixView<T>) -> Self {
+        let mut res = Self::zeroes(a.num_rows(), b.num_cols());
+        Self::view_gemm(T::one(), a, b, T::zero(), &mut res.view_mut());
+        res
+    }
+
+    fn view_gemm(
+        alpha: T,
+        a: &MatrixView<T>,
+        b: &MatrixView<T>,
+        beta: T,
+        c: &mut MatrixViewMut<T>,
+    ) {
+        if let Err(e) = check_gemm(a.shape(), b.shape(), c.shape()) {
+            panic!("{}", e);
+        }
+        let (rows, cols) = c.shape();
 
-        let mut res = vec![0.0; self_rows * other_cols];
+        if c.col_stride() == 1 && (rows <= 1 || c.row_stride() >= cols) {
+            let ldc = c.row_stride().max(cols).max(1);
+            unsafe { gemm_into(alpha, a, b, beta, c.storage_mut().as_mut_ptr(), ldc) };
+        } else {
+            // gemm only writes rows with unit stride, so compute alpha * a * b aside and merge it in
+            let mut res = vec![T::zero(); rows * cols];
+            unsafe { gemm_into(alpha, a, b, T::zero(), res.as_mut_ptr(), cols.max(1)) };
+            for i in 0..rows {
+                for j in 0..cols {
+                    let c_ij = c.get_mut(i, j);
+                    *c_ij = if beta == T::zero() {
+                        res[i * cols + j]
+                    } else {
+                        res[i * cols + j] + beta * *c_ij
+                    };
+                }
+            }
+        }
+    }
+}
+
+impl<T: LapackElement> LinearAlgebra<T> for BlasMatrix<T> {
+    fn lu(&self) -> Result<Lu<T, Self>, MatrixError> {
+        let (a, ipiv) = self.getrf()?;
+        let n = self.num_rows();
+
+        // `ipiv` holds the 1-based row swaps in the order they were applied
+        let mut permutation = (0..n).collect::<Vec<_>>();
+        let mut swaps = 0;
+        for (k, &pivot) in ipiv.iter().enumerate() {
+            let pivot = pivot as usize - 1;
+            if pivot != k {
+                permutation.swap(k, pivot);
+                swaps += 1;
+            }
+        }
+
+        let lu = Self::new_from_vec(transpose(&a, n, n), n, n);
+        Ok(Lu::new(lu, permutation, swaps))
+    }
+
+    fn solve(&self, b: &Self) -> Result<Self, MatrixError> {
+        check_multipliable(self.shape, b.shape)?;
+        let (a, ipiv) = self.getrf()?;
+        let (n, nrhs) = b.shape;
+        if has_negligible_pivot(&a, n) {
+            return Err(MatrixError::Singular);
+        }
+
+        let mut x = transpose(&b.data, n, nrhs);
+        unsafe {
+            T::getrs(
+                b'N',
+                n as i32,
+                nrhs as i32,
+                a.as_ptr(),
+                n.max(1) as i32,
+                ipiv.as_ptr(),
+                x.as_mut_ptr(),
+                n.max(1) as i32,
+            );
+        }
+
+        Ok(Self::new_from_vec(transpose(&x, nrhs, n), n, nrhs))
+    }
+
+    fn inverse(&self) -> Result<Self, MatrixError> {
+        self.solve(&Self::identity(self.num_rows()))
+    }
+
+    fn solve_triangular(&self, triangle: Triangle, b: &Self) -> Result<Self, MatrixError> {
+        check_square(self.shape)?;
+        check_multipliable(self.shape, b.shape)?;
+        let (n, nrhs) = b.shape;
+        if has_zero_on_diagonal(&self.data, n) {
+            return Err(MatrixError::Singular);
+        }
 
+        let uplo = match triangle {
+            Triangle::Upper => CBlasUplo::CblasUpper,
+            Triangle::Lower => CBlasUplo::CblasLower,
+        };
+        let mut x = b.data.clone();
         unsafe {
-            cblas_dgemm(
+            T::trsm(
                 CBlasLayout::CblasRowMajor,
+                CBlasSide::CblasLeft,
+                uplo,
                 CBlasTranspose::CblasNoTrans,
-                CBlasTranspose::CblasNoTrans,
-                self_rows as i32,
-                other_cols as i32,
-                self_cols as i32,
-                1.0,
+                CBlasDiag::CblasNonUnit,
+                n as i32,
+                nrhs as i32,
+                T::one(),
                 self.data.as_ptr(),
-                self_cols as i32,
-                other.data.as_ptr(),
-                other_cols as i32,
-                0.0,
-                res.as_mut_ptr(),
-                other_cols as i32,
+                n as i32,
+                x.as_mut_ptr(),
+                nrhs as i32,
             );
         }
+        Ok(Self::new_from_vec(x, n, nrhs))
+    }
 
-        Self::new_from_vec(res, self_rows, other_cols)
+    fn qr(&self) -> Qr<T, Self> {
+        let (rows, cols) = self.shape;
+        let mut a = transpose(&self.data, rows, cols);
+        let mut tau = vec![T::zero(); rows.min(cols)];
+        unsafe {
+            T::geqrf(
+                rows as i32,
+                cols as i32,
+                a.as_mut_ptr(),
+                rows.max(1) as i32,
+                tau.as_mut_ptr(),
+            );
+        }
+
+        // The compact form is the same in either layout
+        let qr = Self::new_from_vec(transpose(&a, cols, rows), rows, cols);
+        Qr::new(qr, tau)
     }
+}
 
-    fn scalar_multiplication(&self, scalar: f64) -> Self {
-        let data = self.data.iter().map(|a| a * scalar).collect();
-        Self::new_from_vec(data, self.num_rows(), self.num_cols())
+impl<T: BlasLevel1Element> Level1<T> for BlasMatrix<T> {
+    fn dot(&self, other: &Self) -> T {
+        if let Err(e) = check_same_shape(self.shape, other.shape) {
+            panic!("{}", e);
+        }
+        unsafe { T::dot(self.len(), self.data.as_ptr(), 1, other.data.as_ptr(), 1) }
+    }
+
+    fn axpy(&mut self, alpha: T, x: &Self) {
+        if let Err(e) = check_same_shape(self.shape, x.shape) {
+            panic!("{}", e);
+        }
+        let n = self.len();
+        unsafe { T::axpy(n, alpha, x.data.as_ptr(), 1, self.data.as_mut_ptr(), 1) };
+    }
+
+    fn nrm2(&self) -> T {
+        unsafe { T::nrm2(self.len(), self.data.as_ptr(), 1) }
+    }
+
+    fn asum(&self) -> T {
+        unsafe { T::asum(self.len(), self.data.as_ptr(), 1) }
+    }
+
+    fn iamax(&self) -> (usize, usize) {
+        let i = unsafe { T::iamax(self.len(), self.data.as_ptr(), 1) };
+        (i / self.num_cols(), i % self.num_cols())
+    }
+
+    fn scal(&mut self, alpha: T) {
+        unsafe { T::scal(self.len(), alpha, self.data.as_mut_ptr(), 1) };
+    }
+
+    fn rot(&mut self, other: &mut Self, givens: Givens<T>) {
+        if let Err(e) = check_same_shape(self.shape, other.shape) {
+            panic!("{}", e);
+        }
+        let (x, y) = (self.data.as_mut_ptr(), other.data.as_mut_ptr());
+        unsafe { T::rot(self.len(), x, 1, y, 1, givens.c, givens.s) };
+    }
+}
+
+impl<T: BlasLevel1Element> Reductions<T> for BlasMatrix<T> {
+    fn norm_frobenius(&self) -> T {
+        self.nrm2()
+    }
+}
+
+impl<T: BlasElement> Index<(usize, usize)> for BlasMatrix<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        assert_index((row, col), self.shape);
+        &self.data[row * self.num_cols() + col]
+    }
+}
+
+impl<T: BlasElement> IndexMut<(usize, usize)> for BlasMatrix<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        self.get_mut(row, col)
     }
 }
@@ -1,51 +1,85 @@
-use crate::Matrix;
+use crate::error::{assert_data, assert_index, check_gemm, check_same_shape};
+use crate::linalg::{blocked_cholesky_in_place, blocked_lu_in_place};
+use crate::matrices::transpose::{conjugate_transpose, transpose};
+use crate::tuning::{self, TuningConfig};
+use crate::{
+    Cholesky, Element, FlatMatrix, FloatElement, Level1, LinearAlgebra, Lu, Matrix, MatrixError,
+    Reductions,
+};
 use std::cmp::min;
-
-static BLOCK_SIZE: usize = 8;
+use std::ops::{Index, IndexMut};
 
 /// Representing matrix as a one-dimensional vector with blocking
 #[derive(Debug)]
-pub struct BlockedMatrix {
-    data: Vec<f64>,
+pub struct BlockedMatrix<T = f64> {
+    data: Vec<T>,
     shape: (usize, usize),
+    tuning: TuningConfig,
 }
 
-impl BlockedMatrix {
-    fn new_from_vec(data: Vec<f64>, rows: usize, cols: usize) -> Self {
+impl<T: Element> BlockedMatrix<T> {
+    fn new_from_vec(data: Vec<T>, rows: usize, cols: usize) -> Self {
         Self {
             data,
             shape: (rows, cols),
+            tuning: tuning::config(),
         }
     }
 
-    pub fn get_data_vec(&self) -> &Vec<f64> {
+    /// Wraps `data` like `new_from_vec`, keeping this matrix's tuning.
+    fn with_data(&self, data: Vec<T>, rows: usize, cols: usize) -> Self {
+        Self {
+            data,
+            shape: (rows, cols),
+            tuning: self.tuning,
+        }
+    }
+
+    /// Uses the block sizes of `tuning` instead of [`tuning::config`]. The matrices returned by
+    /// this one's arithmetic and transposes keep it.
+    pub fn with_tuning(self, tuning: TuningConfig) -> Self {
+        Self { tuning, ..self }
+    }
+
+    pub fn get_data_vec(&self) -> &Vec<T> {
         &self.data
     }
 
     #[inline(always)]
-    pub fn get(&self, row: usize, col: usize) -> f64 {
+    pub fn get(&self, row: usize, col: usize) -> T {
         self.data[row * self.num_cols() + col]
     }
 
-    fn multiply_block(&self, bi: usize, bj: usize, bk: usize, other: &Self, res: &mut Vec<f64>) {
-        let bi_end = min(bi + BLOCK_SIZE, self.num_rows());
-        let bj_end = min(bj + BLOCK_SIZE, other.num_cols());
-        let bk_end = min(bk + BLOCK_SIZE, self.num_cols());
+    #[allow(clippy::too_many_arguments)]
+    fn multiply_block(
+        &self,
+        bi: usize,
+        bj: usize,
+        bk: usize,
+        block_size: usize,
+        other: &Self,
+        alpha: T,
+        res: &mut [T],
+    ) {
+        let bi_end = min(bi + block_size, self.num_rows());
+        let bj_end = min(bj + block_size, other.num_cols());
+        let bk_end = min(bk + block_size, self.num_cols());
 
         for i in bi..bi_end {
             for j in bj..bj_end {
+                let mut sum = T::zero();
                 for k in bk..bk_end {
-                    res[i * other.num_cols() + j] += self.get(i, k) * other.get(k, j);
+                    sum += self.get(i, k) * other.get(k, j);
                 }
+                res[i * other.num_cols() + j] += alpha * sum;
             }
         }
     }
 }
 
-impl Matrix for BlockedMatrix {
-    fn new(data: Vec<Vec<f64>>) -> Self {
-        let rows = data.len();
-        let cols = data[0].len();
+impl<T: Element> Matrix<T> for BlockedMatrix<T> {
+    fn new(data: Vec<Vec<T>>) -> Self {
+        let (rows, cols) = assert_data(&data);
         let data = data.into_iter().flatten().collect();
         Self::new_from_vec(data, rows, cols)
     }
@@ -62,37 +96,62 @@ impl Matrix for BlockedMatrix {
         self.shape.1
     }
 
-    fn get_data(&self) -> Vec<Vec<f64>> {
+    fn get_data(&self) -> Vec<Vec<T>> {
         self.data
             .chunks(self.num_cols())
             .map(|chunk| chunk.to_vec())
             .collect()
     }
 
+    fn as_slice(&self) -> Option<&[T]> {
+        Some(&self.data)
+    }
+
+    fn row(&self, row: usize) -> &[T] {
+        let cols = self.num_cols();
+        &self.data[row * cols..(row + 1) * cols]
+    }
+
+    fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
+    fn get(&self, row: usize, col: usize) -> T {
+        assert_index((row, col), self.shape);
+        self.data[row * self.num_cols() + col]
+    }
+
+    fn get_mut(&mut self, row: usize, col: usize) -> &mut T {
+        assert_index((row, col), self.shape);
+        let cols = self.num_cols();
+        &mut self.data[row * cols + col]
+    }
+
     fn zeroes(rows: usize, cols: usize) -> Self {
-        Self {
-            data: vec![0.0; rows * cols],
-            shape: (rows, cols),
-        }
+        Self::new_from_vec(vec![T::zero(); rows * cols], rows, cols)
     }
 
     fn identity(size: usize) -> Self {
-        let mut data = vec![0.0; size * size];
+        let mut data = vec![T::zero(); size * size];
 
         for i in 0..size {
-            data[i * size + i] = 1.0;
+            data[i * size + i] = T::one();
         }
 
         BlockedMatrix::new_from_vec(data, size, size)
     }
 
     fn matrix_addition(&self, other: &Self) -> Self {
-        let mut data = vec![0.0; self.num_rows() * self.num_cols()];
+        if let Err(e) = check_same_shape(self.shape(), other.shape()) {
+            panic!("{}", e);
+        }
+        let mut data = vec![T::zero(); self.num_rows() * self.num_cols()];
+        let block_size = self.tuning.block_size();
 
-        for bx in (0..self.num_rows()).step_by(BLOCK_SIZE) {
-            for by in (0..self.num_cols()).step_by(BLOCK_SIZE) {
-                let num_rows_to_process = min(BLOCK_SIZE, self.num_rows() - bx);
-                let num_cols_to_process = min(BLOCK_SIZE, self.num_cols() - by);
+        for bx in (0..self.num_rows()).step_by(block_size) {
+            for by in (0..self.num_cols()).step_by(block_size) {
+                let num_rows_to_process = min(block_size, self.num_rows() - bx);
+                let num_cols_to_process = min(block_size, self.num_cols() - by);
 
                 for i in bx..bx + num_rows_to_process {
                     for j in by..by + num_cols_to_process {
@@ -102,16 +161,20 @@ impl Matrix for BlockedMatrix {
             }
         }
 
-        Self::new_from_vec(data, self.num_rows(), self.num_cols())
+        self.with_data(data, self.num_rows(), self.num_cols())
     }
 
     fn matrix_subtraction(&self, other: &Self) -> Self {
-        let mut data = vec![0.0; self.num_rows() * self.num_cols()];
+        if let Err(e) = check_same_shape(self.shape(), other.shape()) {
+            panic!("{}", e);
+        }
+        let mut data = vec![T::zero(); self.num_rows() * self.num_cols()];
+        let block_size = self.tuning.block_size();
 
-        for bx in (0..self.num_rows()).step_by(BLOCK_SIZE) {
-            for by in (0..self.num_cols()).step_by(BLOCK_SIZE) {
-                let num_rows_to_process = min(BLOCK_SIZE, self.num_rows() - bx);
-                let num_cols_to_process = min(BLOCK_SIZE, self.num_cols() - by);
+        for bx in (0..self.num_rows()).step_by(block_size) {
+            for by in (0..self.num_cols()).step_by(block_size) {
+                let num_rows_to_process = min(block_size, self.num_rows() - bx);
+                let num_cols_to_process = min(block_size, self.num_cols() - by);
 
                 for i in bx..bx + num_rows_to_process {
                     for j in by..by + num_cols_to_process {
@@ -121,43 +184,133 @@ impl Matrix for BlockedMatrix {
             }
         }
 
-        Self::new_from_vec(data, self.num_rows(), self.num_cols())
+        self.with_data(data, self.num_rows(), self.num_cols())
     }
 
     fn matrix_multiplication(&self, other: &Self) -> Self {
-        let self_rows = self.num_rows();
-        let self_cols = self.num_cols();
-        let other_cols = other.num_cols();
+        let (rows, cols) = (self.num_rows(), other.num_cols());
+        let mut res = self.with_data(vec![T::zero(); rows * cols], rows, cols);
+        Self::gemm(T::one(), self, other, T::zero(), &mut res);
+        res
+    }
 
-        let mut res = vec![0.0; self_rows * other_cols];
+    fn scalar_multiplication(&self, scalar: T) -> Self {
+        let mut data = vec![T::zero(); self.num_rows() * self.num_cols()];
+        let block_size = self.tuning.block_size();
 
-        for bi in (0..self_rows).step_by(BLOCK_SIZE) {
-            for bj in (0..other_cols).step_by(BLOCK_SIZE) {
-                for bk in (0..self_cols).step_by(BLOCK_SIZE) {
-                    self.multiply_block(bi, bj, bk, other, &mut res);
+        for bx in (0..self.num_rows()).step_by(block_size) {
+            for by in (0..self.num_cols()).step_by(block_size) {
+                let num_rows_to_process = min(block_size, self.num_rows() - bx);
+                let num_cols_to_process = min(block_size, self.num_cols() - by);
+
+                for i in bx..bx + num_rows_to_process {
+                    for j in by..by + num_cols_to_process {
+                        data[i * self.num_cols() + j] = self.get(i, j) * scalar;
+                    }
                 }
             }
         }
 
-        Self::new_from_vec(res, self_rows, other_cols)
+        self.with_data(data, self.num_rows(), self.num_cols())
+    }
+
+    fn add_assign(&mut self, other: &Self) {
+        if let Err(e) = check_same_shape(self.shape(), other.shape()) {
+            panic!("{}", e);
+        }
+        for (a, &b) in self.data.iter_mut().zip(other.data.iter()) {
+            *a += b;
+        }
+    }
+
+    fn sub_assign(&mut self, other: &Self) {
+        if let Err(e) = check_same_shape(self.shape(), other.shape()) {
+            panic!("{}", e);
+        }
+        for (a, &b) in self.data.iter_mut().zip(other.data.iter()) {
+            *a -= b;
+        }
     }
 
-    fn scalar_multiplication(&self, scalar: f64) -> Self {
-        let mut data = vec![0.0; self.num_rows() * self.num_cols()];
+    fn scale_in_place(&mut self, scalar: T) {
+        self.data.iter_mut().for_each(|a| *a *= scalar);
+    }
 
-        for bx in (0..self.num_rows()).step_by(BLOCK_SIZE) {
-            for by in (0..self.num_cols()).step_by(BLOCK_SIZE) {
-                let num_rows_to_process = min(BLOCK_SIZE, self.num_rows() - bx);
-                let num_cols_to_process = min(BLOCK_SIZE, self.num_cols() - by);
+    fn gemm(alpha: T, a: &Self, b: &Self, beta: T, c: &mut Self) {
+        if let Err(e) = check_gemm(a.shape(), b.shape(), c.shape()) {
+            panic!("{}", e);
+        }
+        if beta == T::zero() {
+            c.data.fill(T::zero());
+        } else if beta != T::one() {
+            c.data.iter_mut().for_each(|x| *x *= beta);
+        }
 
-                for i in bx..bx + num_rows_to_process {
-                    for j in by..by + num_cols_to_process {
-                        data[i * self.num_cols() + j] = self.get(i, j) * scalar;
-                    }
+        let block_size = a.tuning.block_size();
+        for bi in (0..a.num_rows()).step_by(block_size) {
+            for bj in (0..b.num_cols()).step_by(block_size) {
+                for bk in (0..a.num_cols()).step_by(block_size) {
+                    a.multiply_block(bi, bj, bk, block_size, b, alpha, &mut c.data);
                 }
             }
         }
+    }
+
+    fn transpose(&self) -> Self {
+        let data = transpose(&self.data, self.num_rows(), self.num_cols());
+        self.with_data(data, self.num_cols(), self.num_rows())
+    }
+
+    fn conjugate_transpose(&self) -> Self {
+        let data = conjugate_transpose(&self.data, self.num_rows(), self.num_cols());
+        self.with_data(data, self.num_cols(), self.num_rows())
+    }
+}
+
+impl<T: Element> FlatMatrix<T> for BlockedMatrix<T> {
+    fn from_vec(data: Vec<T>, rows: usize, cols: usize) -> Self {
+        assert_eq!(data.len(), rows * cols, "data length must be rows * cols");
+        Self::new_from_vec(data, rows, cols)
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.data
+    }
+
+    fn tuning(&self) -> TuningConfig {
+        self.tuning
+    }
+}
+
+impl<T: FloatElement> LinearAlgebra<T> for BlockedMatrix<T> {
+    fn lu(&self) -> Result<Lu<T, Self>, MatrixError> {
+        let block_size = self.tuning.block_size();
+        Lu::factor_with(self, |a, n, permutation| {
+            blocked_lu_in_place(a, n, block_size, permutation)
+        })
+    }
+
+    fn cholesky(&self) -> Result<Cholesky<T, Self>, MatrixError> {
+        let block_size = self.tuning.block_size();
+        Cholesky::factor_with(self, |a, n| blocked_cholesky_in_place(a, n, block_size, 1))
+    }
+}
+
+impl<T: FloatElement> Level1<T> for BlockedMatrix<T> {}
+
+impl<T: FloatElement> Reductions<T> for BlockedMatrix<T> {}
+
+impl<T: Element> Index<(usize, usize)> for BlockedMatrix<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        assert_index((row, col), self.shape);
+        &self.data[row * self.num_cols() + col]
+    }
+}
 
-        Self::new_from_vec(data, self.num_rows(), self.num_cols())
+impl<T: Element> IndexMut<(usize, usize)> for BlockedMatrix<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        self.get_mut(row, col)
     }
 }
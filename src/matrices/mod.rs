@@ -4,4 +4,7 @@ pub mod blocked_matrix;
 pub mod multithread_matrix;
 pub mod ndarray_matrix;
 pub mod one_d_vec_matrix;
+mod ops;
+pub mod packed_matrix;
+pub(crate) mod transpose;
 pub mod transposed_view_matrix;
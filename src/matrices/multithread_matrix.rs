@@ -1,12 +1,21 @@
-use crate::Matrix;
-use crossbeam::scope;
+use crate::error::{
+    assert_data, assert_index, check_gemm, check_matvec, check_same_shape, check_vecmat,
+};
+use crate::level1;
+use crate::linalg::blocked_cholesky_in_place;
+use crate::matrices::transpose::transpose_rows_into;
+use crate::parallel::ParallelConfig;
+use crate::reductions::{add_columns, first_extremum, nan_max, position, sum, variance};
+use crate::simd::{self, BinaryOp};
+use crate::tuning::{self, TuningConfig};
+use crate::{
+    Cholesky, Element, FlatMatrix, FloatElement, Level1, LinearAlgebra, Matrix, MatrixError,
+    Reductions, Vector,
+};
 use itertools::izip;
-use rayon::ThreadPoolBuilder;
 
 use std::cmp::min;
-
-static NUM_THREADS: usize = 16;
-static BLOCK_SIZE: usize = 8;
+use std::ops::{Index, IndexMut};
 
 #[derive(Copy, Clone)]
 struct SyncMutPtr<T>(*mut T);
@@ -15,53 +24,221 @@ struct SyncMutPtr<T>(*mut T);
 unsafe impl<T> Sync for SyncMutPtr<T> {}
 unsafe impl<T> Send for SyncMutPtr<T> {}
 
+/// Adds up the per-thread partial results of a column-wise reduction.
+fn add_partials<T: Element>(partials: Vec<Vec<T>>, len: usize) -> Vec<T> {
+    let mut total = vec![T::zero(); len];
+    for partial in partials {
+        for (t, p) in total.iter_mut().zip(partial) {
+            *t += p;
+        }
+    }
+    total
+}
+
 /// Representing matrix as a one-dimensional vector
 #[derive(Debug)]
-pub struct MultithreadMatrix {
-    data: Vec<f64>,
+pub struct MultithreadMatrix<T = f64> {
+    data: Vec<T>,
     shape: (usize, usize),
+    tuning: TuningConfig,
 }
 
-impl MultithreadMatrix {
-    fn new_from_vec(data: Vec<f64>, rows: usize, cols: usize) -> Self {
+impl<T: Element> MultithreadMatrix<T> {
+    fn new_from_vec(data: Vec<T>, rows: usize, cols: usize) -> Self {
+        Self {
+            data,
+            shape: (rows, cols),
+            tuning: tuning::config(),
+        }
+    }
+
+    /// Wraps `data` like `new_from_vec`, keeping this matrix's tuning.
+    fn with_data(&self, data: Vec<T>, rows: usize, cols: usize) -> Self {
         Self {
             data,
             shape: (rows, cols),
+            tuning: self.tuning,
         }
     }
 
-    pub fn get_data_vec(&self) -> &Vec<f64> {
+    /// Uses the block sizes and thread count of `tuning` instead of [`tuning::config`]. The
+    /// matrices returned by this one's arithmetic and transposes keep it.
+    pub fn with_tuning(self, tuning: TuningConfig) -> Self {
+        Self { tuning, ..self }
+    }
+
+    /// How to split the work, with the tuned thread count.
+    fn parallel(&self) -> ParallelConfig {
+        self.tuning.parallel()
+    }
+
+    pub fn get_data_vec(&self) -> &Vec<T> {
         &self.data
     }
 
     #[inline(always)]
-    pub fn get(&self, row: usize, col: usize) -> f64 {
+    pub fn get(&self, row: usize, col: usize) -> T {
         self.data[row * self.num_cols() + col]
     }
 
-    fn multiply_block(&self, bi: usize, bj: usize, bk: usize, other: &Self, res: SyncMutPtr<f64>) {
-        let bi_end = min(bi + BLOCK_SIZE, self.num_rows());
-        let bj_end = min(bj + BLOCK_SIZE, other.num_cols());
-        let bk_end = min(bk + BLOCK_SIZE, self.num_cols());
+    fn transpose_with(&self, f: fn(T) -> T) -> Self {
+        let (rows, cols) = self.shape;
+        let mut data = vec![T::zero(); rows * cols];
+        if data.is_empty() {
+            return self.with_data(data, cols, rows);
+        }
+
+        // Each task fills a band of whole rows of the result
+        let parallel = self.parallel();
+        let rows_per_chunk = parallel.chunk_rows(cols, rows);
+        let chunks = data.chunks_mut(rows_per_chunk * rows);
+
+        parallel.for_each(chunks.enumerate(), |(chunk_index, data_chunk)| {
+            transpose_rows_into(
+                &self.data,
+                rows,
+                cols,
+                data_chunk,
+                chunk_index * rows_per_chunk,
+                f,
+            );
+        });
+
+        self.with_data(data, cols, rows)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn multiply_block(
+        &self,
+        bi: usize,
+        bj: usize,
+        bk: usize,
+        block_size: usize,
+        other: &Self,
+        alpha: T,
+        res: SyncMutPtr<T>,
+    ) {
+        let bi_end = min(bi + block_size, self.num_rows());
+        let bj_end = min(bj + block_size, other.num_cols());
+        let bk_end = min(bk + block_size, self.num_cols());
 
         let res_ptr = res.0;
 
         for i in bi..bi_end {
             for j in bj..bj_end {
+                let mut sum = T::zero();
                 for k in bk..bk_end {
-                    unsafe {
-                        *res_ptr.add(i * other.num_cols() + j) += self.get(i, k) * other.get(k, j);
-                    }
+                    sum += self.get(i, k) * other.get(k, j);
+                }
+                unsafe {
+                    *res_ptr.add(i * other.num_cols() + j) += alpha * sum;
                 }
             }
         }
     }
+
+    /// Scales the `block_size` block of `res` at `(bi, bj)` by `beta`, zeroing it when `beta` is
+    /// zero.
+    fn scale_block(
+        bi: usize,
+        bj: usize,
+        block_size: usize,
+        shape: (usize, usize),
+        beta: T,
+        res: SyncMutPtr<T>,
+    ) {
+        let (rows, cols) = shape;
+        let res_ptr = res.0;
+
+        for i in bi..min(bi + block_size, rows) {
+            for j in bj..min(bj + block_size, cols) {
+                unsafe {
+                    let x = res_ptr.add(i * cols + j);
+                    *x = if beta == T::zero() {
+                        T::zero()
+                    } else {
+                        beta * *x
+                    };
+                }
+            }
+        }
+    }
+
+    /// Applies `op` element-wise with `other` into a new matrix, each task running the SIMD
+    /// kernel on an independent chunk.
+    fn zip_op(&self, other: &Self, op: BinaryOp) -> Self {
+        if let Err(e) = check_same_shape(self.shape(), other.shape()) {
+            panic!("{}", e);
+        }
+        let mut data = vec![T::zero(); self.data.len()];
+        let chunk_size = self.element_chunk_size();
+        let chunks = data.chunks_mut(chunk_size);
+        let self_chunks = self.data.chunks(chunk_size);
+        let other_chunks = other.data.chunks(chunk_size);
+
+        self.parallel().for_each(
+            izip!(chunks, self_chunks, other_chunks),
+            |(data_chunk, self_chunk, other_chunk)| {
+                simd::zip_into(op, self_chunk, other_chunk, data_chunk)
+            },
+        );
+
+        self.with_data(data, self.num_rows(), self.num_cols())
+    }
+
+    /// Applies `op` element-wise with `other` in place, each task taking an independent chunk.
+    fn zip_in_place(&mut self, other: &Self, op: BinaryOp) {
+        if let Err(e) = check_same_shape(self.shape(), other.shape()) {
+            panic!("{}", e);
+        }
+        let parallel = self.parallel();
+        let chunk_size = self.element_chunk_size();
+        let chunks = self.data.chunks_mut(chunk_size);
+        let other_chunks = other.data.chunks(chunk_size);
+
+        parallel.for_each(chunks.zip(other_chunks), |(data_chunk, other_chunk)| {
+            simd::zip_assign(op, data_chunk, other_chunk)
+        });
+    }
+
+    /// Runs `f` on each `chunk_size`-long chunk of `data` on the shared pool, along with the
+    /// index of the chunk's first element, and returns the results in order.
+    fn map_chunks<R: Send>(
+        &self,
+        chunk_size: usize,
+        f: impl Fn(usize, &[T]) -> R + Sync,
+    ) -> Vec<R> {
+        self.parallel()
+            .map(self.data.chunks(chunk_size).enumerate(), |(c, chunk)| {
+                f(c * chunk_size, chunk)
+            })
+    }
+
+    /// Splits the elements evenly across the pool, like `matrix_addition`.
+    fn element_chunk_size(&self) -> usize {
+        self.parallel().chunk_len(self.data.len())
+    }
+
+    /// Splits whole rows evenly across the pool.
+    fn row_chunk_size(&self) -> usize {
+        self.parallel().chunk_rows(self.num_rows(), self.num_cols()) * self.num_cols()
+    }
+
+    /// Sums `f(j, a_ij)` down each column `j`, each task taking a chunk of rows.
+    fn column_sums_with(&self, f: impl Fn(usize, T) -> T + Sync) -> Vec<T> {
+        let cols = self.num_cols();
+        let partials = self.map_chunks(self.row_chunk_size(), |_, chunk| {
+            let mut sums = vec![T::zero(); cols];
+            add_columns(&mut sums, chunk.chunks(cols), &f);
+            sums
+        });
+        add_partials(partials, cols)
+    }
 }
 
-impl Matrix for MultithreadMatrix {
-    fn new(data: Vec<Vec<f64>>) -> Self {
-        let rows = data.len();
-        let cols = data[0].len();
+impl<T: Element> Matrix<T> for MultithreadMatrix<T> {
+    fn new(data: Vec<Vec<T>>) -> Self {
+        let (rows, cols) = assert_data(&data);
         let data = data.into_iter().flatten().collect();
         Self::new_from_vec(data, rows, cols)
     }
@@ -78,118 +255,369 @@ impl Matrix for MultithreadMatrix {
         self.shape.1
     }
 
-    fn get_data(&self) -> Vec<Vec<f64>> {
+    fn get_data(&self) -> Vec<Vec<T>> {
         self.data
             .chunks(self.num_cols())
             .map(|chunk| chunk.to_vec())
             .collect()
     }
 
+    fn as_slice(&self) -> Option<&[T]> {
+        Some(&self.data)
+    }
+
+    fn row(&self, row: usize) -> &[T] {
+        let cols = self.num_cols();
+        &self.data[row * cols..(row + 1) * cols]
+    }
+
+    fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
+    fn get(&self, row: usize, col: usize) -> T {
+        assert_index((row, col), self.shape);
+        self.data[row * self.num_cols() + col]
+    }
+
+    fn get_mut(&mut self, row: usize, col: usize) -> &mut T {
+        assert_index((row, col), self.shape);
+        let cols = self.num_cols();
+        &mut self.data[row * cols + col]
+    }
+
     fn zeroes(rows: usize, cols: usize) -> Self {
-        Self {
-            data: vec![0.0; rows * cols],
-            shape: (rows, cols),
-        }
+        Self::new_from_vec(vec![T::zero(); rows * cols], rows, cols)
     }
 
     fn identity(size: usize) -> Self {
-        let mut data = vec![0.0; size * size];
+        let mut data = vec![T::zero(); size * size];
 
         for i in 0..size {
-            data[i * size + i] = 1.0;
+            data[i * size + i] = T::one();
         }
 
         MultithreadMatrix::new_from_vec(data, size, size)
     }
 
     fn matrix_addition(&self, other: &Self) -> Self {
-        let mut data = vec![0.0; self.num_rows() * self.num_cols()];
-        let chunk_size = (data.len() + NUM_THREADS - 1) / NUM_THREADS;
+        self.zip_op(other, BinaryOp::Add)
+    }
+
+    fn matrix_subtraction(&self, other: &Self) -> Self {
+        self.zip_op(other, BinaryOp::Sub)
+    }
+
+    fn matrix_multiplication(&self, other: &Self) -> Self {
+        let (rows, cols) = (self.num_rows(), other.num_cols());
+        let mut res = self.with_data(vec![T::zero(); rows * cols], rows, cols);
+        Self::gemm(T::one(), self, other, T::zero(), &mut res);
+        res
+    }
+
+    fn scalar_multiplication(&self, scalar: T) -> Self {
+        let mut data = vec![T::zero(); self.num_rows() * self.num_cols()];
+        let chunk_size = self.element_chunk_size();
         let chunks = data.chunks_mut(chunk_size);
         let self_chunks = self.data.chunks(chunk_size);
-        let other_chunks = other.data.chunks(chunk_size);
 
-        scope(|s| {
-            for (data_chunk, self_chunk, other_chunk) in izip!(chunks, self_chunks, other_chunks) {
-                s.spawn(|_| {
-                    for i in 0..data_chunk.len() {
-                        data_chunk[i] = self_chunk[i] + other_chunk[i];
-                    }
-                });
-            }
-        })
-        .unwrap();
+        self.parallel()
+            .for_each(chunks.zip(self_chunks), |(data_chunk, self_chunk)| {
+                simd::scale_into(self_chunk, scalar, data_chunk)
+            });
 
-        Self::new_from_vec(data, self.num_rows(), self.num_cols())
+        self.with_data(data, self.num_rows(), self.num_cols())
     }
 
-    fn matrix_subtraction(&self, other: &Self) -> Self {
-        let mut data = vec![0.0; self.num_rows() * self.num_cols()];
-        let chunk_size = (data.len() + NUM_THREADS - 1) / NUM_THREADS;
+    fn map(&self, f: impl Fn(T) -> T + Sync) -> Self {
+        let mut data = vec![T::zero(); self.data.len()];
+        let chunk_size = self.element_chunk_size();
+        let chunks = data.chunks_mut(chunk_size);
+        let self_chunks = self.data.chunks(chunk_size);
+
+        self.parallel()
+            .for_each(chunks.zip(self_chunks), |(data_chunk, self_chunk)| {
+                for (y, &x) in data_chunk.iter_mut().zip(self_chunk) {
+                    *y = f(x);
+                }
+            });
+
+        self.with_data(data, self.num_rows(), self.num_cols())
+    }
+
+    fn zip_map(&self, other: &Self, f: impl Fn(T, T) -> T + Sync) -> Self {
+        if let Err(e) = check_same_shape(self.shape, other.shape) {
+            panic!("{}", e);
+        }
+        let mut data = vec![T::zero(); self.data.len()];
+        let chunk_size = self.element_chunk_size();
         let chunks = data.chunks_mut(chunk_size);
         let self_chunks = self.data.chunks(chunk_size);
         let other_chunks = other.data.chunks(chunk_size);
 
-        scope(|s| {
-            for (data_chunk, self_chunk, other_chunk) in izip!(chunks, self_chunks, other_chunks) {
-                s.spawn(|_| {
-                    for i in 0..data_chunk.len() {
-                        data_chunk[i] = self_chunk[i] - other_chunk[i];
-                    }
-                });
+        self.parallel().for_each(
+            izip!(chunks, self_chunks, other_chunks),
+            |(data_chunk, self_chunk, other_chunk)| {
+                for (y, (&a, &b)) in data_chunk
+                    .iter_mut()
+                    .zip(self_chunk.iter().zip(other_chunk))
+                {
+                    *y = f(a, b);
+                }
+            },
+        );
+
+        self.with_data(data, self.num_rows(), self.num_cols())
+    }
+
+    fn hadamard_product(&self, other: &Self) -> Self {
+        if let Err(e) = check_same_shape(self.shape, other.shape) {
+            panic!("{}", e);
+        }
+        self.zip_op(other, BinaryOp::Mul)
+    }
+
+    fn elementwise_division(&self, other: &Self) -> Self {
+        if let Err(e) = check_same_shape(self.shape, other.shape) {
+            panic!("{}", e);
+        }
+        self.zip_op(other, BinaryOp::Div)
+    }
+
+    fn add_assign(&mut self, other: &Self) {
+        self.zip_in_place(other, BinaryOp::Add);
+    }
+
+    fn sub_assign(&mut self, other: &Self) {
+        self.zip_in_place(other, BinaryOp::Sub);
+    }
+
+    fn scale_in_place(&mut self, scalar: T) {
+        let chunk_size = self.element_chunk_size();
+        self.parallel()
+            .for_each(self.data.chunks_mut(chunk_size), |data_chunk| {
+                simd::scale_assign(data_chunk, scalar)
+            });
+    }
+
+    fn gemm(alpha: T, a: &Self, b: &Self, beta: T, c: &mut Self) {
+        if let Err(e) = check_gemm(a.shape(), b.shape(), c.shape()) {
+            panic!("{}", e);
+        }
+        let a_rows = a.num_rows();
+        let a_cols = a.num_cols();
+        let b_cols = b.num_cols();
+        let c_shape = c.shape;
+        let block_size = a.tuning.block_size();
+        let parallel = a.parallel();
+
+        let res_ptr: SyncMutPtr<T> = SyncMutPtr(c.data.as_mut_ptr());
+        let blocks = (0..a_rows)
+            .step_by(block_size)
+            .flat_map(|bi| (0..b_cols).step_by(block_size).map(move |bj| (bi, bj)));
+        // Each task is responsible for a block of the result matrix
+        let multiply = |(bi, bj)| {
+            Self::scale_block(bi, bj, block_size, c_shape, beta, res_ptr);
+            for bk in (0..a_cols).step_by(block_size) {
+                a.multiply_block(bi, bj, bk, block_size, b, alpha, res_ptr);
             }
-        })
-        .unwrap();
+        };
 
-        Self::new_from_vec(data, self.num_rows(), self.num_cols())
+        if parallel.threads_for_flops(a_rows * a_cols * b_cols) > 1 {
+            parallel.for_each(blocks, multiply);
+        } else {
+            blocks.for_each(multiply);
+        }
     }
 
-    fn matrix_multiplication(&self, other: &Self) -> Self {
-        let self_rows = self.num_rows();
-        let self_cols = self.num_cols();
-        let other_cols = other.num_cols();
-
-        let mut res = vec![0.0; self_rows * other_cols];
-
-        let pool = ThreadPoolBuilder::new()
-            .num_threads(NUM_THREADS)
-            .build()
-            .unwrap();
-
-        let res_ptr: SyncMutPtr<f64> = SyncMutPtr(res.as_mut_ptr());
-        pool.scope(|s| {
-            for bi in (0..self_rows).step_by(BLOCK_SIZE) {
-                for bj in (0..other_cols).step_by(BLOCK_SIZE) {
-                    // Each thread is responsible for a block of the result matrix
-                    s.spawn(move |_| {
-                        for bk in (0..self_cols).step_by(BLOCK_SIZE) {
-                            self.multiply_block(bi, bj, bk, other, res_ptr);
-                        }
-                    });
+    /// Each task computes the entries of `y` for a chunk of rows.
+    fn matvec(&self, x: &Vector<T>) -> Vector<T> {
+        if let Err(e) = check_matvec(self.shape, x.len()) {
+            panic!("{}", e);
+        }
+        let (rows, cols) = self.shape;
+        let mut y = Vector::zeroes(rows);
+        let parallel = self.parallel();
+        let rows_per_chunk = parallel.chunk_rows(rows, cols);
+        let x = x.as_slice();
+
+        let y_chunks = y.as_mut_slice().chunks_mut(rows_per_chunk);
+        let a_chunks = self.data.chunks(rows_per_chunk * cols);
+        parallel.for_each(y_chunks.zip(a_chunks), |(y_chunk, a_chunk)| {
+            for (y_i, row) in y_chunk.iter_mut().zip(a_chunk.chunks(cols)) {
+                *y_i = row
+                    .iter()
+                    .zip(x)
+                    .fold(T::zero(), |sum, (&a, &x)| sum + a * x);
+            }
+        });
+        y
+    }
+
+    /// Each task sums `x_i * row_i` over a chunk of rows, then the partial sums are added up.
+    fn vecmat(&self, x: &Vector<T>) -> Vector<T> {
+        if let Err(e) = check_vecmat(x.len(), self.shape) {
+            panic!("{}", e);
+        }
+        let cols = self.num_cols();
+        let x = x.as_slice();
+        let partials = self.map_chunks(self.row_chunk_size(), |start, chunk| {
+            let mut partial = vec![T::zero(); cols];
+            for (&x_i, row) in x[start / cols..].iter().zip(chunk.chunks(cols)) {
+                for (p_j, &a_ij) in partial.iter_mut().zip(row) {
+                    *p_j += x_i * a_ij;
                 }
             }
+            partial
         });
+        add_partials(partials, cols).into()
+    }
 
-        Self::new_from_vec(res, self_rows, other_cols)
+    fn sum(&self) -> T {
+        let partials = self.map_chunks(self.element_chunk_size(), |_, chunk| sum(chunk));
+        sum(&partials)
     }
 
-    fn scalar_multiplication(&self, scalar: f64) -> Self {
-        let mut data = vec![0.0; self.num_rows() * self.num_cols()];
-        let chunk_size = (data.len() + NUM_THREADS - 1) / NUM_THREADS;
-        let chunks = data.chunks_mut(chunk_size);
-        let self_chunks = self.data.chunks(chunk_size);
+    fn row_sums(&self) -> Vector<T> {
+        let cols = self.num_cols();
+        self.map_chunks(self.row_chunk_size(), |_, chunk| {
+            chunk.chunks(cols).map(sum).collect::<Vec<_>>()
+        })
+        .concat()
+        .into()
+    }
 
-        scope(|s| {
-            for (data_chunk, self_chunk) in izip!(chunks, self_chunks) {
-                s.spawn(|_| {
-                    for i in 0..data_chunk.len() {
-                        data_chunk[i] = self_chunk[i] * scalar;
-                    }
-                });
-            }
+    fn col_sums(&self) -> Vector<T> {
+        self.column_sums_with(|_, a| a).into()
+    }
+
+    fn transpose(&self) -> Self {
+        self.transpose_with(|x| x)
+    }
+
+    fn conjugate_transpose(&self) -> Self {
+        self.transpose_with(T::conj)
+    }
+}
+
+impl<T: Element> FlatMatrix<T> for MultithreadMatrix<T> {
+    fn from_vec(data: Vec<T>, rows: usize, cols: usize) -> Self {
+        assert_eq!(data.len(), rows * cols, "data length must be rows * cols");
+        Self::new_from_vec(data, rows, cols)
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.data
+    }
+
+    fn tuning(&self) -> TuningConfig {
+        self.tuning
+    }
+}
+
+impl<T: FloatElement> LinearAlgebra<T> for MultithreadMatrix<T> {
+    fn cholesky(&self) -> Result<Cholesky<T, Self>, MatrixError> {
+        let block_size = self.tuning.cholesky_block_size();
+        let parallel = self.parallel();
+        Cholesky::factor_with(self, |a, n| {
+            let threads = parallel.threads_for_flops(n * n * n / 3);
+            blocked_cholesky_in_place(a, n, block_size, threads)
         })
-        .unwrap();
+    }
+}
+
+impl<T: FloatElement> Level1<T> for MultithreadMatrix<T> {}
+
+/// Reductions over the entries or rows split the data like `matrix_addition`, and column
+/// reductions add up per-thread partial sums like `vecmat`.
+impl<T: FloatElement> Reductions<T> for MultithreadMatrix<T> {
+    fn norm_frobenius(&self) -> T {
+        self.map_chunks(self.element_chunk_size(), |_, chunk| level1::nrm2(chunk))
+            .into_iter()
+            .fold(T::zero(), T::hypot)
+    }
+
+    fn norm_1(&self) -> T {
+        self.column_sums_with(|_, a| a.abs())
+            .into_iter()
+            .fold(T::zero(), nan_max)
+    }
+
+    fn norm_inf(&self) -> T {
+        let cols = self.num_cols();
+        self.map_chunks(self.row_chunk_size(), |_, chunk| {
+            chunk
+                .chunks(cols)
+                .map(level1::asum)
+                .fold(T::zero(), nan_max)
+        })
+        .into_iter()
+        .fold(T::zero(), nan_max)
+    }
+
+    fn norm_max(&self) -> T {
+        self.map_chunks(self.element_chunk_size(), |_, chunk| {
+            chunk
+                .iter()
+                .fold(T::zero(), |max, &a| nan_max(max, a.abs()))
+        })
+        .into_iter()
+        .fold(T::zero(), nan_max)
+    }
+
+    fn argmin(&self) -> (usize, usize) {
+        self.arg_extremum(|a, b| a < b)
+    }
+
+    fn argmax(&self) -> (usize, usize) {
+        self.arg_extremum(|a, b| a > b)
+    }
+
+    fn row_variances(&self) -> Vector<T> {
+        let cols = self.num_cols();
+        self.map_chunks(self.row_chunk_size(), |_, chunk| {
+            chunk.chunks(cols).map(variance).collect::<Vec<_>>()
+        })
+        .concat()
+        .into()
+    }
+
+    fn col_variances(&self) -> Vector<T> {
+        let means = self.col_means();
+        let rows = T::from(self.num_rows()).unwrap();
+        self.column_sums_with(|j, a| (a - means[j]).powi(2))
+            .into_iter()
+            .map(|sum| sum / rows)
+            .collect::<Vec<_>>()
+            .into()
+    }
+}
+
+impl<T: FloatElement> MultithreadMatrix<T> {
+    /// Finds the first extremum of each chunk on its own thread, then the first among those.
+    fn arg_extremum(&self, better: impl Fn(T, T) -> bool + Sync) -> (usize, usize) {
+        let candidates = self.map_chunks(self.element_chunk_size(), |start, chunk| {
+            let entries = chunk.iter().copied().enumerate();
+            first_extremum(entries.map(|(i, a)| (start + i, a)), &better)
+        });
+        position(
+            first_extremum(candidates.into_iter().flatten(), &better),
+            self.num_cols(),
+        )
+    }
+}
+
+impl<T: Element> Index<(usize, usize)> for MultithreadMatrix<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        assert_index((row, col), self.shape);
+        &self.data[row * self.num_cols() + col]
+    }
+}
 
-        Self::new_from_vec(data, self.num_rows(), self.num_cols())
+impl<T: Element> IndexMut<(usize, usize)> for MultithreadMatrix<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        self.get_mut(row, col)
     }
 }
@@ -1,15 +1,20 @@
-use crate::Matrix;
-use ndarray::Array2;
+use crate::error::{
+    assert_data, assert_index, check_gemm, check_matvec, check_same_shape, check_vecmat,
+};
+use crate::{Element, FloatElement, Matrix, Reductions, Vector};
+use ndarray::linalg::general_mat_mul;
+use ndarray::{Array2, ArrayView1, Axis, Zip};
+use std::ops::{Index, IndexMut};
 
 /// Wrapper around ndarray::Array2
 #[derive(Debug)]
-pub struct NdarrayMatrix {
-    data: Array2<f64>,
+pub struct NdarrayMatrix<T = f64> {
+    data: Array2<T>,
 }
 
-impl Matrix for NdarrayMatrix {
-    fn new(data: Vec<Vec<f64>>) -> Self {
-        let shape = (data.len(), data[0].len());
+impl<T: Element> Matrix<T> for NdarrayMatrix<T> {
+    fn new(data: Vec<Vec<T>>) -> Self {
+        let shape = assert_data(&data);
         let converted_data =
             Array2::from_shape_vec(shape, data.iter().flatten().cloned().collect())
                 .expect("Failed to convert vector into Array2");
@@ -30,7 +35,7 @@ impl Matrix for NdarrayMatrix {
         self.data.ncols()
     }
 
-    fn get_data(&self) -> Vec<Vec<f64>> {
+    fn get_data(&self) -> Vec<Vec<T>> {
         self.data
             .rows()
             .into_iter()
@@ -38,6 +43,35 @@ impl Matrix for NdarrayMatrix {
             .collect()
     }
 
+    fn as_slice(&self) -> Option<&[T]> {
+        self.data.as_slice()
+    }
+
+    fn row(&self, row: usize) -> &[T] {
+        self.data
+            .row(row)
+            .to_slice()
+            .expect("rows of a standard layout array are contiguous")
+    }
+
+    fn into_vec(self) -> Vec<T> {
+        if self.data.is_standard_layout() {
+            self.data.into_raw_vec_and_offset().0
+        } else {
+            self.data.iter().copied().collect()
+        }
+    }
+
+    fn get(&self, row: usize, col: usize) -> T {
+        assert_index((row, col), self.shape());
+        self.data[[row, col]]
+    }
+
+    fn get_mut(&mut self, row: usize, col: usize) -> &mut T {
+        assert_index((row, col), self.shape());
+        &mut self.data[[row, col]]
+    }
+
     fn zeroes(rows: usize, cols: usize) -> Self {
         let shape = (rows, cols);
         let data = Array2::zeros(shape);
@@ -50,12 +84,18 @@ impl Matrix for NdarrayMatrix {
     }
 
     fn matrix_addition(&self, other: &Self) -> Self {
+        if let Err(e) = check_same_shape(self.shape(), other.shape()) {
+            panic!("{}", e);
+        }
         Self {
             data: &self.data + &other.data,
         }
     }
 
     fn matrix_subtraction(&self, other: &Self) -> Self {
+        if let Err(e) = check_same_shape(self.shape(), other.shape()) {
+            panic!("{}", e);
+        }
         Self {
             data: &self.data - &other.data,
         }
@@ -67,9 +107,112 @@ impl Matrix for NdarrayMatrix {
         }
     }
 
-    fn scalar_multiplication(&self, scalar: f64) -> Self {
+    fn scalar_multiplication(&self, scalar: T) -> Self {
         Self {
-            data: &self.data * scalar,
+            data: self.data.mapv(|a| a * scalar),
+        }
+    }
+
+    fn add_assign(&mut self, other: &Self) {
+        if let Err(e) = check_same_shape(self.shape(), other.shape()) {
+            panic!("{}", e);
+        }
+        self.data += &other.data;
+    }
+
+    fn sub_assign(&mut self, other: &Self) {
+        if let Err(e) = check_same_shape(self.shape(), other.shape()) {
+            panic!("{}", e);
+        }
+        self.data -= &other.data;
+    }
+
+    fn scale_in_place(&mut self, scalar: T) {
+        self.data.mapv_inplace(|a| a * scalar);
+    }
+
+    fn gemm(alpha: T, a: &Self, b: &Self, beta: T, c: &mut Self) {
+        if let Err(e) = check_gemm(a.shape(), b.shape(), c.shape()) {
+            panic!("{}", e);
+        }
+        general_mat_mul(alpha, &a.data, &b.data, beta, &mut c.data);
+    }
+
+    fn matvec(&self, x: &Vector<T>) -> Vector<T> {
+        if let Err(e) = check_matvec(self.shape(), x.len()) {
+            panic!("{}", e);
+        }
+        self.data
+            .dot(&ArrayView1::from(x.as_slice()))
+            .to_vec()
+            .into()
+    }
+
+    fn vecmat(&self, x: &Vector<T>) -> Vector<T> {
+        if let Err(e) = check_vecmat(x.len(), self.shape()) {
+            panic!("{}", e);
         }
+        ArrayView1::from(x.as_slice())
+            .dot(&self.data)
+            .to_vec()
+            .into()
+    }
+
+    fn map(&self, f: impl Fn(T) -> T + Sync) -> Self {
+        Self {
+            data: self.data.mapv(f),
+        }
+    }
+
+    fn zip_map(&self, other: &Self, f: impl Fn(T, T) -> T + Sync) -> Self {
+        if let Err(e) = check_same_shape(self.shape(), other.shape()) {
+            panic!("{}", e);
+        }
+        Self {
+            data: Zip::from(&self.data)
+                .and(&other.data)
+                .map_collect(|&a, &b| f(a, b)),
+        }
+    }
+
+    fn sum(&self) -> T {
+        self.data.sum()
+    }
+
+    fn row_sums(&self) -> Vector<T> {
+        self.data.sum_axis(Axis(1)).to_vec().into()
+    }
+
+    fn col_sums(&self) -> Vector<T> {
+        self.data.sum_axis(Axis(0)).to_vec().into()
+    }
+
+    fn transpose(&self) -> Self {
+        Self {
+            data: self.data.t().as_standard_layout().into_owned(),
+        }
+    }
+
+    fn conjugate_transpose(&self) -> Self {
+        Self {
+            data: self.data.t().as_standard_layout().mapv(|x| x.conj()),
+        }
+    }
+}
+
+impl<T: FloatElement> Reductions<T> for NdarrayMatrix<T> {}
+
+impl<T: Element> Index<(usize, usize)> for NdarrayMatrix<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        assert_index((row, col), self.shape());
+        &self.data[[row, col]]
+    }
+}
+
+impl<T: Element> IndexMut<(usize, usize)> for NdarrayMatrix<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        self.get_mut(row, col)
     }
 }
@@ -1,34 +1,47 @@
-use crate::Matrix;
+use crate::error::{assert_data, assert_index, check_same_shape};
+use crate::matrices::transpose::{conjugate_transpose, transpose};
+use crate::simd::{self, BinaryOp};
+use crate::{Element, FlatMatrix, FloatElement, Level1, LinearAlgebra, Matrix, Reductions};
+use std::ops::{Index, IndexMut};
 
 /// Representing matrix as a one-dimensional vector
 #[derive(Debug)]
-pub struct OneDVecMatrix {
-    data: Vec<f64>,
+pub struct OneDVecMatrix<T = f64> {
+    data: Vec<T>,
     shape: (usize, usize),
 }
 
-impl OneDVecMatrix {
-    fn new_from_vec(data: Vec<f64>, rows: usize, cols: usize) -> Self {
+impl<T: Element> OneDVecMatrix<T> {
+    fn new_from_vec(data: Vec<T>, rows: usize, cols: usize) -> Self {
         Self {
             data,
             shape: (rows, cols),
         }
     }
 
-    pub fn get_data_vec(&self) -> &Vec<f64> {
+    pub fn get_data_vec(&self) -> &Vec<T> {
         &self.data
     }
 
     #[inline(always)]
-    pub fn get(&self, row: usize, col: usize) -> f64 {
+    pub fn get(&self, row: usize, col: usize) -> T {
         self.data[row * self.num_cols() + col]
     }
+
+    /// Applies `op` element-wise with `other`, using the SIMD kernels for `f32` and `f64`.
+    fn zip_op(&self, other: &Self, op: BinaryOp) -> Self {
+        if let Err(e) = check_same_shape(self.shape(), other.shape()) {
+            panic!("{}", e);
+        }
+        let mut data = vec![T::zero(); self.data.len()];
+        simd::zip_into(op, &self.data, &other.data, &mut data);
+        Self::new_from_vec(data, self.num_rows(), self.num_cols())
+    }
 }
 
-impl Matrix for OneDVecMatrix {
-    fn new(data: Vec<Vec<f64>>) -> Self {
-        let rows = data.len();
-        let cols = data[0].len();
+impl<T: Element> Matrix<T> for OneDVecMatrix<T> {
+    fn new(data: Vec<Vec<T>>) -> Self {
+        let (rows, cols) = assert_data(&data);
         let data = data.into_iter().flatten().collect();
         Self::new_from_vec(data, rows, cols)
     }
@@ -45,48 +58,60 @@ impl Matrix for OneDVecMatrix {
         self.shape.1
     }
 
-    fn get_data(&self) -> Vec<Vec<f64>> {
+    fn get_data(&self) -> Vec<Vec<T>> {
         self.data
             .chunks(self.num_cols())
             .map(|chunk| chunk.to_vec())
             .collect()
     }
 
+    fn as_slice(&self) -> Option<&[T]> {
+        Some(&self.data)
+    }
+
+    fn row(&self, row: usize) -> &[T] {
+        let cols = self.num_cols();
+        &self.data[row * cols..(row + 1) * cols]
+    }
+
+    fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
+    fn get(&self, row: usize, col: usize) -> T {
+        assert_index((row, col), self.shape);
+        self.data[row * self.num_cols() + col]
+    }
+
+    fn get_mut(&mut self, row: usize, col: usize) -> &mut T {
+        assert_index((row, col), self.shape);
+        let cols = self.num_cols();
+        &mut self.data[row * cols + col]
+    }
+
     fn zeroes(rows: usize, cols: usize) -> Self {
         Self {
-            data: vec![0.0; rows * cols],
+            data: vec![T::zero(); rows * cols],
             shape: (rows, cols),
         }
     }
 
     fn identity(size: usize) -> Self {
-        let mut data = vec![0.0; size * size];
+        let mut data = vec![T::zero(); size * size];
 
         for i in 0..size {
-            data[i * size + i] = 1.0;
+            data[i * size + i] = T::one();
         }
 
         OneDVecMatrix::new_from_vec(data, size, size)
     }
 
     fn matrix_addition(&self, other: &Self) -> Self {
-        let data = self
-            .data
-            .iter()
-            .zip(other.data.iter())
-            .map(|(a, b)| a + b)
-            .collect();
-        Self::new_from_vec(data, self.num_rows(), self.num_cols())
+        self.zip_op(other, BinaryOp::Add)
     }
 
     fn matrix_subtraction(&self, other: &Self) -> Self {
-        let data = self
-            .data
-            .iter()
-            .zip(other.data.iter())
-            .map(|(a, b)| a - b)
-            .collect();
-        Self::new_from_vec(data, self.num_rows(), self.num_cols())
+        self.zip_op(other, BinaryOp::Sub)
     }
 
     fn matrix_multiplication(&self, other: &Self) -> Self {
@@ -94,7 +119,7 @@ impl Matrix for OneDVecMatrix {
         let self_cols = self.num_cols();
         let other_cols = other.num_cols();
 
-        let mut res = vec![0.0; self_rows * other_cols];
+        let mut res = vec![T::zero(); self_rows * other_cols];
 
         for i in 0..self_rows {
             for j in 0..other_cols {
@@ -107,8 +132,87 @@ impl Matrix for OneDVecMatrix {
         Self::new_from_vec(res, self_rows, other_cols)
     }
 
-    fn scalar_multiplication(&self, scalar: f64) -> Self {
-        let data = self.data.iter().map(|a| a * scalar).collect();
+    fn scalar_multiplication(&self, scalar: T) -> Self {
+        let mut data = vec![T::zero(); self.data.len()];
+        simd::scale_into(&self.data, scalar, &mut data);
         Self::new_from_vec(data, self.num_rows(), self.num_cols())
     }
+
+    fn hadamard_product(&self, other: &Self) -> Self {
+        if let Err(e) = check_same_shape(self.shape, other.shape) {
+            panic!("{}", e);
+        }
+        self.zip_op(other, BinaryOp::Mul)
+    }
+
+    fn elementwise_division(&self, other: &Self) -> Self {
+        if let Err(e) = check_same_shape(self.shape, other.shape) {
+            panic!("{}", e);
+        }
+        self.zip_op(other, BinaryOp::Div)
+    }
+
+    fn add_assign(&mut self, other: &Self) {
+        if let Err(e) = check_same_shape(self.shape(), other.shape()) {
+            panic!("{}", e);
+        }
+        simd::zip_assign(BinaryOp::Add, &mut self.data, &other.data);
+    }
+
+    fn sub_assign(&mut self, other: &Self) {
+        if let Err(e) = check_same_shape(self.shape(), other.shape()) {
+            panic!("{}", e);
+        }
+        simd::zip_assign(BinaryOp::Sub, &mut self.data, &other.data);
+    }
+
+    fn scale_in_place(&mut self, scalar: T) {
+        simd::scale_assign(&mut self.data, scalar);
+    }
+
+    fn gemm(alpha: T, a: &Self, b: &Self, beta: T, c: &mut Self) {
+        Self::view_gemm(alpha, &a.view(), &b.view(), beta, &mut c.view_mut());
+    }
+
+    fn transpose(&self) -> Self {
+        let data = transpose(&self.data, self.num_rows(), self.num_cols());
+        Self::new_from_vec(data, self.num_cols(), self.num_rows())
+    }
+
+    fn conjugate_transpose(&self) -> Self {
+        let data = conjugate_transpose(&self.data, self.num_rows(), self.num_cols());
+        Self::new_from_vec(data, self.num_cols(), self.num_rows())
+    }
+}
+
+impl<T: Element> FlatMatrix<T> for OneDVecMatrix<T> {
+    fn from_vec(data: Vec<T>, rows: usize, cols: usize) -> Self {
+        assert_eq!(data.len(), rows * cols, "data length must be rows * cols");
+        Self::new_from_vec(data, rows, cols)
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.data
+    }
+}
+
+impl<T: FloatElement> LinearAlgebra<T> for OneDVecMatrix<T> {}
+
+impl<T: FloatElement> Level1<T> for OneDVecMatrix<T> {}
+
+impl<T: FloatElement> Reductions<T> for OneDVecMatrix<T> {}
+
+impl<T: Element> Index<(usize, usize)> for OneDVecMatrix<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        assert_index((row, col), self.shape);
+        &self.data[row * self.num_cols() + col]
+    }
+}
+
+impl<T: Element> IndexMut<(usize, usize)> for OneDVecMatrix<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        self.get_mut(row, col)
+    }
 }
@@ -0,0 +1,161 @@
+// Arithmetic operators for every backend, delegating to the `Matrix` trait methods.
+// Mismatched shapes panic with the corresponding `MatrixError` message.
+
+use crate::blas::BlasElement;
+use crate::matrices::basic_matrix::BasicMatrix;
+use crate::matrices::blas_matrix::BlasMatrix;
+use crate::matrices::blocked_matrix::BlockedMatrix;
+use crate::matrices::multithread_matrix::MultithreadMatrix;
+use crate::matrices::ndarray_matrix::NdarrayMatrix;
+use crate::matrices::one_d_vec_matrix::OneDVecMatrix;
+use crate::matrices::packed_matrix::PackedMatrix;
+use crate::matrices::transposed_view_matrix::TransposedViewMatrix;
+use crate::{Element, Matrix};
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+macro_rules! impl_binary_op {
+    ($matrix:ident, $bound:ident, $op:ident, $method:ident, $try_method:ident) => {
+        impl<T: $bound> $op<&$matrix<T>> for &$matrix<T> {
+            type Output = $matrix<T>;
+
+            fn $method(self, rhs: &$matrix<T>) -> $matrix<T> {
+                self.$try_method(rhs).unwrap_or_else(|e| panic!("{}", e))
+            }
+        }
+
+        impl<T: $bound> $op<$matrix<T>> for &$matrix<T> {
+            type Output = $matrix<T>;
+
+            fn $method(self, rhs: $matrix<T>) -> $matrix<T> {
+                self.$method(&rhs)
+            }
+        }
+
+        impl<T: $bound> $op<&$matrix<T>> for $matrix<T> {
+            type Output = $matrix<T>;
+
+            fn $method(self, rhs: &$matrix<T>) -> $matrix<T> {
+                (&self).$method(rhs)
+            }
+        }
+
+        impl<T: $bound> $op<$matrix<T>> for $matrix<T> {
+            type Output = $matrix<T>;
+
+            fn $method(self, rhs: $matrix<T>) -> $matrix<T> {
+                (&self).$method(&rhs)
+            }
+        }
+    };
+}
+
+macro_rules! impl_assign_op {
+    ($matrix:ident, $bound:ident, $op:ident, $method:ident, $try_method:ident) => {
+        impl<T: $bound> $op<&$matrix<T>> for $matrix<T> {
+            fn $method(&mut self, rhs: &$matrix<T>) {
+                self.$try_method(rhs).unwrap_or_else(|e| panic!("{}", e))
+            }
+        }
+
+        impl<T: $bound> $op<$matrix<T>> for $matrix<T> {
+            fn $method(&mut self, rhs: $matrix<T>) {
+                self.$try_method(&rhs).unwrap_or_else(|e| panic!("{}", e))
+            }
+        }
+    };
+}
+
+macro_rules! impl_left_scalar_mul {
+    ($matrix:ident, $($t:ty),*) => {
+        $(
+            impl Mul<&$matrix<$t>> for $t {
+                type Output = $matrix<$t>;
+
+                fn mul(self, rhs: &$matrix<$t>) -> $matrix<$t> {
+                    rhs.scalar_multiplication(self)
+                }
+            }
+
+            impl Mul<$matrix<$t>> for $t {
+                type Output = $matrix<$t>;
+
+                fn mul(self, rhs: $matrix<$t>) -> $matrix<$t> {
+                    rhs.scalar_multiplication(self)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_matrix_ops {
+    ($matrix:ident, $bound:ident) => {
+        impl_binary_op!($matrix, $bound, Add, add, try_matrix_addition);
+        impl_binary_op!($matrix, $bound, Sub, sub, try_matrix_subtraction);
+        impl_binary_op!($matrix, $bound, Mul, mul, try_matrix_multiplication);
+
+        impl_assign_op!($matrix, $bound, AddAssign, add_assign, try_add_assign);
+        impl_assign_op!($matrix, $bound, SubAssign, sub_assign, try_sub_assign);
+
+        // The product can't be written over its own operand, so this one still allocates.
+        impl<T: $bound> MulAssign<&$matrix<T>> for $matrix<T> {
+            fn mul_assign(&mut self, rhs: &$matrix<T>) {
+                *self = (&*self).mul(rhs);
+            }
+        }
+
+        impl<T: $bound> MulAssign<$matrix<T>> for $matrix<T> {
+            fn mul_assign(&mut self, rhs: $matrix<T>) {
+                *self = (&*self).mul(&rhs);
+            }
+        }
+
+        impl<T: $bound> Mul<T> for &$matrix<T> {
+            type Output = $matrix<T>;
+
+            fn mul(self, rhs: T) -> $matrix<T> {
+                self.scalar_multiplication(rhs)
+            }
+        }
+
+        impl<T: $bound> Mul<T> for $matrix<T> {
+            type Output = $matrix<T>;
+
+            fn mul(self, rhs: T) -> $matrix<T> {
+                self.scalar_multiplication(rhs)
+            }
+        }
+
+        impl<T: $bound> MulAssign<T> for $matrix<T> {
+            fn mul_assign(&mut self, rhs: T) {
+                self.scale_in_place(rhs);
+            }
+        }
+
+        impl<T: $bound + Neg<Output = T>> Neg for &$matrix<T> {
+            type Output = $matrix<T>;
+
+            fn neg(self) -> $matrix<T> {
+                self.scalar_multiplication(-T::one())
+            }
+        }
+
+        impl<T: $bound + Neg<Output = T>> Neg for $matrix<T> {
+            type Output = $matrix<T>;
+
+            fn neg(self) -> $matrix<T> {
+                -&self
+            }
+        }
+
+        impl_left_scalar_mul!($matrix, f32, f64);
+    };
+}
+
+impl_matrix_ops!(BasicMatrix, Element);
+impl_matrix_ops!(OneDVecMatrix, Element);
+impl_matrix_ops!(TransposedViewMatrix, Element);
+impl_matrix_ops!(BlockedMatrix, Element);
+impl_matrix_ops!(MultithreadMatrix, Element);
+impl_matrix_ops!(NdarrayMatrix, Element);
+impl_matrix_ops!(PackedMatrix, Element);
+impl_matrix_ops!(BlasMatrix, BlasElement);
@@ -0,0 +1,271 @@
+use crate::error::{assert_data, assert_index, check_gemm, check_multipliable, check_same_shape};
+use crate::gemm::packed_gemm;
+use crate::matrices::transpose::{conjugate_transpose, transpose};
+use crate::simd::{self, BinaryOp};
+use crate::tuning::{self, TuningConfig};
+use crate::{
+    Element, FlatMatrix, FloatElement, Level1, LinearAlgebra, Matrix, MatrixView, MatrixViewMut,
+    Reductions,
+};
+use std::ops::{Index, IndexMut};
+
+/// Representing matrix as a one-dimensional vector, multiplied by packing blocks of the operands
+/// into contiguous panels for a register-blocked micro-kernel, as in BLIS
+#[derive(Debug)]
+pub struct PackedMatrix<T = f64> {
+    data: Vec<T>,
+    shape: (usize, usize),
+    tuning: TuningConfig,
+}
+
+impl<T: Element> PackedMatrix<T> {
+    fn new_from_vec(data: Vec<T>, rows: usize, cols: usize) -> Self {
+        Self {
+            data,
+            shape: (rows, cols),
+            tuning: tuning::config(),
+        }
+    }
+
+    /// Wraps `data` like `new_from_vec`, keeping this matrix's tuning.
+    fn with_data(&self, data: Vec<T>, rows: usize, cols: usize) -> Self {
+        Self {
+            data,
+            shape: (rows, cols),
+            tuning: self.tuning,
+        }
+    }
+
+    /// Uses the block sizes of `tuning` instead of [`tuning::config`]. The matrices returned by
+    /// this one's arithmetic and transposes keep it.
+    pub fn with_tuning(self, tuning: TuningConfig) -> Self {
+        Self { tuning, ..self }
+    }
+
+    pub fn get_data_vec(&self) -> &Vec<T> {
+        &self.data
+    }
+
+    #[inline(always)]
+    pub fn get(&self, row: usize, col: usize) -> T {
+        self.data[row * self.num_cols() + col]
+    }
+
+    /// Applies `op` element-wise with `other`, using the SIMD kernels for `f32` and `f64`.
+    fn zip_op(&self, other: &Self, op: BinaryOp) -> Self {
+        if let Err(e) = check_same_shape(self.shape(), other.shape()) {
+            panic!("{}", e);
+        }
+        let mut data = vec![T::zero(); self.data.len()];
+        simd::zip_into(op, &self.data, &other.data, &mut data);
+        self.with_data(data, self.num_rows(), self.num_cols())
+    }
+}
+
+impl<T: Element> Matrix<T> for PackedMatrix<T> {
+    fn new(data: Vec<Vec<T>>) -> Self {
+        let (rows, cols) = assert_data(&data);
+        let data = data.into_iter().flatten().collect();
+        Self::new_from_vec(data, rows, cols)
+    }
+
+    fn shape(&self) -> (usize, usize) {
+        self.shape
+    }
+
+    fn num_rows(&self) -> usize {
+        self.shape.0
+    }
+
+    fn num_cols(&self) -> usize {
+        self.shape.1
+    }
+
+    fn get_data(&self) -> Vec<Vec<T>> {
+        self.data
+            .chunks(self.num_cols())
+            .map(|chunk| chunk.to_vec())
+            .collect()
+    }
+
+    fn as_slice(&self) -> Option<&[T]> {
+        Some(&self.data)
+    }
+
+    fn row(&self, row: usize) -> &[T] {
+        let cols = self.num_cols();
+        &self.data[row * cols..(row + 1) * cols]
+    }
+
+    fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
+    fn get(&self, row: usize, col: usize) -> T {
+        assert_index((row, col), self.shape);
+        self.data[row * self.num_cols() + col]
+    }
+
+    fn get_mut(&mut self, row: usize, col: usize) -> &mut T {
+        assert_index((row, col), self.shape);
+        let cols = self.num_cols();
+        &mut self.data[row * cols + col]
+    }
+
+    fn zeroes(rows: usize, cols: usize) -> Self {
+        Self::new_from_vec(vec![T::zero(); rows * cols], rows, cols)
+    }
+
+    fn identity(size: usize) -> Self {
+        let mut data = vec![T::zero(); size * size];
+
+        for i in 0..size {
+            data[i * size + i] = T::one();
+        }
+
+        PackedMatrix::new_from_vec(data, size, size)
+    }
+
+    fn matrix_addition(&self, other: &Self) -> Self {
+        self.zip_op(other, BinaryOp::Add)
+    }
+
+    fn matrix_subtraction(&self, other: &Self) -> Self {
+        self.zip_op(other, BinaryOp::Sub)
+    }
+
+    fn matrix_multiplication(&self, other: &Self) -> Self {
+        let (rows, cols) = (self.num_rows(), other.num_cols());
+        let mut res = self.with_data(vec![T::zero(); rows * cols], rows, cols);
+        Self::gemm(T::one(), self, other, T::zero(), &mut res);
+        res
+    }
+
+    fn scalar_multiplication(&self, scalar: T) -> Self {
+        let mut data = vec![T::zero(); self.data.len()];
+        simd::scale_into(&self.data, scalar, &mut data);
+        self.with_data(data, self.num_rows(), self.num_cols())
+    }
+
+    fn hadamard_product(&self, other: &Self) -> Self {
+        if let Err(e) = check_same_shape(self.shape, other.shape) {
+            panic!("{}", e);
+        }
+        self.zip_op(other, BinaryOp::Mul)
+    }
+
+    fn elementwise_division(&self, other: &Self) -> Self {
+        if let Err(e) = check_same_shape(self.shape, other.shape) {
+            panic!("{}", e);
+        }
+        self.zip_op(other, BinaryOp::Div)
+    }
+
+    fn add_assign(&mut self, other: &Self) {
+        if let Err(e) = check_same_shape(self.shape(), other.shape()) {
+            panic!("{}", e);
+        }
+        simd::zip_assign(BinaryOp::Add, &mut self.data, &other.data);
+    }
+
+    fn sub_assign(&mut self, other: &Self) {
+        if let Err(e) = check_same_shape(self.shape(), other.shape()) {
+            panic!("{}", e);
+        }
+        simd::zip_assign(BinaryOp::Sub, &mut self.data, &other.data);
+    }
+
+    fn scale_in_place(&mut self, scalar: T) {
+        simd::scale_assign(&mut self.data, scalar);
+    }
+
+    fn gemm(alpha: T, a: &Self, b: &Self, beta: T, c: &mut Self) {
+        if let Err(e) = check_gemm(a.shape(), b.shape(), c.shape()) {
+            panic!("{}", e);
+        }
+        let block_sizes = a.tuning.packed_block_sizes();
+        packed_gemm(
+            alpha,
+            &a.view(),
+            &b.view(),
+            beta,
+            &mut c.view_mut(),
+            block_sizes,
+        );
+    }
+
+    fn transpose(&self) -> Self {
+        let data = transpose(&self.data, self.num_rows(), self.num_cols());
+        self.with_data(data, self.num_cols(), self.num_rows())
+    }
+
+    fn conjugate_transpose(&self) -> Self {
+        let data = conjugate_transpose(&self.data, self.num_rows(), self.num_cols());
+        self.with_data(data, self.num_cols(), self.num_rows())
+    }
+}
+
+impl<T: Element> FlatMatrix<T> for PackedMatrix<T> {
+    fn from_vec(data: Vec<T>, rows: usize, cols: usize) -> Self {
+        assert_eq!(data.len(), rows * cols, "data length must be rows * cols");
+        Self::new_from_vec(data, rows, cols)
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.data
+    }
+
+    fn tuning(&self) -> TuningConfig {
+        self.tuning
+    }
+
+    fn view_multiplication(a: &MatrixView<T>, b: &MatrixView<T>) -> Self {
+        if let Err(e) = check_multipliable(a.shape(), b.shape()) {
+            panic!("{}", e);
+        }
+        let mut res = Self::zeroes(a.num_rows(), b.num_cols());
+        packed_gemm(
+            T::one(),
+            a,
+            b,
+            T::zero(),
+            &mut res.view_mut(),
+            tuning::config().packed_block_sizes(),
+        );
+        res
+    }
+
+    fn view_gemm(
+        alpha: T,
+        a: &MatrixView<T>,
+        b: &MatrixView<T>,
+        beta: T,
+        c: &mut MatrixViewMut<T>,
+    ) {
+        if let Err(e) = check_gemm(a.shape(), b.shape(), c.shape()) {
+            panic!("{}", e);
+        }
+        packed_gemm(alpha, a, b, beta, c, tuning::config().packed_block_sizes());
+    }
+}
+
+impl<T: FloatElement> LinearAlgebra<T> for PackedMatrix<T> {}
+
+impl<T: FloatElement> Level1<T> for PackedMatrix<T> {}
+
+impl<T: FloatElement> Reductions<T> for PackedMatrix<T> {}
+
+impl<T: Element> Index<(usize, usize)> for PackedMatrix<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        assert_index((row, col), self.shape);
+        &self.data[row * self.num_cols() + col]
+    }
+}
+
+impl<T: Element> IndexMut<(usize, usize)> for PackedMatrix<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        self.get_mut(row, col)
+    }
+}
@@ -0,0 +1,54 @@
+use crate::Element;
+use std::cmp::min;
+
+// Tiles of this size keep both the rows being read and the rows being written in cache.
+const TILE_SIZE: usize = 32;
+
+/// Writes rows `first_row..` of the transpose of the row-major `rows x cols` matrix `src`
+/// into `dst`, applying `f` to every element. `dst` must hold a whole number of rows.
+///
+/// Passing a sub-slice of the output lets several threads fill disjoint rows concurrently.
+pub(crate) fn transpose_rows_into<T: Element>(
+    src: &[T],
+    rows: usize,
+    cols: usize,
+    dst: &mut [T],
+    first_row: usize,
+    f: fn(T) -> T,
+) {
+    if rows == 0 {
+        return;
+    }
+    let dst_rows = dst.len() / rows;
+    debug_assert!(first_row + dst_rows <= cols);
+
+    for bj in (0..dst_rows).step_by(TILE_SIZE) {
+        for bi in (0..rows).step_by(TILE_SIZE) {
+            for j in bj..min(bj + TILE_SIZE, dst_rows) {
+                let col = first_row + j;
+                for i in bi..min(bi + TILE_SIZE, rows) {
+                    dst[j * rows + i] = f(src[i * cols + col]);
+                }
+            }
+        }
+    }
+}
+
+/// Cache-blocked transpose of the row-major `rows x cols` matrix `src` into `dst`.
+pub(crate) fn transpose_into<T: Element>(src: &[T], rows: usize, cols: usize, dst: &mut [T]) {
+    transpose_rows_into(src, rows, cols, dst, 0, |x| x);
+}
+
+/// Transpose of the row-major `rows x cols` matrix stored in `data`.
+pub(crate) fn transpose<T: Element>(data: &[T], rows: usize, cols: usize) -> Vec<T> {
+    let mut res = vec![T::zero(); data.len()];
+    transpose_into(data, rows, cols, &mut res);
+    res
+}
+
+/// Conjugate transpose of the row-major `rows x cols` matrix stored in `data`.
+pub(crate) fn conjugate_transpose<T: Element>(data: &[T], rows: usize, cols: usize) -> Vec<T> {
+    let mut res = vec![T::zero(); data.len()];
+    transpose_rows_into(data, rows, cols, &mut res, 0, T::conj);
+    res
+}
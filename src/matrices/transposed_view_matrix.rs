@@ -1,26 +1,25 @@
-use crate::Matrix;
+use crate::error::{assert_data, assert_index, check_gemm, check_same_shape};
+use crate::matrices::transpose::{transpose, transpose_into};
+use crate::{Element, FloatElement, Matrix, Reductions};
+use std::ops::{Index, IndexMut};
 
 /// Representing matrix as a one-dimensional vector
 #[derive(Debug)]
-pub struct TransposedViewMatrix {
-    data: Vec<f64>,
-    transposed_data: Vec<f64>,
+pub struct TransposedViewMatrix<T = f64> {
+    data: Vec<T>,
+    transposed_data: Vec<T>,
     transposed: bool,
     shape: (usize, usize),
 }
 
-impl TransposedViewMatrix {
-    fn new_from_vec(data: Vec<f64>, rows: usize, cols: usize, eager: bool) -> Self {
-        let mut transposed_data = vec![0.0; data.len()];
+impl<T: Element> TransposedViewMatrix<T> {
+    fn new_from_vec(data: Vec<T>, rows: usize, cols: usize, eager: bool) -> Self {
+        let mut transposed_data = vec![T::zero(); data.len()];
         let mut transposed = false;
 
         // If eager, we will transpose the data immediately.
         if eager {
-            for i in 0..rows {
-                for j in 0..cols {
-                    transposed_data[j * rows + i] = data[i * cols + j];
-                }
-            }
+            transpose_into(&data, rows, cols, &mut transposed_data);
             transposed = true;
         }
 
@@ -32,29 +31,56 @@ impl TransposedViewMatrix {
         }
     }
 
-    pub fn get_data_vec(&self) -> &Vec<f64> {
+    pub fn get_data_vec(&self) -> &Vec<T> {
         &self.data
     }
 
-    pub fn get_transposed_data_vec(&self) -> &Vec<f64> {
+    /// Whether the transposed copy is in sync with the data. Writes through `get_mut` or
+    /// `IndexMut` invalidate it; multiplication then transposes on demand.
+    pub fn is_transposed(&self) -> bool {
+        self.transposed
+    }
+
+    pub fn get_transposed_data_vec(&self) -> &Vec<T> {
         &self.transposed_data
     }
 
     #[inline(always)]
-    pub fn get(&self, row: usize, col: usize) -> f64 {
+    pub fn get(&self, row: usize, col: usize) -> T {
         self.data[row * self.num_cols() + col]
     }
 
+    /// Applies `f` element-wise with `other`, keeping the transposed copy in sync when both are.
+    fn zip_in_place(&mut self, other: &Self, f: fn(&mut T, T)) {
+        if let Err(e) = check_same_shape(self.shape(), other.shape()) {
+            panic!("{}", e);
+        }
+        for (a, &b) in self.data.iter_mut().zip(other.data.iter()) {
+            f(a, b);
+        }
+
+        if self.transposed && other.transposed {
+            for (a, &b) in self
+                .transposed_data
+                .iter_mut()
+                .zip(other.transposed_data.iter())
+            {
+                f(a, b);
+            }
+        } else {
+            self.transposed = false;
+        }
+    }
+
     #[inline(always)]
-    pub fn get_transposed(&self, row: usize, col: usize) -> f64 {
+    pub fn get_transposed(&self, row: usize, col: usize) -> T {
         self.transposed_data[row * self.num_rows() + col]
     }
 }
 
-impl Matrix for TransposedViewMatrix {
-    fn new(data: Vec<Vec<f64>>) -> Self {
-        let rows = data.len();
-        let cols = data[0].len();
+impl<T: Element> Matrix<T> for TransposedViewMatrix<T> {
+    fn new(data: Vec<Vec<T>>) -> Self {
+        let (rows, cols) = assert_data(&data);
         let data = data.into_iter().flatten().collect();
         Self::new_from_vec(data, rows, cols, true)
     }
@@ -71,77 +97,190 @@ impl Matrix for TransposedViewMatrix {
         self.shape.1
     }
 
-    fn get_data(&self) -> Vec<Vec<f64>> {
+    fn get_data(&self) -> Vec<Vec<T>> {
         self.data
             .chunks(self.num_cols())
             .map(|chunk| chunk.to_vec())
             .collect()
     }
 
+    fn as_slice(&self) -> Option<&[T]> {
+        Some(&self.data)
+    }
+
+    fn row(&self, row: usize) -> &[T] {
+        let cols = self.num_cols();
+        &self.data[row * cols..(row + 1) * cols]
+    }
+
+    fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
+    fn get(&self, row: usize, col: usize) -> T {
+        assert_index((row, col), self.shape);
+        self.data[row * self.num_cols() + col]
+    }
+
+    fn get_mut(&mut self, row: usize, col: usize) -> &mut T {
+        assert_index((row, col), self.shape);
+        // The caller may write through the reference, so the transposed copy can no longer be trusted.
+        self.transposed = false;
+        let cols = self.num_cols();
+        &mut self.data[row * cols + col]
+    }
+
+    fn set(&mut self, row: usize, col: usize, value: T) {
+        assert_index((row, col), self.shape);
+        let (rows, cols) = self.shape;
+        self.data[row * cols + col] = value;
+        if self.transposed {
+            self.transposed_data[col * rows + row] = value;
+        }
+    }
+
     fn zeroes(rows: usize, cols: usize) -> Self {
         Self {
-            data: vec![0.0; rows * cols],
-            transposed_data: vec![0.0; rows * cols],
+            data: vec![T::zero(); rows * cols],
+            transposed_data: vec![T::zero(); rows * cols],
             transposed: true,
             shape: (rows, cols),
         }
     }
 
     fn identity(size: usize) -> Self {
-        let mut data = vec![0.0; size * size];
+        let mut data = vec![T::zero(); size * size];
 
         for i in 0..size {
-            data[i * size + i] = 1.0;
+            data[i * size + i] = T::one();
         }
 
         TransposedViewMatrix::new_from_vec(data, size, size, true)
     }
 
     fn matrix_addition(&self, other: &Self) -> Self {
+        if let Err(e) = check_same_shape(self.shape(), other.shape()) {
+            panic!("{}", e);
+        }
         let data = self
             .data
             .iter()
             .zip(other.data.iter())
-            .map(|(a, b)| a + b)
+            .map(|(&a, &b)| a + b)
             .collect();
         Self::new_from_vec(data, self.num_rows(), self.num_cols(), false)
     }
 
     fn matrix_subtraction(&self, other: &Self) -> Self {
+        if let Err(e) = check_same_shape(self.shape(), other.shape()) {
+            panic!("{}", e);
+        }
         let data = self
             .data
             .iter()
             .zip(other.data.iter())
-            .map(|(a, b)| a - b)
+            .map(|(&a, &b)| a - b)
             .collect();
         Self::new_from_vec(data, self.num_rows(), self.num_cols(), false)
     }
 
     fn matrix_multiplication(&self, other: &Self) -> Self {
-        if !other.transposed {
-            panic!("Other matrix must be transposed");
+        let mut res = Self::new_from_vec(
+            vec![T::zero(); self.num_rows() * other.num_cols()],
+            self.num_rows(),
+            other.num_cols(),
+            false,
+        );
+        Self::gemm(T::one(), self, other, T::zero(), &mut res);
+        res
+    }
+
+    fn scalar_multiplication(&self, scalar: T) -> Self {
+        let data = self.data.iter().map(|&a| a * scalar).collect();
+        Self::new_from_vec(data, self.num_rows(), self.num_cols(), false)
+    }
+
+    fn add_assign(&mut self, other: &Self) {
+        self.zip_in_place(other, |a, b| *a += b);
+    }
+
+    fn sub_assign(&mut self, other: &Self) {
+        self.zip_in_place(other, |a, b| *a -= b);
+    }
+
+    fn scale_in_place(&mut self, scalar: T) {
+        self.data.iter_mut().for_each(|a| *a *= scalar);
+        // Scaling commutes with transposing, so a synced copy stays synced.
+        if self.transposed {
+            self.transposed_data.iter_mut().for_each(|a| *a *= scalar);
         }
+    }
 
-        let self_rows = self.num_rows();
-        let self_cols = self.num_cols();
-        let other_cols = other.num_cols();
+    fn gemm(alpha: T, a: &Self, b: &Self, beta: T, c: &mut Self) {
+        if let Err(e) = check_gemm(a.shape(), b.shape(), c.shape()) {
+            panic!("{}", e);
+        }
+        let a_rows = a.num_rows();
+        let a_cols = a.num_cols();
+        let b_cols = b.num_cols();
 
-        let mut res = vec![0.0; self_rows * other_cols];
+        // Results of other operations are not eagerly transposed, so transpose them on demand.
+        let b_transposed;
+        let b_transposed_data = if b.transposed {
+            &b.transposed_data
+        } else {
+            b_transposed = transpose(&b.data, b.num_rows(), b_cols);
+            &b_transposed
+        };
 
-        for i in 0..self_rows {
-            for j in 0..other_cols {
-                for k in 0..self_cols {
-                    // Expects other to be transposed
-                    res[i * other_cols + j] += self.get(i, k) * other.get_transposed(j, k);
+        for i in 0..a_rows {
+            for j in 0..b_cols {
+                let mut sum = T::zero();
+                for k in 0..a_cols {
+                    sum += a.get(i, k) * b_transposed_data[j * a_cols + k];
                 }
+                let c_ij = &mut c.data[i * b_cols + j];
+                *c_ij = if beta == T::zero() {
+                    alpha * sum
+                } else {
+                    alpha * sum + beta * *c_ij
+                };
             }
         }
+        c.transposed = false;
+    }
+
+    fn transpose(&self) -> Self {
+        let (rows, cols) = self.shape;
+        // The two copies swap roles, so a synced matrix transposes without any work.
+        let data = if self.transposed {
+            self.transposed_data.clone()
+        } else {
+            transpose(&self.data, rows, cols)
+        };
 
-        Self::new_from_vec(res, self_rows, other_cols, false)
+        Self {
+            transposed_data: self.data.clone(),
+            data,
+            transposed: true,
+            shape: (cols, rows),
+        }
     }
+}
 
-    fn scalar_multiplication(&self, scalar: f64) -> Self {
-        let data = self.data.iter().map(|a| a * scalar).collect();
-        Self::new_from_vec(data, self.num_rows(), self.num_cols(), false)
+impl<T: FloatElement> Reductions<T> for TransposedViewMatrix<T> {}
+
+impl<T: Element> Index<(usize, usize)> for TransposedViewMatrix<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        assert_index((row, col), self.shape);
+        &self.data[row * self.num_cols() + col]
+    }
+}
+
+impl<T: Element> IndexMut<(usize, usize)> for TransposedViewMatrix<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        self.get_mut(row, col)
     }
 }
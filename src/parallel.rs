@@ -0,0 +1,188 @@
+//! The thread pools shared by `MultithreadMatrix`.
+//!
+//! A pool is built on first use for each thread count and reused by every operation with that
+//! count afterwards. The count comes from the matrix's [`crate::tuning::TuningConfig`] if it sets
+//! one, otherwise from the [`ParallelConfig`]: an explicit [`ParallelConfig::with_threads`],
+//! otherwise the `RMATRIX_NUM_THREADS` environment variable, otherwise one thread per available
+//! core. Work below the configured thresholds runs on the calling thread, where handing it to
+//! the pool would cost more than it saves.
+
+use rayon::prelude::*;
+use rayon::{ThreadPool, ThreadPoolBuilder};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread::available_parallelism;
+
+/// How `MultithreadMatrix` splits its work.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParallelConfig {
+    threads: usize,
+    min_elements: usize,
+    min_flops: usize,
+}
+
+impl ParallelConfig {
+    /// The default of [`ParallelConfig::min_elements`].
+    pub const DEFAULT_MIN_ELEMENTS: usize = 1 << 15;
+    /// The default of [`ParallelConfig::min_flops`], a 64 x 64 product.
+    pub const DEFAULT_MIN_FLOPS: usize = 1 << 18;
+
+    /// `RMATRIX_NUM_THREADS` threads if it is set to a positive integer, otherwise one per
+    /// available core, with the default thresholds.
+    pub fn from_env() -> Self {
+        let threads = std::env::var("RMATRIX_NUM_THREADS")
+            .ok()
+            .and_then(|threads| threads.trim().parse().ok())
+            .filter(|&threads| threads > 0)
+            .unwrap_or_else(|| available_parallelism().map_or(1, |threads| threads.get()));
+        Self {
+            threads,
+            min_elements: Self::DEFAULT_MIN_ELEMENTS,
+            min_flops: Self::DEFAULT_MIN_FLOPS,
+        }
+    }
+
+    /// Uses `threads` threads, at least one.
+    pub fn with_threads(self, threads: usize) -> Self {
+        Self {
+            threads: threads.max(1),
+            ..self
+        }
+    }
+
+    pub fn with_min_elements(self, min_elements: usize) -> Self {
+        Self {
+            min_elements,
+            ..self
+        }
+    }
+
+    pub fn with_min_flops(self, min_flops: usize) -> Self {
+        Self { min_flops, ..self }
+    }
+
+    /// The number of threads of the pool the work is split across.
+    pub fn threads(&self) -> usize {
+        self.threads
+    }
+
+    /// Element-wise operations, reductions and matrix-vector products on fewer elements run on
+    /// the calling thread.
+    pub fn min_elements(&self) -> usize {
+        self.min_elements
+    }
+
+    /// Products and factorizations with fewer multiply-adds run on the calling thread.
+    pub fn min_flops(&self) -> usize {
+        self.min_flops
+    }
+
+    /// Makes this the configuration of every later operation, except that matrices tuned with
+    /// their own thread count keep it. Operations already running finish with the old one.
+    pub fn apply(self) {
+        shared().as_mut().unwrap().config = self;
+    }
+
+    /// The chunk length splitting `len` elements evenly across the pool, or `len` itself (a
+    /// single chunk) if there are too few to split.
+    pub(crate) fn chunk_len(&self, len: usize) -> usize {
+        if len < self.min_elements {
+            len.max(1)
+        } else {
+            len.div_ceil(self.threads).max(1)
+        }
+    }
+
+    /// Like `chunk_len`, in whole rows of `cols` elements.
+    pub(crate) fn chunk_rows(&self, rows: usize, cols: usize) -> usize {
+        if rows * cols < self.min_elements {
+            rows.max(1)
+        } else {
+            rows.div_ceil(self.threads).max(1)
+        }
+    }
+
+    /// The number of threads to split `flops` multiply-adds across: 1 below the threshold.
+    pub(crate) fn threads_for_flops(&self, flops: usize) -> usize {
+        if flops < self.min_flops {
+            1
+        } else {
+            self.threads
+        }
+    }
+
+    /// Calls `f` on every item, on the pool with this many threads if there is more than one.
+    pub(crate) fn for_each<I: Send>(
+        &self,
+        items: impl IntoIterator<Item = I>,
+        f: impl Fn(I) + Sync,
+    ) {
+        let items = items.into_iter().collect::<Vec<_>>();
+        if items.len() <= 1 {
+            items.into_iter().for_each(f);
+        } else {
+            pool(self.threads).install(|| items.into_par_iter().for_each(&f));
+        }
+    }
+
+    /// Calls `f` on every item like `for_each`, returning the results in order.
+    pub(crate) fn map<I: Send, R: Send>(
+        &self,
+        items: impl IntoIterator<Item = I>,
+        f: impl Fn(I) -> R + Sync,
+    ) -> Vec<R> {
+        let items = items.into_iter().collect::<Vec<_>>();
+        if items.len() <= 1 {
+            items.into_iter().map(f).collect()
+        } else {
+            pool(self.threads).install(|| items.into_par_iter().map(&f).collect())
+        }
+    }
+}
+
+impl Default for ParallelConfig {
+    fn default() -> Self {
+        Self::from_env()
+    }
+}
+
+struct Shared {
+    config: ParallelConfig,
+    pools: HashMap<usize, Arc<ThreadPool>>,
+}
+
+static SHARED: Mutex<Option<Shared>> = Mutex::new(None);
+
+/// The shared state, with the configuration read from the environment on first use.
+fn shared() -> MutexGuard<'static, Option<Shared>> {
+    let mut shared = SHARED.lock().unwrap_or_else(|e| e.into_inner());
+    shared.get_or_insert_with(|| Shared {
+        config: ParallelConfig::from_env(),
+        pools: HashMap::new(),
+    });
+    shared
+}
+
+/// The current configuration.
+pub fn config() -> ParallelConfig {
+    shared().as_ref().unwrap().config
+}
+
+/// The pool of `threads` threads, built on first use.
+fn pool(threads: usize) -> Arc<ThreadPool> {
+    let mut shared = shared();
+    shared
+        .as_mut()
+        .unwrap()
+        .pools
+        .entry(threads)
+        .or_insert_with(|| {
+            let pool = ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .thread_name(|i| format!("rmatrix-{}", i))
+                .build()
+                .expect("failed to build the thread pool");
+            Arc::new(pool)
+        })
+        .clone()
+}
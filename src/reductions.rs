@@ -0,0 +1,152 @@
+//! Norms and statistics of the matrices with real floating-point elements.
+//!
+//! The kernels here work on a run of whole rows, so the defaults of [`Reductions`] can call them
+//! once per row while `MultithreadMatrix` calls them once per chunk of rows.
+
+use crate::level1;
+use crate::{Element, FloatElement, Matrix, Vector};
+
+/// Adds `f(j, a_ij)` to `sums[j]` for every entry of `rows`.
+pub(crate) fn add_columns<'a, T: Element>(
+    sums: &mut [T],
+    rows: impl Iterator<Item = &'a [T]>,
+    f: impl Fn(usize, T) -> T,
+) {
+    for row in rows {
+        for (j, (sum, &a_ij)) in sums.iter_mut().zip(row).enumerate() {
+            *sum += f(j, a_ij);
+        }
+    }
+}
+
+/// The sum of `x`.
+pub(crate) fn sum<T: Element>(x: &[T]) -> T {
+    x.iter().fold(T::zero(), |sum, &x| sum + x)
+}
+
+/// The population variance of `x` (dividing by its length), in two passes so that a large mean
+/// doesn't cancel out the deviations.
+pub(crate) fn variance<T: FloatElement>(x: &[T]) -> T {
+    let len = T::from(x.len()).unwrap();
+    let mean = sum(x) / len;
+    x.iter().fold(T::zero(), |sum, &x| sum + (x - mean).powi(2)) / len
+}
+
+/// The first of the indexed entries that no other entry is `better` than, skipping NaNs. `None` if
+/// every entry is NaN.
+pub(crate) fn first_extremum<T: FloatElement>(
+    entries: impl Iterator<Item = (usize, T)>,
+    better: impl Fn(T, T) -> bool,
+) -> Option<(usize, T)> {
+    entries
+        .filter(|(_, x)| !x.is_nan())
+        .fold(None, |best, (i, x)| match best {
+            Some((_, b)) if !better(x, b) => best,
+            _ => Some((i, x)),
+        })
+}
+
+/// The larger of `a` and `b`, or NaN if either is, so that the norms don't hide NaN entries.
+pub(crate) fn nan_max<T: FloatElement>(a: T, b: T) -> T {
+    if a.is_nan() || a > b { a } else { b }
+}
+
+/// The position of the entry at row-major index `extremum`, or of the first entry if there is none.
+pub(crate) fn position<T>(extremum: Option<(usize, T)>, cols: usize) -> (usize, usize) {
+    let i = extremum.map_or(0, |(i, _)| i);
+    (i / cols, i % cols)
+}
+
+/// Norms and statistics of the entries, their rows and their columns.
+///
+/// The norms are NaN if any entry is NaN. `min`, `max`, `argmin` and `argmax` skip NaN entries,
+/// and if every entry is NaN, they report the first one.
+pub trait Reductions<T: FloatElement = f64>: Matrix<T> {
+    /// The square root of the sum of the squared entries, without overflowing on large entries.
+    fn norm_frobenius(&self) -> T {
+        self.row_iter()
+            .map(level1::nrm2)
+            .fold(T::zero(), |norm, row| norm.hypot(row))
+    }
+
+    /// The largest absolute column sum, i.e. the operator norm induced by the vector 1-norm.
+    fn norm_1(&self) -> T {
+        let mut sums = vec![T::zero(); self.num_cols()];
+        add_columns(&mut sums, self.row_iter(), |_, a| a.abs());
+        sums.into_iter().fold(T::zero(), nan_max)
+    }
+
+    /// The largest absolute row sum, i.e. the operator norm induced by the vector infinity-norm.
+    fn norm_inf(&self) -> T {
+        self.row_iter().map(level1::asum).fold(T::zero(), nan_max)
+    }
+
+    /// The largest absolute value of an entry.
+    fn norm_max(&self) -> T {
+        self.row_iter()
+            .flatten()
+            .fold(T::zero(), |max, &a| nan_max(max, a.abs()))
+    }
+
+    fn mean(&self) -> T {
+        let (rows, cols) = self.shape();
+        self.sum() / T::from(rows * cols).unwrap()
+    }
+
+    fn min(&self) -> T {
+        let (i, j) = self.argmin();
+        self.get(i, j)
+    }
+
+    fn max(&self) -> T {
+        let (i, j) = self.argmax();
+        self.get(i, j)
+    }
+
+    /// The position of the first smallest entry.
+    fn argmin(&self) -> (usize, usize) {
+        let entries = self.row_iter().flatten().copied().enumerate();
+        position(first_extremum(entries, |a, b| a < b), self.num_cols())
+    }
+
+    /// The position of the first largest entry.
+    fn argmax(&self) -> (usize, usize) {
+        let entries = self.row_iter().flatten().copied().enumerate();
+        position(first_extremum(entries, |a, b| a > b), self.num_cols())
+    }
+
+    fn row_means(&self) -> Vector<T> {
+        let cols = T::from(self.num_cols()).unwrap();
+        self.row_sums()
+            .iter()
+            .map(|&sum| sum / cols)
+            .collect::<Vec<_>>()
+            .into()
+    }
+
+    fn col_means(&self) -> Vector<T> {
+        let rows = T::from(self.num_rows()).unwrap();
+        self.col_sums()
+            .iter()
+            .map(|&sum| sum / rows)
+            .collect::<Vec<_>>()
+            .into()
+    }
+
+    /// The population variance of each row, dividing by the number of columns.
+    fn row_variances(&self) -> Vector<T> {
+        self.row_iter().map(variance).collect::<Vec<_>>().into()
+    }
+
+    /// The population variance of each column, dividing by the number of rows.
+    fn col_variances(&self) -> Vector<T> {
+        let means = self.col_means();
+        let mut sums = vec![T::zero(); self.num_cols()];
+        add_columns(&mut sums, self.row_iter(), |j, a| (a - means[j]).powi(2));
+        let rows = T::from(self.num_rows()).unwrap();
+        sums.into_iter()
+            .map(|sum| sum / rows)
+            .collect::<Vec<_>>()
+            .into()
+    }
+}
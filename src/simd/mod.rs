@@ -0,0 +1,297 @@
+//! Explicit SIMD kernels for the element-wise arithmetic and the GEMM micro-kernel, chosen at
+//! runtime from the instruction sets the CPU supports.
+//!
+//! The first kernel call detects the best [`Isa`], unless the `RMATRIX_SIMD` environment variable
+//! names a supported one (`scalar`, `avx2`, `avx512` or `neon`), and [`set_isa`] switches it
+//! later. The kernels cover `f32` and `f64`; every other element type, and [`Isa::Scalar`], runs
+//! the portable loops.
+
+/// Defines a [`ZipKernel`] for the target feature `$feature`, applying `$vector_op` to `$lanes`
+/// elements at a time and `$op` to the tail.
+macro_rules! zip_kernel {
+    ($name:ident, $t:ty, $feature:literal, $lanes:literal, $load:ident, $store:ident, $vector_op:ident, $op:tt) => {
+        #[target_feature(enable = $feature)]
+        unsafe fn $name(a: *const $t, b: *const $t, out: *mut $t, len: usize) {
+            let mut i = 0;
+            while i + $lanes <= len {
+                unsafe { $store(out.add(i), $vector_op($load(a.add(i)), $load(b.add(i)))) };
+                i += $lanes;
+            }
+            while i < len {
+                unsafe { *out.add(i) = *a.add(i) $op *b.add(i) };
+                i += 1;
+            }
+        }
+    };
+}
+
+/// Defines a [`ScaleKernel`] multiplying `$lanes` elements at a time.
+macro_rules! scale_kernel {
+    ($name:ident, $t:ty, $feature:literal, $lanes:literal, $load:ident, $store:ident, $mul:ident, $splat:ident) => {
+        #[target_feature(enable = $feature)]
+        unsafe fn $name(a: *const $t, scalar: $t, out: *mut $t, len: usize) {
+            let scalar_v = $splat(scalar);
+            let mut i = 0;
+            while i + $lanes <= len {
+                unsafe { $store(out.add(i), $mul($load(a.add(i)), scalar_v)) };
+                i += $lanes;
+            }
+            while i < len {
+                unsafe { *out.add(i) = *a.add(i) * scalar };
+                i += 1;
+            }
+        }
+    };
+}
+
+/// Defines the run function of a [`MicroKernel`] for `$mr` rows and `$vectors` registers of
+/// `$lanes` columns, keeping the whole tile in `$mr * $vectors` accumulator registers.
+macro_rules! gemm_kernel {
+    ($name:ident, $t:ty, $feature:literal, $v:ty, $lanes:literal, $mr:literal, $vectors:literal, $zero:ident, $load:ident, $splat:ident, $fma:ident, $store:ident) => {
+        #[target_feature(enable = $feature)]
+        unsafe fn $name(depth: usize, a: *const $t, b: *const $t, ab: *mut $t) {
+            let mut acc: [[$v; $vectors]; $mr] = [[$zero(); $vectors]; $mr];
+            for p in 0..depth {
+                let (a, b) = unsafe { (a.add(p * $mr), b.add(p * $vectors * $lanes)) };
+                let mut b_p: [$v; $vectors] = [$zero(); $vectors];
+                for (v, b_v) in b_p.iter_mut().enumerate() {
+                    *b_v = unsafe { $load(b.add(v * $lanes)) };
+                }
+                for (i, acc_i) in acc.iter_mut().enumerate() {
+                    let a_i = $splat(unsafe { *a.add(i) });
+                    for (acc_iv, &b_v) in acc_i.iter_mut().zip(&b_p) {
+                        *acc_iv = $fma(a_i, b_v, *acc_iv);
+                    }
+                }
+            }
+            for (i, acc_i) in acc.iter().enumerate() {
+                for (v, &acc_iv) in acc_i.iter().enumerate() {
+                    unsafe { $store(ab.add((i * $vectors + v) * $lanes), acc_iv) };
+                }
+            }
+        }
+    };
+}
+
+#[cfg(target_arch = "aarch64")]
+mod neon;
+#[cfg(target_arch = "x86_64")]
+mod x86;
+
+#[cfg(target_arch = "aarch64")]
+use neon as arch;
+#[cfg(target_arch = "x86_64")]
+use x86 as arch;
+
+use crate::Element;
+use crate::gemm::MicroKernel;
+use std::any::TypeId;
+use std::mem::ManuallyDrop;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// An instruction set with SIMD kernels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum Isa {
+    /// The portable loops, left to the compiler's auto-vectorization.
+    Scalar,
+    /// AVX2 with FMA, on x86_64.
+    Avx2,
+    /// AVX-512F, on x86_64.
+    Avx512,
+    /// NEON, on aarch64.
+    Neon,
+}
+
+impl Isa {
+    const ALL: [Isa; 4] = [Isa::Scalar, Isa::Avx2, Isa::Avx512, Isa::Neon];
+
+    /// Whether the CPU running the program supports this instruction set.
+    pub fn is_supported(self) -> bool {
+        match self {
+            Isa::Scalar => true,
+            #[cfg(target_arch = "x86_64")]
+            Isa::Avx2 => is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma"),
+            #[cfg(target_arch = "x86_64")]
+            Isa::Avx512 => is_x86_feature_detected!("avx512f"),
+            #[cfg(target_arch = "aarch64")]
+            Isa::Neon => std::arch::is_aarch64_feature_detected!("neon"),
+            _ => false,
+        }
+    }
+
+    /// The widest supported instruction set.
+    pub fn detect() -> Self {
+        [Isa::Avx512, Isa::Avx2, Isa::Neon]
+            .into_iter()
+            .find(|isa| isa.is_supported())
+            .unwrap_or(Isa::Scalar)
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "scalar" => Some(Isa::Scalar),
+            "avx2" => Some(Isa::Avx2),
+            "avx512" => Some(Isa::Avx512),
+            "neon" => Some(Isa::Neon),
+            _ => None,
+        }
+    }
+}
+
+/// The active instruction set, or `UNSET` before the first kernel call.
+static ACTIVE: AtomicU8 = AtomicU8::new(UNSET);
+const UNSET: u8 = u8::MAX;
+
+/// The instruction set the kernels currently use.
+pub fn active_isa() -> Isa {
+    match ACTIVE.load(Ordering::Relaxed) {
+        UNSET => {
+            let isa = std::env::var("RMATRIX_SIMD")
+                .ok()
+                .and_then(|name| Isa::from_name(&name))
+                .filter(|isa| isa.is_supported())
+                .unwrap_or_else(Isa::detect);
+            ACTIVE.store(isa as u8, Ordering::Relaxed);
+            isa
+        }
+        active => Isa::ALL[active as usize],
+    }
+}
+
+/// Makes the kernels use `isa`, e.g. to compare the instruction sets in a benchmark. Panics if
+/// the CPU doesn't support it.
+pub fn set_isa(isa: Isa) {
+    assert!(isa.is_supported(), "{:?} is not supported on this CPU", isa);
+    ACTIVE.store(isa as u8, Ordering::Relaxed);
+}
+
+/// Reinterprets `value` as a `U`, if `T` and `U` are the same type. Lets the generic code hand
+/// out the kernels written for `f32` and `f64`.
+fn cast<T: 'static, U: 'static>(value: T) -> Option<U> {
+    if TypeId::of::<T>() != TypeId::of::<U>() {
+        return None;
+    }
+    let value = ManuallyDrop::new(value);
+    // SAFETY: `T` and `U` are the same type
+    Some(unsafe { std::mem::transmute_copy(&*value) })
+}
+
+/// An element-wise binary operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+/// Computes `out[i] = a[i] op b[i]` for `len` elements. `out` may be `a`.
+type ZipKernel<T> = unsafe fn(a: *const T, b: *const T, out: *mut T, len: usize);
+
+/// Computes `out[i] = a[i] * scalar` for `len` elements. `out` may be `a`.
+type ScaleKernel<T> = unsafe fn(a: *const T, scalar: T, out: *mut T, len: usize);
+
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+fn zip_kernel<T: Element>(op: BinaryOp) -> Option<ZipKernel<T>> {
+    arch::zip_kernel(active_isa(), op)
+}
+
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+fn zip_kernel<T: Element>(_: BinaryOp) -> Option<ZipKernel<T>> {
+    None
+}
+
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+fn scale_kernel<T: Element>() -> Option<ScaleKernel<T>> {
+    arch::scale_kernel(active_isa())
+}
+
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+fn scale_kernel<T: Element>() -> Option<ScaleKernel<T>> {
+    None
+}
+
+/// The GEMM micro-kernel of the active instruction set for `T`, if it has one.
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+pub(crate) fn gemm_kernel<T: Element>() -> Option<MicroKernel<T>> {
+    arch::gemm_kernel(active_isa())
+}
+
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+pub(crate) fn gemm_kernel<T: Element>() -> Option<MicroKernel<T>> {
+    None
+}
+
+fn zip_with<T: Element>(a: &[T], b: &[T], out: &mut [T], f: impl Fn(T, T) -> T) {
+    for (y, (&a, &b)) in out.iter_mut().zip(a.iter().zip(b)) {
+        *y = f(a, b);
+    }
+}
+
+fn zip_assign_with<T: Element>(a: &mut [T], b: &[T], f: impl Fn(&mut T, T)) {
+    for (a, &b) in a.iter_mut().zip(b) {
+        f(a, b);
+    }
+}
+
+/// Computes `out = a op b` element-wise, panicking if the lengths differ.
+pub(crate) fn zip_into<T: Element>(op: BinaryOp, a: &[T], b: &[T], out: &mut [T]) {
+    assert!(
+        a.len() == out.len() && b.len() == out.len(),
+        "slices must have the same length"
+    );
+    if let Some(kernel) = zip_kernel::<T>(op) {
+        // SAFETY: the three slices hold `out.len()` elements, and the kernel is only handed out
+        // for a supported instruction set
+        unsafe { kernel(a.as_ptr(), b.as_ptr(), out.as_mut_ptr(), out.len()) };
+        return;
+    }
+    match op {
+        BinaryOp::Add => zip_with(a, b, out, |a, b| a + b),
+        BinaryOp::Sub => zip_with(a, b, out, |a, b| a - b),
+        BinaryOp::Mul => zip_with(a, b, out, |a, b| a * b),
+        BinaryOp::Div => zip_with(a, b, out, |a, b| a / b),
+    }
+}
+
+/// Computes `a = a op b` element-wise, panicking if the lengths differ.
+pub(crate) fn zip_assign<T: Element>(op: BinaryOp, a: &mut [T], b: &[T]) {
+    assert_eq!(a.len(), b.len(), "slices must have the same length");
+    if let Some(kernel) = zip_kernel::<T>(op) {
+        let a = a.as_mut_ptr();
+        // SAFETY: as in `zip_into`, with `a` as both input and output
+        unsafe { kernel(a, b.as_ptr(), a, b.len()) };
+        return;
+    }
+    match op {
+        BinaryOp::Add => zip_assign_with(a, b, |a, b| *a += b),
+        BinaryOp::Sub => zip_assign_with(a, b, |a, b| *a -= b),
+        BinaryOp::Mul => zip_assign_with(a, b, |a, b| *a *= b),
+        BinaryOp::Div => zip_assign_with(a, b, |a, b| *a /= b),
+    }
+}
+
+/// Computes `out = a * scalar`, panicking if the lengths differ.
+pub(crate) fn scale_into<T: Element>(a: &[T], scalar: T, out: &mut [T]) {
+    assert_eq!(a.len(), out.len(), "slices must have the same length");
+    if let Some(kernel) = scale_kernel::<T>() {
+        // SAFETY: as in `zip_into`
+        unsafe { kernel(a.as_ptr(), scalar, out.as_mut_ptr(), out.len()) };
+        return;
+    }
+    for (y, &a) in out.iter_mut().zip(a) {
+        *y = a * scalar;
+    }
+}
+
+/// Computes `a *= scalar`.
+pub(crate) fn scale_assign<T: Element>(a: &mut [T], scalar: T) {
+    if let Some(kernel) = scale_kernel::<T>() {
+        let (len, a) = (a.len(), a.as_mut_ptr());
+        // SAFETY: as in `zip_assign`
+        unsafe { kernel(a, scalar, a, len) };
+        return;
+    }
+    a.iter_mut().for_each(|a| *a *= scalar);
+}
@@ -0,0 +1,120 @@
+//! NEON kernels.
+
+use super::{BinaryOp, Isa, ScaleKernel, ZipKernel, cast};
+use crate::gemm::MicroKernel;
+use std::arch::aarch64::*;
+
+#[inline]
+#[target_feature(enable = "neon")]
+fn zero_f64() -> float64x2_t {
+    vdupq_n_f64(0.0)
+}
+
+#[inline]
+#[target_feature(enable = "neon")]
+fn zero_f32() -> float32x4_t {
+    vdupq_n_f32(0.0)
+}
+
+/// `a * b + acc`, in the argument order of `_mm256_fmadd_pd`.
+#[inline]
+#[target_feature(enable = "neon")]
+fn fma_f64(a: float64x2_t, b: float64x2_t, acc: float64x2_t) -> float64x2_t {
+    vfmaq_f64(acc, a, b)
+}
+
+#[inline]
+#[target_feature(enable = "neon")]
+fn fma_f32(a: float32x4_t, b: float32x4_t, acc: float32x4_t) -> float32x4_t {
+    vfmaq_f32(acc, a, b)
+}
+
+zip_kernel!(add_f64_neon, f64, "neon", 2, vld1q_f64, vst1q_f64, vaddq_f64, +);
+zip_kernel!(sub_f64_neon, f64, "neon", 2, vld1q_f64, vst1q_f64, vsubq_f64, -);
+zip_kernel!(mul_f64_neon, f64, "neon", 2, vld1q_f64, vst1q_f64, vmulq_f64, *);
+zip_kernel!(div_f64_neon, f64, "neon", 2, vld1q_f64, vst1q_f64, vdivq_f64, /);
+zip_kernel!(add_f32_neon, f32, "neon", 4, vld1q_f32, vst1q_f32, vaddq_f32, +);
+zip_kernel!(sub_f32_neon, f32, "neon", 4, vld1q_f32, vst1q_f32, vsubq_f32, -);
+zip_kernel!(mul_f32_neon, f32, "neon", 4, vld1q_f32, vst1q_f32, vmulq_f32, *);
+zip_kernel!(div_f32_neon, f32, "neon", 4, vld1q_f32, vst1q_f32, vdivq_f32, /);
+
+scale_kernel!(
+    scale_f64_neon,
+    f64,
+    "neon",
+    2,
+    vld1q_f64,
+    vst1q_f64,
+    vmulq_f64,
+    vdupq_n_f64
+);
+scale_kernel!(
+    scale_f32_neon,
+    f32,
+    "neon",
+    4,
+    vld1q_f32,
+    vst1q_f32,
+    vmulq_f32,
+    vdupq_n_f32
+);
+
+gemm_kernel!(
+    gemm_f64_neon,
+    f64,
+    "neon",
+    float64x2_t,
+    2,
+    4,
+    4,
+    zero_f64,
+    vld1q_f64,
+    vdupq_n_f64,
+    fma_f64,
+    vst1q_f64
+);
+gemm_kernel!(
+    gemm_f32_neon,
+    f32,
+    "neon",
+    float32x4_t,
+    4,
+    8,
+    2,
+    zero_f32,
+    vld1q_f32,
+    vdupq_n_f32,
+    fma_f32,
+    vst1q_f32
+);
+
+pub(super) fn zip_kernel<T: 'static>(isa: Isa, op: BinaryOp) -> Option<ZipKernel<T>> {
+    use BinaryOp::*;
+    let (f64_kernel, f32_kernel): (ZipKernel<f64>, ZipKernel<f32>) = match (isa, op) {
+        (Isa::Neon, Add) => (add_f64_neon, add_f32_neon),
+        (Isa::Neon, Sub) => (sub_f64_neon, sub_f32_neon),
+        (Isa::Neon, Mul) => (mul_f64_neon, mul_f32_neon),
+        (Isa::Neon, Div) => (div_f64_neon, div_f32_neon),
+        _ => return None,
+    };
+    cast(f64_kernel).or_else(|| cast(f32_kernel))
+}
+
+pub(super) fn scale_kernel<T: 'static>(isa: Isa) -> Option<ScaleKernel<T>> {
+    let (f64_kernel, f32_kernel): (ScaleKernel<f64>, ScaleKernel<f32>) = match isa {
+        Isa::Neon => (scale_f64_neon, scale_f32_neon),
+        _ => return None,
+    };
+    cast(f64_kernel).or_else(|| cast(f32_kernel))
+}
+
+pub(super) fn gemm_kernel<T: 'static>(isa: Isa) -> Option<MicroKernel<T>> {
+    let (f64_kernel, f32_kernel) = match isa {
+        Isa::Neon => (
+            MicroKernel::<f64>::new(4, 8, gemm_f64_neon),
+            MicroKernel::<f32>::new(8, 8, gemm_f32_neon),
+        ),
+        _ => return None,
+    };
+    cast(f64_kernel).or_else(|| cast(f32_kernel))
+}
@@ -0,0 +1,160 @@
+//! AVX2 / FMA and AVX-512F kernels.
+
+use super::{BinaryOp, Isa, ScaleKernel, ZipKernel, cast};
+use crate::gemm::MicroKernel;
+use std::arch::x86_64::*;
+
+zip_kernel!(add_f64_avx2, f64, "avx2", 4, _mm256_loadu_pd, _mm256_storeu_pd, _mm256_add_pd, +);
+zip_kernel!(sub_f64_avx2, f64, "avx2", 4, _mm256_loadu_pd, _mm256_storeu_pd, _mm256_sub_pd, -);
+zip_kernel!(mul_f64_avx2, f64, "avx2", 4, _mm256_loadu_pd, _mm256_storeu_pd, _mm256_mul_pd, *);
+zip_kernel!(div_f64_avx2, f64, "avx2", 4, _mm256_loadu_pd, _mm256_storeu_pd, _mm256_div_pd, /);
+zip_kernel!(add_f32_avx2, f32, "avx2", 8, _mm256_loadu_ps, _mm256_storeu_ps, _mm256_add_ps, +);
+zip_kernel!(sub_f32_avx2, f32, "avx2", 8, _mm256_loadu_ps, _mm256_storeu_ps, _mm256_sub_ps, -);
+zip_kernel!(mul_f32_avx2, f32, "avx2", 8, _mm256_loadu_ps, _mm256_storeu_ps, _mm256_mul_ps, *);
+zip_kernel!(div_f32_avx2, f32, "avx2", 8, _mm256_loadu_ps, _mm256_storeu_ps, _mm256_div_ps, /);
+zip_kernel!(add_f64_avx512, f64, "avx512f", 8, _mm512_loadu_pd, _mm512_storeu_pd, _mm512_add_pd, +);
+zip_kernel!(sub_f64_avx512, f64, "avx512f", 8, _mm512_loadu_pd, _mm512_storeu_pd, _mm512_sub_pd, -);
+zip_kernel!(mul_f64_avx512, f64, "avx512f", 8, _mm512_loadu_pd, _mm512_storeu_pd, _mm512_mul_pd, *);
+zip_kernel!(div_f64_avx512, f64, "avx512f", 8, _mm512_loadu_pd, _mm512_storeu_pd, _mm512_div_pd, /);
+zip_kernel!(add_f32_avx512, f32, "avx512f", 16, _mm512_loadu_ps, _mm512_storeu_ps, _mm512_add_ps, +);
+zip_kernel!(sub_f32_avx512, f32, "avx512f", 16, _mm512_loadu_ps, _mm512_storeu_ps, _mm512_sub_ps, -);
+zip_kernel!(mul_f32_avx512, f32, "avx512f", 16, _mm512_loadu_ps, _mm512_storeu_ps, _mm512_mul_ps, *);
+zip_kernel!(div_f32_avx512, f32, "avx512f", 16, _mm512_loadu_ps, _mm512_storeu_ps, _mm512_div_ps, /);
+
+scale_kernel!(
+    scale_f64_avx2,
+    f64,
+    "avx2",
+    4,
+    _mm256_loadu_pd,
+    _mm256_storeu_pd,
+    _mm256_mul_pd,
+    _mm256_set1_pd
+);
+scale_kernel!(
+    scale_f32_avx2,
+    f32,
+    "avx2",
+    8,
+    _mm256_loadu_ps,
+    _mm256_storeu_ps,
+    _mm256_mul_ps,
+    _mm256_set1_ps
+);
+scale_kernel!(
+    scale_f64_avx512,
+    f64,
+    "avx512f",
+    8,
+    _mm512_loadu_pd,
+    _mm512_storeu_pd,
+    _mm512_mul_pd,
+    _mm512_set1_pd
+);
+scale_kernel!(
+    scale_f32_avx512,
+    f32,
+    "avx512f",
+    16,
+    _mm512_loadu_ps,
+    _mm512_storeu_ps,
+    _mm512_mul_ps,
+    _mm512_set1_ps
+);
+
+gemm_kernel!(
+    gemm_f64_avx2,
+    f64,
+    "avx2,fma",
+    __m256d,
+    4,
+    6,
+    2,
+    _mm256_setzero_pd,
+    _mm256_loadu_pd,
+    _mm256_set1_pd,
+    _mm256_fmadd_pd,
+    _mm256_storeu_pd
+);
+gemm_kernel!(
+    gemm_f32_avx2,
+    f32,
+    "avx2,fma",
+    __m256,
+    8,
+    6,
+    2,
+    _mm256_setzero_ps,
+    _mm256_loadu_ps,
+    _mm256_set1_ps,
+    _mm256_fmadd_ps,
+    _mm256_storeu_ps
+);
+gemm_kernel!(
+    gemm_f64_avx512,
+    f64,
+    "avx512f",
+    __m512d,
+    8,
+    8,
+    2,
+    _mm512_setzero_pd,
+    _mm512_loadu_pd,
+    _mm512_set1_pd,
+    _mm512_fmadd_pd,
+    _mm512_storeu_pd
+);
+gemm_kernel!(
+    gemm_f32_avx512,
+    f32,
+    "avx512f",
+    __m512,
+    16,
+    8,
+    2,
+    _mm512_setzero_ps,
+    _mm512_loadu_ps,
+    _mm512_set1_ps,
+    _mm512_fmadd_ps,
+    _mm512_storeu_ps
+);
+
+pub(super) fn zip_kernel<T: 'static>(isa: Isa, op: BinaryOp) -> Option<ZipKernel<T>> {
+    use BinaryOp::*;
+    let (f64_kernel, f32_kernel): (ZipKernel<f64>, ZipKernel<f32>) = match (isa, op) {
+        (Isa::Avx2, Add) => (add_f64_avx2, add_f32_avx2),
+        (Isa::Avx2, Sub) => (sub_f64_avx2, sub_f32_avx2),
+        (Isa::Avx2, Mul) => (mul_f64_avx2, mul_f32_avx2),
+        (Isa::Avx2, Div) => (div_f64_avx2, div_f32_avx2),
+        (Isa::Avx512, Add) => (add_f64_avx512, add_f32_avx512),
+        (Isa::Avx512, Sub) => (sub_f64_avx512, sub_f32_avx512),
+        (Isa::Avx512, Mul) => (mul_f64_avx512, mul_f32_avx512),
+        (Isa::Avx512, Div) => (div_f64_avx512, div_f32_avx512),
+        _ => return None,
+    };
+    cast(f64_kernel).or_else(|| cast(f32_kernel))
+}
+
+pub(super) fn scale_kernel<T: 'static>(isa: Isa) -> Option<ScaleKernel<T>> {
+    let (f64_kernel, f32_kernel): (ScaleKernel<f64>, ScaleKernel<f32>) = match isa {
+        Isa::Avx2 => (scale_f64_avx2, scale_f32_avx2),
+        Isa::Avx512 => (scale_f64_avx512, scale_f32_avx512),
+        _ => return None,
+    };
+    cast(f64_kernel).or_else(|| cast(f32_kernel))
+}
+
+pub(super) fn gemm_kernel<T: 'static>(isa: Isa) -> Option<MicroKernel<T>> {
+    let (f64_kernel, f32_kernel) = match isa {
+        Isa::Avx2 => (
+            MicroKernel::<f64>::new(6, 8, gemm_f64_avx2),
+            MicroKernel::<f32>::new(6, 16, gemm_f32_avx2),
+        ),
+        Isa::Avx512 => (
+            MicroKernel::<f64>::new(8, 16, gemm_f64_avx512),
+            MicroKernel::<f32>::new(8, 32, gemm_f32_avx512),
+        ),
+        _ => return None,
+    };
+    cast(f64_kernel).or_else(|| cast(f32_kernel))
+}
@@ -0,0 +1,174 @@
+//! Strassen's matrix multiplication, in Winograd's variant: seven half-size products and fifteen
+//! additions per level instead of eight products.
+//!
+//! Each level multiplies the even-sized core of the operands quadrant by quadrant, running the
+//! seven products on the shared pool when there are enough multiply-adds to split. The last row,
+//! column or step along the shared dimension of an odd size is peeled off and multiplied
+//! directly, and products whose smallest dimension is at most the cutoff use the blocked kernel.
+
+use crate::parallel::ParallelConfig;
+use crate::tuning::TuningConfig;
+use crate::view::zip_map_views;
+use crate::{Element, MatrixView, MatrixViewMut};
+use std::cmp::min;
+
+/// Product of two views as a row-major `Vec`, recursing while every dimension is above the
+/// cutoff of `tuning` and running on its threads.
+pub(crate) fn strassen_multiply<T: Element>(
+    a: &MatrixView<T>,
+    b: &MatrixView<T>,
+    tuning: &TuningConfig,
+) -> Vec<T> {
+    product(
+        a,
+        b,
+        tuning.strassen_cutoff().max(1),
+        tuning.block_size().max(1),
+        &tuning.parallel(),
+    )
+}
+
+fn product<T: Element>(
+    a: &MatrixView<T>,
+    b: &MatrixView<T>,
+    cutoff: usize,
+    block_size: usize,
+    parallel: &ParallelConfig,
+) -> Vec<T> {
+    let (rows, cols) = (a.num_rows(), b.num_cols());
+    let mut res = vec![T::zero(); rows * cols];
+    let mut res_view = MatrixViewMut::from_row_major(&mut res, rows, cols);
+    multiply_add(a, b, &mut res_view, cutoff, block_size, parallel);
+    res
+}
+
+/// Computes `c += a * b`.
+fn multiply_add<T: Element>(
+    a: &MatrixView<T>,
+    b: &MatrixView<T>,
+    c: &mut MatrixViewMut<T>,
+    cutoff: usize,
+    block_size: usize,
+    parallel: &ParallelConfig,
+) {
+    let (m, k) = a.shape();
+    let n = b.num_cols();
+    if min(m, min(k, n)) <= cutoff {
+        blocked_multiply_add(a, b, c, block_size);
+        return;
+    }
+
+    let (hm, hk, hn) = (m / 2, k / 2, n / 2);
+    let (a11, a12) = (a.submatrix(0..hm, 0..hk), a.submatrix(0..hm, hk..2 * hk));
+    let (a21, a22) = (
+        a.submatrix(hm..2 * hm, 0..hk),
+        a.submatrix(hm..2 * hm, hk..2 * hk),
+    );
+    let (b11, b12) = (b.submatrix(0..hk, 0..hn), b.submatrix(0..hk, hn..2 * hn));
+    let (b21, b22) = (
+        b.submatrix(hk..2 * hk, 0..hn),
+        b.submatrix(hk..2 * hk, hn..2 * hn),
+    );
+
+    let add = |x: T, y: T| x + y;
+    let sub = |x: T, y: T| x - y;
+    let s1 = zip_map_views(&a21, &a22, add);
+    let s1 = MatrixView::from_row_major(&s1, hm, hk);
+    let s2 = zip_map_views(&s1, &a11, sub);
+    let s2 = MatrixView::from_row_major(&s2, hm, hk);
+    let s3 = zip_map_views(&a11, &a21, sub);
+    let s4 = zip_map_views(&a12, &s2, sub);
+    let t1 = zip_map_views(&b12, &b11, sub);
+    let t1 = MatrixView::from_row_major(&t1, hk, hn);
+    let t2 = zip_map_views(&b22, &t1, sub);
+    let t2 = MatrixView::from_row_major(&t2, hk, hn);
+    let t3 = zip_map_views(&b22, &b12, sub);
+    let t4 = zip_map_views(&t2, &b21, sub);
+
+    let factors = [
+        (a11, b11),
+        (a12, b21),
+        (MatrixView::from_row_major(&s4, hm, hk), b22),
+        (a22, MatrixView::from_row_major(&t4, hk, hn)),
+        (s1, t1),
+        (s2, t2),
+        (
+            MatrixView::from_row_major(&s3, hm, hk),
+            MatrixView::from_row_major(&t3, hk, hn),
+        ),
+    ];
+    let multiply =
+        |(x, y): (MatrixView<T>, MatrixView<T>)| product(&x, &y, cutoff, block_size, parallel);
+    let products = if parallel.threads_for_flops(7 * hm * hk * hn) > 1 {
+        parallel.map(factors, multiply)
+    } else {
+        factors.into_iter().map(multiply).collect()
+    };
+    let [p1, p2, p3, p4, p5, p6, p7] = &products[..] else {
+        unreachable!("seven factors give seven products");
+    };
+
+    for i in 0..hm {
+        for j in 0..hn {
+            let ij = i * hn + j;
+            let u2 = p1[ij] + p6[ij];
+            let u3 = u2 + p7[ij];
+            *c.at_mut(i, j) += p1[ij] + p2[ij];
+            *c.at_mut(i, j + hn) += u2 + p5[ij] + p3[ij];
+            *c.at_mut(i + hm, j) += u3 - p4[ij];
+            *c.at_mut(i + hm, j + hn) += u3 + p5[ij];
+        }
+    }
+
+    // Peel off what the even-sized core leaves out
+    if k % 2 == 1 {
+        blocked_multiply_add(
+            &a.submatrix(0..2 * hm, k - 1..k),
+            &b.submatrix(k - 1..k, 0..2 * hn),
+            &mut c.submatrix_mut(0..2 * hm, 0..2 * hn),
+            block_size,
+        );
+    }
+    if n % 2 == 1 {
+        blocked_multiply_add(
+            a,
+            &b.submatrix(0..k, n - 1..n),
+            &mut c.submatrix_mut(0..m, n - 1..n),
+            block_size,
+        );
+    }
+    if m % 2 == 1 {
+        blocked_multiply_add(
+            &a.submatrix(m - 1..m, 0..k),
+            &b.submatrix(0..k, 0..2 * hn),
+            &mut c.submatrix_mut(m - 1..m, 0..2 * hn),
+            block_size,
+        );
+    }
+}
+
+/// Computes `c += a * b` in `block_size` tiles, accumulating rows of `b` like `gemm_views`.
+fn blocked_multiply_add<T: Element>(
+    a: &MatrixView<T>,
+    b: &MatrixView<T>,
+    c: &mut MatrixViewMut<T>,
+    block_size: usize,
+) {
+    let (m, k) = a.shape();
+    let n = b.num_cols();
+
+    for bi in (0..m).step_by(block_size) {
+        for bk in (0..k).step_by(block_size) {
+            for bj in (0..n).step_by(block_size) {
+                for i in bi..min(bi + block_size, m) {
+                    for p in bk..min(bk + block_size, k) {
+                        let a_ip = a.at(i, p);
+                        for j in bj..min(bj + block_size, n) {
+                            *c.at_mut(i, j) += a_ip * b.at(p, j);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
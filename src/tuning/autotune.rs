@@ -0,0 +1,243 @@
+use super::{BlockSizes, TuningConfig, config, default_path};
+use crate::linalg::LinearAlgebra;
+use crate::matrices::blocked_matrix::BlockedMatrix;
+use crate::matrices::multithread_matrix::MultithreadMatrix;
+use crate::matrices::packed_matrix::PackedMatrix;
+use crate::{FlatMatrix, Matrix};
+use std::io;
+use std::thread::available_parallelism;
+use std::time::{Duration, Instant};
+
+/// Times candidate block sizes and thread counts on the current machine.
+///
+/// Each setting is swept in turn, keeping the best of the earlier ones: the thread count on the
+/// multithread matrix's product, the tile size on the blocked and multithread products, the
+/// Cholesky panel width on the multithread factorization, the packed block sizes on the packed
+/// product and the cutoff on Strassen's product, all of `size x size` `f64` matrices. Each
+/// candidate is given to the matrices it is timed on, so the sweep changes no shared settings.
+#[derive(Debug, Clone)]
+pub struct Autotuner {
+    size: usize,
+    repeats: usize,
+    thread_counts: Vec<usize>,
+    block_sizes: Vec<usize>,
+    cholesky_block_sizes: Vec<usize>,
+    packed_block_sizes: Vec<BlockSizes>,
+    strassen_cutoffs: Vec<usize>,
+}
+
+impl Autotuner {
+    /// Sweeps 256 x 256 matrices, keeping the fastest of 3 runs of each candidate.
+    pub fn new() -> Self {
+        let cores = available_parallelism().map_or(1, |cores| cores.get());
+        let mut thread_counts = (0..)
+            .map(|i| 1 << i)
+            .take_while(|&threads| threads < cores)
+            .collect::<Vec<_>>();
+        thread_counts.push(cores);
+
+        let packed_block_sizes = [48, 96, 192]
+            .into_iter()
+            .flat_map(|mc| {
+                [128, 256, 512].map(|kc| BlockSizes {
+                    mc,
+                    kc,
+                    nc: BlockSizes::default().nc,
+                })
+            })
+            .collect();
+
+        Self {
+            size: 256,
+            repeats: 3,
+            thread_counts,
+            block_sizes: vec![4, 8, 16, 32, 64],
+            cholesky_block_sizes: vec![16, 32, 64, 128],
+            packed_block_sizes,
+            strassen_cutoffs: vec![32, 64, 128, 256],
+        }
+    }
+
+    pub fn with_size(self, size: usize) -> Self {
+        Self {
+            size: size.max(1),
+            ..self
+        }
+    }
+
+    pub fn with_repeats(self, repeats: usize) -> Self {
+        Self {
+            repeats: repeats.max(1),
+            ..self
+        }
+    }
+
+    pub fn with_thread_counts(self, thread_counts: Vec<usize>) -> Self {
+        Self {
+            thread_counts,
+            ..self
+        }
+    }
+
+    pub fn with_block_sizes(self, block_sizes: Vec<usize>) -> Self {
+        Self {
+            block_sizes,
+            ..self
+        }
+    }
+
+    pub fn with_cholesky_block_sizes(self, cholesky_block_sizes: Vec<usize>) -> Self {
+        Self {
+            cholesky_block_sizes,
+            ..self
+        }
+    }
+
+    pub fn with_packed_block_sizes(self, packed_block_sizes: Vec<BlockSizes>) -> Self {
+        Self {
+            packed_block_sizes,
+            ..self
+        }
+    }
+
+    pub fn with_strassen_cutoffs(self, strassen_cutoffs: Vec<usize>) -> Self {
+        Self {
+            strassen_cutoffs,
+            ..self
+        }
+    }
+
+    /// Returns the fastest configuration, starting from [`config`] and keeping its value for any
+    /// setting without candidates.
+    pub fn run(&self) -> TuningConfig {
+        let n = self.size;
+        let a = entries(n, 0);
+        let b = entries(n, 1);
+        let spd = (0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|j| {
+                        1.0 / (1.0 + i.abs_diff(j) as f64) + if i == j { n as f64 } else { 0.0 }
+                    })
+                    .collect()
+            })
+            .collect::<Vec<Vec<f64>>>();
+
+        let blocked =
+            |data: &Vec<Vec<f64>>, config| BlockedMatrix::new(data.clone()).with_tuning(config);
+        let multithread =
+            |data: &Vec<Vec<f64>>, config| MultithreadMatrix::new(data.clone()).with_tuning(config);
+        let packed =
+            |data: &Vec<Vec<f64>>, config| PackedMatrix::new(data.clone()).with_tuning(config);
+
+        let multithread_product = |config| {
+            let (a, b) = (multithread(&a, config), multithread(&b, config));
+            self.time(|| {
+                a.matrix_multiplication(&b);
+            })
+        };
+        let tiled_products = |config| {
+            let (blocked_a, blocked_b) = (blocked(&a, config), blocked(&b, config));
+            let (multithread_a, multithread_b) = (multithread(&a, config), multithread(&b, config));
+            self.time(|| {
+                blocked_a.matrix_multiplication(&blocked_b);
+                multithread_a.matrix_multiplication(&multithread_b);
+            })
+        };
+        let cholesky = |config| {
+            let spd = multithread(&spd, config);
+            self.time(|| {
+                spd.cholesky().unwrap();
+            })
+        };
+        let packed_product = |config| {
+            let (a, b) = (packed(&a, config), packed(&b, config));
+            self.time(|| {
+                a.matrix_multiplication(&b);
+            })
+        };
+        let strassen_product = |config| {
+            let (a, b) = (packed(&a, config), packed(&b, config));
+            self.time(|| {
+                a.strassen_multiplication(&b);
+            })
+        };
+
+        let mut best = config();
+        best = Self::sweep(best, &self.thread_counts, multithread_product, |c, &t| {
+            c.with_threads(t)
+        });
+        best = Self::sweep(best, &self.block_sizes, tiled_products, |c, &size| {
+            c.with_block_size(size)
+        });
+        best = Self::sweep(best, &self.cholesky_block_sizes, cholesky, |c, &size| {
+            c.with_cholesky_block_size(size)
+        });
+        best = Self::sweep(
+            best,
+            &self.packed_block_sizes,
+            packed_product,
+            |c, &sizes| c.with_packed_block_sizes(sizes),
+        );
+        Self::sweep(
+            best,
+            &self.strassen_cutoffs,
+            strassen_product,
+            |c, &cutoff| c.with_strassen_cutoff(cutoff),
+        )
+    }
+
+    /// The candidate `set(config, candidate)` that `time` reports fastest, or `config` if there
+    /// are no candidates.
+    fn sweep<C>(
+        config: TuningConfig,
+        candidates: &[C],
+        time: impl Fn(TuningConfig) -> Duration,
+        set: impl Fn(TuningConfig, &C) -> TuningConfig,
+    ) -> TuningConfig {
+        candidates
+            .iter()
+            .map(|candidate| {
+                let candidate = set(config, candidate);
+                (time(candidate), candidate)
+            })
+            .min_by_key(|&(time, _)| time)
+            .map_or(config, |(_, candidate)| candidate)
+    }
+
+    /// The fastest of `repeats` runs of `workload`.
+    fn time(&self, workload: impl Fn()) -> Duration {
+        (0..self.repeats)
+            .map(|_| {
+                let start = Instant::now();
+                workload();
+                start.elapsed()
+            })
+            .min()
+            .unwrap()
+    }
+}
+
+impl Default for Autotuner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Runs the default [`Autotuner`] and saves the result to [`default_path`], where
+/// [`super::init`] finds it on later runs.
+pub fn autotune() -> io::Result<TuningConfig> {
+    let best = Autotuner::new().run();
+    best.save(default_path())?;
+    Ok(best)
+}
+
+fn entries(n: usize, seed: usize) -> Vec<Vec<f64>> {
+    (0..n)
+        .map(|i| {
+            (0..n)
+                .map(|j| ((i * n + j) * 7 + seed) as f64 % 23.0 / 4.0 - 2.0)
+                .collect()
+        })
+        .collect()
+}
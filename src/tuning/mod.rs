@@ -0,0 +1,206 @@
+//! Block sizes and thread count of the blocked, multithread and packed matrices, and the cutoff
+//! of Strassen's multiplication.
+//!
+//! Each of those matrices holds a [`TuningConfig`], given with its `with_tuning` or taken from
+//! [`config`] when it is built. That is the defaults unless [`init`] loaded a tuning file first.
+//! The [`Autotuner`] times candidates on the current machine and [`autotune`] writes the best
+//! ones to [`default_path`], so later runs can load them.
+
+mod autotune;
+
+pub use autotune::{Autotuner, autotune};
+
+use crate::parallel::{self, ParallelConfig};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// The cache blocking of the packed matrix's GEMM, in elements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockSizes {
+    /// Rows of `a` packed at once. Rounded up to a multiple of the micro-kernel's rows.
+    pub mc: usize,
+    /// Depth of the packed blocks along the shared dimension.
+    pub kc: usize,
+    /// Columns of `b` packed at once. Rounded up to a multiple of the micro-kernel's columns.
+    pub nc: usize,
+}
+
+impl Default for BlockSizes {
+    /// Sizes for `f64` on a core with 32 KiB of L1 and 256 KiB or more of L2: a `kc x 8`
+    /// micro-panel of `b` takes 16 KiB and an `mc x kc` block of `a` takes 192 KiB.
+    fn default() -> Self {
+        Self {
+            mc: 96,
+            kc: 256,
+            nc: 4096,
+        }
+    }
+}
+
+/// Block sizes at each level and the thread count used by the matrices.
+///
+/// Missing fields of a tuning file take their defaults, and zero sizes are raised to one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TuningConfig {
+    block_size: usize,
+    cholesky_block_size: usize,
+    packed: BlockSizes,
+    strassen_cutoff: usize,
+    threads: Option<usize>,
+}
+
+impl TuningConfig {
+    /// Reads a configuration saved by [`TuningConfig::save`].
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = BufReader::new(File::open(path)?);
+        let config: Self = serde_json::from_reader(file)?;
+        let sanitized = Self::default()
+            .with_block_size(config.block_size)
+            .with_cholesky_block_size(config.cholesky_block_size)
+            .with_packed_block_sizes(config.packed)
+            .with_strassen_cutoff(config.strassen_cutoff);
+        Ok(match config.threads {
+            Some(threads) => sanitized.with_threads(threads),
+            None => sanitized,
+        })
+    }
+
+    /// Writes the configuration to `path` as JSON.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let file = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(file, self)?;
+        Ok(())
+    }
+
+    /// Uses `block_size x block_size` tiles, at least 1.
+    pub fn with_block_size(self, block_size: usize) -> Self {
+        Self {
+            block_size: block_size.max(1),
+            ..self
+        }
+    }
+
+    /// Uses `cholesky_block_size`-wide panels, at least 1.
+    pub fn with_cholesky_block_size(self, cholesky_block_size: usize) -> Self {
+        Self {
+            cholesky_block_size: cholesky_block_size.max(1),
+            ..self
+        }
+    }
+
+    /// Uses `packed` for the packed GEMM, each size at least 1.
+    pub fn with_packed_block_sizes(self, packed: BlockSizes) -> Self {
+        Self {
+            packed: BlockSizes {
+                mc: packed.mc.max(1),
+                kc: packed.kc.max(1),
+                nc: packed.nc.max(1),
+            },
+            ..self
+        }
+    }
+
+    /// Stops Strassen's recursion at products with a dimension of at most `strassen_cutoff`, at
+    /// least 1.
+    pub fn with_strassen_cutoff(self, strassen_cutoff: usize) -> Self {
+        Self {
+            strassen_cutoff: strassen_cutoff.max(1),
+            ..self
+        }
+    }
+
+    /// Runs the multithread matrix's operations and Strassen's products on `threads` threads,
+    /// at least one.
+    pub fn with_threads(self, threads: usize) -> Self {
+        Self {
+            threads: Some(threads.max(1)),
+            ..self
+        }
+    }
+
+    /// The tile of the blocked and multithread matrices' products and element-wise loops, and
+    /// the panel width of the blocked matrix's factorizations.
+    pub fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    /// The panel width of the multithread matrix's Cholesky factorization. Wider than
+    /// `block_size` so each round of tasks in the trailing update has enough work to pay for
+    /// handing them to the pool.
+    pub fn cholesky_block_size(&self) -> usize {
+        self.cholesky_block_size
+    }
+
+    /// The cache blocking of the packed matrix's GEMM.
+    pub fn packed_block_sizes(&self) -> BlockSizes {
+        self.packed
+    }
+
+    /// The largest dimension at which [`crate::FlatMatrix::strassen_multiplication`] stops
+    /// recursing and multiplies with the blocked kernel.
+    pub fn strassen_cutoff(&self) -> usize {
+        self.strassen_cutoff
+    }
+
+    /// The number of threads, if this configuration sets one. Otherwise the
+    /// [`ParallelConfig`] picks it.
+    pub fn threads(&self) -> Option<usize> {
+        self.threads
+    }
+
+    /// The current [`ParallelConfig`] with this configuration's thread count.
+    pub(crate) fn parallel(&self) -> ParallelConfig {
+        let config = parallel::config();
+        match self.threads {
+            Some(threads) => config.with_threads(threads),
+            None => config,
+        }
+    }
+}
+
+impl Default for TuningConfig {
+    fn default() -> Self {
+        Self {
+            block_size: 8,
+            cholesky_block_size: 64,
+            packed: BlockSizes::default(),
+            strassen_cutoff: 128,
+            threads: None,
+        }
+    }
+}
+
+static DEFAULT: OnceLock<TuningConfig> = OnceLock::new();
+
+/// The tuning file written by [`autotune`]: `RMATRIX_TUNING_FILE` if it is set, otherwise
+/// `rmatrix-tuning.json` in the working directory.
+pub fn default_path() -> PathBuf {
+    std::env::var_os("RMATRIX_TUNING_FILE")
+        .map_or_else(|| PathBuf::from("rmatrix-tuning.json"), PathBuf::from)
+}
+
+/// Loads the tuning file at `path` and makes it the [`config`] of every matrix built afterwards.
+///
+/// Call it once at startup, before building any matrix: the configuration is fixed on first
+/// use, and later calls fail with [`io::ErrorKind::AlreadyExists`]. A missing or malformed file
+/// fails without fixing anything.
+pub fn init(path: impl AsRef<Path>) -> io::Result<TuningConfig> {
+    let config = TuningConfig::load(path)?;
+    DEFAULT.set(config).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            "the tuning configuration is already in use",
+        )
+    })?;
+    Ok(config)
+}
+
+/// The configuration of matrices built without `with_tuning`: the one loaded by [`init`], or
+/// the defaults.
+pub fn config() -> TuningConfig {
+    *DEFAULT.get_or_init(TuningConfig::default)
+}
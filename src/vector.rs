@@ -0,0 +1,116 @@
+use crate::error::check_same_shape;
+use crate::level1::{self, Givens};
+use crate::{Element, FloatElement};
+use std::ops::{Index, IndexMut};
+
+/// A dense vector, for matrix-vector products without faking it as an `n x 1` matrix.
+///
+/// Whether it acts as a column or a row depends on the product: `matvec` treats it as a column
+/// and `vecmat` as a row.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Vector<T = f64> {
+    data: Vec<T>,
+}
+
+impl<T: Element> Vector<T> {
+    pub fn new(data: Vec<T>) -> Self {
+        Self { data }
+    }
+
+    pub fn zeroes(len: usize) -> Self {
+        Self::new(vec![T::zero(); len])
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.data
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.data.iter()
+    }
+}
+
+/// BLAS level-1 operations, using the kernels of [`crate::level1`].
+impl<T: FloatElement> Vector<T> {
+    /// Computes `self · other`, panicking if the lengths differ.
+    pub fn dot(&self, other: &Self) -> T {
+        assert_same_len(self, other);
+        level1::dot(&self.data, &other.data)
+    }
+
+    /// Computes `self += alpha * x`, panicking if the lengths differ.
+    pub fn axpy(&mut self, alpha: T, x: &Self) {
+        assert_same_len(self, x);
+        level1::axpy(alpha, &x.data, &mut self.data);
+    }
+
+    /// The Euclidean norm.
+    pub fn nrm2(&self) -> T {
+        level1::nrm2(&self.data)
+    }
+
+    /// The sum of the absolute values.
+    pub fn asum(&self) -> T {
+        level1::asum(&self.data)
+    }
+
+    /// The index of the first entry with the largest absolute value, or 0 if the vector is empty.
+    pub fn iamax(&self) -> usize {
+        level1::iamax(&self.data)
+    }
+
+    /// Computes `self *= alpha`.
+    pub fn scal(&mut self, alpha: T) {
+        level1::scal(alpha, &mut self.data);
+    }
+
+    /// Applies `givens` to the pairs `(self_i, other_i)`, panicking if the lengths differ.
+    pub fn rot(&mut self, other: &mut Self, givens: Givens<T>) {
+        assert_same_len(self, other);
+        level1::rot(&mut self.data, &mut other.data, givens);
+    }
+}
+
+/// Panics with a `DimensionMismatch` between the two vectors, seen as columns, if their lengths
+/// differ.
+fn assert_same_len<T>(x: &Vector<T>, y: &Vector<T>) {
+    if let Err(e) = check_same_shape((x.data.len(), 1), (y.data.len(), 1)) {
+        panic!("{}", e);
+    }
+}
+
+impl<T: Element> From<Vec<T>> for Vector<T> {
+    fn from(data: Vec<T>) -> Self {
+        Self::new(data)
+    }
+}
+
+impl<T: Element> Index<usize> for Vector<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        &self.data[index]
+    }
+}
+
+impl<T: Element> IndexMut<usize> for Vector<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        &mut self.data[index]
+    }
+}
@@ -0,0 +1,454 @@
+use crate::error::{assert_index, check_range, check_same_shape};
+use crate::{Element, Matrix, MatrixError};
+use std::marker::PhantomData;
+use std::ops::{Index, IndexMut, Range};
+
+/// Zero-copy transposed view of a matrix: element `(i, j)` of the view is element `(j, i)`
+/// of the underlying matrix.
+#[derive(Debug)]
+pub struct Transposed<'a, T, M> {
+    matrix: &'a M,
+    _element: PhantomData<T>,
+}
+
+impl<T, M> Clone for Transposed<'_, T, M> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, M> Copy for Transposed<'_, T, M> {}
+
+impl<'a, T: Element, M: Matrix<T>> Transposed<'a, T, M> {
+    pub fn new(matrix: &'a M) -> Self {
+        Self {
+            matrix,
+            _element: PhantomData,
+        }
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        let (rows, cols) = self.matrix.shape();
+        (cols, rows)
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.matrix.num_cols()
+    }
+
+    pub fn num_cols(&self) -> usize {
+        self.matrix.num_rows()
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> T {
+        self.matrix.get(col, row)
+    }
+
+    /// Borrows a column of the view, which is a row of the underlying matrix.
+    pub fn col(&self, col: usize) -> &'a [T] {
+        self.matrix.row(col)
+    }
+
+    /// Copies the view into an owned matrix.
+    pub fn to_matrix(&self) -> M {
+        self.matrix.transpose()
+    }
+}
+
+impl<T: Element, M: Matrix<T> + Index<(usize, usize), Output = T>> Index<(usize, usize)>
+    for Transposed<'_, T, M>
+{
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        &self.matrix[(col, row)]
+    }
+}
+
+/// Number of elements a view needs from the start of its storage.
+fn required_len(shape: (usize, usize), row_stride: usize, col_stride: usize) -> usize {
+    if shape.0 == 0 || shape.1 == 0 {
+        0
+    } else {
+        (shape.0 - 1) * row_stride + (shape.1 - 1) * col_stride + 1
+    }
+}
+
+/// Borrowed, possibly strided, block of a row-major matrix. Element `(i, j)` lives at
+/// `i * row_stride + j * col_stride` in the underlying storage.
+#[derive(Debug, Clone, Copy)]
+pub struct MatrixView<'a, T> {
+    data: &'a [T],
+    shape: (usize, usize),
+    row_stride: usize,
+    col_stride: usize,
+}
+
+impl<'a, T: Element> MatrixView<'a, T> {
+    pub fn new(
+        data: &'a [T],
+        shape: (usize, usize),
+        row_stride: usize,
+        col_stride: usize,
+    ) -> Result<Self, MatrixError> {
+        let required = required_len(shape, row_stride, col_stride);
+        if required > data.len() {
+            return Err(MatrixError::StorageTooSmall {
+                required,
+                len: data.len(),
+            });
+        }
+
+        Ok(Self {
+            data,
+            shape,
+            row_stride,
+            col_stride,
+        })
+    }
+
+    /// View of a whole contiguous row-major matrix.
+    pub(crate) fn from_row_major(data: &'a [T], rows: usize, cols: usize) -> Self {
+        debug_assert_eq!(data.len(), rows * cols);
+        Self {
+            data,
+            shape: (rows, cols),
+            row_stride: cols,
+            col_stride: 1,
+        }
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        self.shape
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.shape.0
+    }
+
+    pub fn num_cols(&self) -> usize {
+        self.shape.1
+    }
+
+    pub fn row_stride(&self) -> usize {
+        self.row_stride
+    }
+
+    pub fn col_stride(&self) -> usize {
+        self.col_stride
+    }
+
+    /// The storage starting at element `(0, 0)`.
+    pub(crate) fn storage(&self) -> &'a [T] {
+        self.data
+    }
+
+    #[inline(always)]
+    pub(crate) fn at(&self, row: usize, col: usize) -> T {
+        self.data[row * self.row_stride + col * self.col_stride]
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> T {
+        assert_index((row, col), self.shape);
+        self.at(row, col)
+    }
+
+    /// Borrows a row, if its elements are adjacent in the storage.
+    pub fn row(&self, row: usize) -> Option<&'a [T]> {
+        assert_index((row, 0), (self.shape.0, 1));
+        if self.col_stride != 1 {
+            return None;
+        }
+        let start = row * self.row_stride;
+        Some(&self.data[start..start + self.shape.1])
+    }
+
+    /// Sub-block covering `rows` x `cols` of this view, panicking if the ranges are out of bounds.
+    pub fn submatrix(&self, rows: Range<usize>, cols: Range<usize>) -> Self {
+        self.try_submatrix(rows, cols)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_submatrix(
+        &self,
+        rows: Range<usize>,
+        cols: Range<usize>,
+    ) -> Result<Self, MatrixError> {
+        check_range(&rows, &cols, self.shape)?;
+        let start = rows.start * self.row_stride + cols.start * self.col_stride;
+
+        Ok(Self {
+            data: &self.data[start.min(self.data.len())..],
+            shape: (rows.len(), cols.len()),
+            row_stride: self.row_stride,
+            col_stride: self.col_stride,
+        })
+    }
+
+    /// Transposed view of the same storage, obtained by swapping the strides.
+    pub fn transposed(&self) -> Self {
+        Self {
+            data: self.data,
+            shape: (self.shape.1, self.shape.0),
+            row_stride: self.col_stride,
+            col_stride: self.row_stride,
+        }
+    }
+
+    /// Copies the view into a new row-major `Vec`.
+    pub fn to_vec(&self) -> Vec<T> {
+        let (rows, cols) = self.shape;
+        let mut res = Vec::with_capacity(rows * cols);
+        for i in 0..rows {
+            res.extend((0..cols).map(|j| self.at(i, j)));
+        }
+        res
+    }
+}
+
+impl<T: Element> Index<(usize, usize)> for MatrixView<'_, T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        assert_index((row, col), self.shape);
+        &self.data[row * self.row_stride + col * self.col_stride]
+    }
+}
+
+/// Mutable counterpart of [`MatrixView`].
+#[derive(Debug)]
+pub struct MatrixViewMut<'a, T> {
+    data: &'a mut [T],
+    shape: (usize, usize),
+    row_stride: usize,
+    col_stride: usize,
+}
+
+impl<'a, T: Element> MatrixViewMut<'a, T> {
+    pub fn new(
+        data: &'a mut [T],
+        shape: (usize, usize),
+        row_stride: usize,
+        col_stride: usize,
+    ) -> Result<Self, MatrixError> {
+        let required = required_len(shape, row_stride, col_stride);
+        if required > data.len() {
+            return Err(MatrixError::StorageTooSmall {
+                required,
+                len: data.len(),
+            });
+        }
+
+        Ok(Self {
+            data,
+            shape,
+            row_stride,
+            col_stride,
+        })
+    }
+
+    /// View of a whole contiguous row-major matrix.
+    pub(crate) fn from_row_major(data: &'a mut [T], rows: usize, cols: usize) -> Self {
+        debug_assert_eq!(data.len(), rows * cols);
+        Self {
+            data,
+            shape: (rows, cols),
+            row_stride: cols,
+            col_stride: 1,
+        }
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        self.shape
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.shape.0
+    }
+
+    pub fn num_cols(&self) -> usize {
+        self.shape.1
+    }
+
+    pub fn row_stride(&self) -> usize {
+        self.row_stride
+    }
+
+    pub fn col_stride(&self) -> usize {
+        self.col_stride
+    }
+
+    /// The storage starting at element `(0, 0)`.
+    pub(crate) fn storage_mut(&mut self) -> &mut [T] {
+        self.data
+    }
+
+    #[inline(always)]
+    pub(crate) fn at_mut(&mut self, row: usize, col: usize) -> &mut T {
+        &mut self.data[row * self.row_stride + col * self.col_stride]
+    }
+
+    pub fn as_view(&self) -> MatrixView<'_, T> {
+        MatrixView {
+            data: self.data,
+            shape: self.shape,
+            row_stride: self.row_stride,
+            col_stride: self.col_stride,
+        }
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> T {
+        self.as_view().get(row, col)
+    }
+
+    pub fn get_mut(&mut self, row: usize, col: usize) -> &mut T {
+        assert_index((row, col), self.shape);
+        self.at_mut(row, col)
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, value: T) {
+        *self.get_mut(row, col) = value;
+    }
+
+    /// Reborrows the view for a shorter lifetime.
+    pub fn reborrow(&mut self) -> MatrixViewMut<'_, T> {
+        MatrixViewMut {
+            data: self.data,
+            shape: self.shape,
+            row_stride: self.row_stride,
+            col_stride: self.col_stride,
+        }
+    }
+
+    /// Mutable sub-block covering `rows` x `cols`, panicking if the ranges are out of bounds.
+    pub fn submatrix_mut(
+        &mut self,
+        rows: Range<usize>,
+        cols: Range<usize>,
+    ) -> MatrixViewMut<'_, T> {
+        self.reborrow().into_submatrix(rows, cols)
+    }
+
+    /// Like `submatrix_mut`, but consumes the view to keep the original lifetime.
+    pub fn into_submatrix(self, rows: Range<usize>, cols: Range<usize>) -> Self {
+        self.try_into_submatrix(rows, cols)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_into_submatrix(
+        self,
+        rows: Range<usize>,
+        cols: Range<usize>,
+    ) -> Result<Self, MatrixError> {
+        check_range(&rows, &cols, self.shape)?;
+        let start = rows.start * self.row_stride + cols.start * self.col_stride;
+        let len = self.data.len();
+
+        Ok(Self {
+            data: &mut self.data[start.min(len)..],
+            shape: (rows.len(), cols.len()),
+            row_stride: self.row_stride,
+            col_stride: self.col_stride,
+        })
+    }
+
+    pub fn fill(&mut self, value: T) {
+        let (rows, cols) = self.shape;
+        for i in 0..rows {
+            for j in 0..cols {
+                *self.at_mut(i, j) = value;
+            }
+        }
+    }
+
+    /// Copies `other` into this view, panicking if the shapes differ.
+    pub fn copy_from(&mut self, other: &MatrixView<T>) {
+        if let Err(e) = check_same_shape(self.shape, other.shape) {
+            panic!("{}", e);
+        }
+        let (rows, cols) = self.shape;
+        for i in 0..rows {
+            for j in 0..cols {
+                *self.at_mut(i, j) = other.at(i, j);
+            }
+        }
+    }
+}
+
+impl<T: Element> Index<(usize, usize)> for MatrixViewMut<'_, T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        assert_index((row, col), self.shape);
+        &self.data[row * self.row_stride + col * self.col_stride]
+    }
+}
+
+impl<T: Element> IndexMut<(usize, usize)> for MatrixViewMut<'_, T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        self.get_mut(row, col)
+    }
+}
+
+/// `f` applied to every element of a view, as a row-major `Vec`.
+pub(crate) fn map_view<T: Element>(a: &MatrixView<T>, f: impl Fn(T) -> T) -> Vec<T> {
+    let (rows, cols) = a.shape();
+    let mut res = Vec::with_capacity(rows * cols);
+    for i in 0..rows {
+        res.extend((0..cols).map(|j| f(a.at(i, j))));
+    }
+    res
+}
+
+/// Element-wise combination of two views of the same shape, as a row-major `Vec`.
+pub(crate) fn zip_map_views<T: Element>(
+    a: &MatrixView<T>,
+    b: &MatrixView<T>,
+    f: impl Fn(T, T) -> T,
+) -> Vec<T> {
+    let (rows, cols) = a.shape();
+    let mut res = Vec::with_capacity(rows * cols);
+    for i in 0..rows {
+        res.extend((0..cols).map(|j| f(a.at(i, j), b.at(i, j))));
+    }
+    res
+}
+
+/// Product of two views as a row-major `Vec`.
+pub(crate) fn multiply_views<T: Element>(a: &MatrixView<T>, b: &MatrixView<T>) -> Vec<T> {
+    let (rows, cols) = (a.num_rows(), b.num_cols());
+    let mut res = vec![T::zero(); rows * cols];
+    let mut res_view = MatrixViewMut::from_row_major(&mut res, rows, cols);
+    gemm_views(T::one(), a, b, T::zero(), &mut res_view);
+    res
+}
+
+/// Computes `c = alpha * a * b + beta * c`, accumulating rows of `b` so the inner loop is
+/// sequential whenever `b` and `c` have unit column stride.
+pub(crate) fn gemm_views<T: Element>(
+    alpha: T,
+    a: &MatrixView<T>,
+    b: &MatrixView<T>,
+    beta: T,
+    c: &mut MatrixViewMut<T>,
+) {
+    let (rows, inner) = a.shape();
+    let cols = b.num_cols();
+
+    for i in 0..rows {
+        for j in 0..cols {
+            let c_ij = c.at_mut(i, j);
+            *c_ij = if beta == T::zero() {
+                T::zero()
+            } else {
+                beta * *c_ij
+            };
+        }
+        for p in 0..inner {
+            let a_ip = alpha * a.at(i, p);
+            for j in 0..cols {
+                *c.at_mut(i, j) += a_ip * b.at(p, j);
+            }
+        }
+    }
+}
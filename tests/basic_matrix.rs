@@ -1,3 +1,4 @@
+use rmatrix::Complex;
 use rmatrix::matrices::basic_matrix::*;
 use test_functions::*;
 mod test_functions;
@@ -41,3 +42,175 @@ fn basic_matrix_test_matrix_multiplication() {
 fn basic_matrix_test_scalar_multiplication() {
     test_scalar_multiplication::<BasicMatrix>();
 }
+
+#[test]
+fn basic_matrix_test_chained_operations() {
+    test_chained_operations::<BasicMatrix>();
+}
+
+#[test]
+fn basic_matrix_test_try_new() {
+    test_try_new::<BasicMatrix>();
+}
+
+#[test]
+fn basic_matrix_test_new_invalid_data() {
+    test_new_invalid_data::<BasicMatrix>();
+}
+
+#[test]
+fn basic_matrix_test_try_zeroes_and_identity() {
+    test_try_zeroes_and_identity::<BasicMatrix>();
+}
+
+#[test]
+fn basic_matrix_test_try_matrix_addition() {
+    test_try_matrix_addition::<BasicMatrix>();
+}
+
+#[test]
+fn basic_matrix_test_try_matrix_subtraction() {
+    test_try_matrix_subtraction::<BasicMatrix>();
+}
+
+#[test]
+fn basic_matrix_test_try_matrix_multiplication() {
+    test_try_matrix_multiplication::<BasicMatrix>();
+}
+
+#[test]
+fn basic_matrix_test_f32_elements() {
+    test_element_type::<f32, BasicMatrix<f32>>();
+}
+
+#[test]
+fn basic_matrix_test_i32_elements() {
+    test_element_type::<i32, BasicMatrix<i32>>();
+}
+
+#[test]
+fn basic_matrix_test_complex_elements() {
+    test_complex_elements::<BasicMatrix<Complex<f64>>>();
+}
+
+#[test]
+fn basic_matrix_test_conjugate_transpose() {
+    test_conjugate_transpose::<BasicMatrix<Complex<f64>>>();
+}
+
+#[test]
+fn basic_matrix_test_operators() {
+    test_operators::<BasicMatrix>();
+}
+
+#[test]
+fn basic_matrix_test_operators_dimension_mismatch() {
+    test_operators_dimension_mismatch::<BasicMatrix>();
+}
+
+#[test]
+fn basic_matrix_test_addition_subtraction_dimension_mismatch() {
+    test_addition_subtraction_dimension_mismatch::<BasicMatrix>();
+}
+
+#[test]
+fn basic_matrix_test_assign_dimension_mismatch() {
+    test_assign_dimension_mismatch::<BasicMatrix>();
+}
+
+#[test]
+#[should_panic(expected = "dimension mismatch")]
+fn basic_matrix_test_gemm_dimension_mismatch() {
+    test_gemm_dimension_mismatch::<BasicMatrix>();
+}
+
+#[test]
+fn basic_matrix_test_element_access() {
+    test_element_access::<BasicMatrix>();
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn basic_matrix_test_index_out_of_bounds() {
+    test_index_out_of_bounds::<BasicMatrix>();
+}
+
+#[test]
+fn basic_matrix_test_data_access() {
+    test_data_access::<BasicMatrix>();
+}
+
+#[test]
+fn basic_matrix_test_transpose() {
+    test_transpose::<BasicMatrix>();
+}
+
+#[test]
+fn basic_matrix_test_transposed_view() {
+    test_transposed_view::<BasicMatrix>();
+}
+
+#[test]
+fn basic_matrix_test_in_place_arithmetic() {
+    test_in_place_arithmetic::<BasicMatrix>();
+}
+
+#[test]
+fn basic_matrix_test_gemm() {
+    test_gemm::<BasicMatrix>();
+}
+
+#[test]
+fn basic_matrix_test_symmetric_eigen() {
+    test_symmetric_eigen::<BasicMatrix>();
+}
+
+#[test]
+fn basic_matrix_test_eigenvalues() {
+    test_eigenvalues::<BasicMatrix>();
+}
+
+#[test]
+fn basic_matrix_test_matvec() {
+    test_matvec::<BasicMatrix>();
+}
+
+#[test]
+fn basic_matrix_test_vecmat() {
+    test_vecmat::<BasicMatrix>();
+}
+
+#[test]
+fn basic_matrix_test_trace_and_sums() {
+    test_trace_and_sums::<BasicMatrix>();
+}
+
+#[test]
+fn basic_matrix_test_norms() {
+    test_norms::<BasicMatrix>();
+}
+
+#[test]
+fn basic_matrix_test_min_max() {
+    test_min_max::<BasicMatrix>();
+}
+
+#[test]
+fn basic_matrix_test_row_col_statistics() {
+    test_row_col_statistics::<BasicMatrix>();
+}
+
+#[test]
+fn basic_matrix_test_hadamard_and_division() {
+    test_hadamard_and_division::<BasicMatrix>();
+}
+
+#[test]
+fn basic_matrix_test_map_and_zip_map() {
+    test_map_and_zip_map::<BasicMatrix>();
+}
+
+#[test]
+fn basic_matrix_test_unary_functions() {
+    test_unary_functions::<BasicMatrix>();
+}
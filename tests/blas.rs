@@ -0,0 +1,276 @@
+use rmatrix::blas::*;
+
+// A = [1 2 3; 4 5 6] and B = [7 8; 9 10; 11 12] in row-major order.
+const A: [f64; 6] = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+const B: [f64; 6] = [7.0, 8.0, 9.0, 10.0, 11.0, 12.0];
+
+#[test]
+fn blas_test_dgemm_transposed_operands() {
+    // A^T and B^T stored row-major, so op(A) * op(B) is still A * B
+    let a_t = [1.0, 4.0, 2.0, 5.0, 3.0, 6.0];
+    let b_t = [7.0, 9.0, 11.0, 8.0, 10.0, 12.0];
+    let mut c = [0.0; 4];
+
+    unsafe {
+        cblas_dgemm(
+            CBlasLayout::CblasRowMajor,
+            CBlasTranspose::CblasTrans,
+            CBlasTranspose::CblasTrans,
+            2,
+            2,
+            3,
+            1.0,
+            a_t.as_ptr(),
+            2,
+            b_t.as_ptr(),
+            3,
+            0.0,
+            c.as_mut_ptr(),
+            2,
+        );
+    }
+
+    assert_eq!(c, [58.0, 64.0, 139.0, 154.0]);
+}
+
+#[test]
+fn blas_test_dgemm_col_major_alpha_beta() {
+    // Row-major A is column-major A^T, so this computes C^T = 2 * B^T * A^T + C^T
+    let mut c = [1.0, 1.0, 1.0, 1.0];
+
+    unsafe {
+        cblas_dgemm(
+            CBlasLayout::CblasColMajor,
+            CBlasTranspose::CblasNoTrans,
+            CBlasTranspose::CblasNoTrans,
+            2,
+            2,
+            3,
+            2.0,
+            B.as_ptr(),
+            2,
+            A.as_ptr(),
+            3,
+            1.0,
+            c.as_mut_ptr(),
+            2,
+        );
+    }
+
+    // Column-major C^T is row-major C = 2 * A * B + 1
+    assert_eq!(c, [117.0, 129.0, 279.0, 309.0]);
+}
+
+#[test]
+fn blas_test_dgetrf_dgetrs() {
+    // [2 1 1; 4 3 3; 8 7 9] in column-major order
+    let mut lu = [2.0, 4.0, 8.0, 1.0, 3.0, 7.0, 1.0, 3.0, 9.0];
+    let mut ipiv = [0; 3];
+    let info = unsafe { f64::getrf(3, 3, lu.as_mut_ptr(), 3, ipiv.as_mut_ptr()) };
+    assert_eq!(info, 0);
+    assert_eq!(ipiv, [3, 3, 3]);
+
+    // A * [1 2 3]^T and A^T * [1 2 3]^T
+    for (trans, rhs) in [(b'N', [7.0, 19.0, 49.0]), (b'T', [34.0, 28.0, 34.0])] {
+        let mut x = rhs;
+        unsafe {
+            f64::getrs(
+                trans,
+                3,
+                1,
+                lu.as_ptr(),
+                3,
+                ipiv.as_ptr(),
+                x.as_mut_ptr(),
+                3,
+            )
+        };
+        for (x_i, expected) in x.iter().zip([1.0, 2.0, 3.0]) {
+            assert!((x_i - expected).abs() < 1e-12, "{:?}", x);
+        }
+    }
+}
+
+#[test]
+fn blas_test_dgetrf_singular() {
+    let mut lu = [1.0, 2.0, 2.0, 4.0];
+    let mut ipiv = [0; 2];
+    let info = unsafe { f64::getrf(2, 2, lu.as_mut_ptr(), 2, ipiv.as_mut_ptr()) };
+    assert_eq!(info, 2);
+}
+
+#[test]
+fn blas_test_dtrsm() {
+    // U = [2 1; 0 4] and L = U^T, stored row-major with junk in the unused triangle
+    let u = [2.0, 1.0, 99.0, 4.0];
+    let l = [2.0, 99.0, 1.0, 4.0];
+
+    // U * X = B with B = U * [1 2; 3 4]
+    let mut b = [5.0, 8.0, 12.0, 16.0];
+    unsafe {
+        cblas_dtrsm(
+            CBlasLayout::CblasRowMajor,
+            CBlasSide::CblasLeft,
+            CBlasUplo::CblasUpper,
+            CBlasTranspose::CblasNoTrans,
+            CBlasDiag::CblasNonUnit,
+            2,
+            2,
+            1.0,
+            u.as_ptr(),
+            2,
+            b.as_mut_ptr(),
+            2,
+        );
+    }
+    assert_eq!(b, [1.0, 2.0, 3.0, 4.0]);
+
+    // X * L^T = 2 * B, i.e. X * U = 2 * B with B = [1 2; 3 4] * U / 2
+    let mut b = [1.0, 4.5, 3.0, 9.5];
+    unsafe {
+        cblas_dtrsm(
+            CBlasLayout::CblasRowMajor,
+            CBlasSide::CblasRight,
+            CBlasUplo::CblasLower,
+            CBlasTranspose::CblasTrans,
+            CBlasDiag::CblasNonUnit,
+            2,
+            2,
+            2.0,
+            l.as_ptr(),
+            2,
+            b.as_mut_ptr(),
+            2,
+        );
+    }
+    assert_eq!(b, [1.0, 2.0, 3.0, 4.0]);
+
+    // Column-major, where `l` is U and the unit diagonal is assumed: [1 1; 0 1] * X = B
+    let mut b = [3.0, 2.0, 7.0, 4.0];
+    unsafe {
+        cblas_dtrsm(
+            CBlasLayout::CblasColMajor,
+            CBlasSide::CblasLeft,
+            CBlasUplo::CblasUpper,
+            CBlasTranspose::CblasNoTrans,
+            CBlasDiag::CblasUnit,
+            2,
+            2,
+            1.0,
+            l.as_ptr(),
+            2,
+            b.as_mut_ptr(),
+            2,
+        );
+    }
+    assert_eq!(b, [1.0, 2.0, 3.0, 4.0]);
+}
+
+#[test]
+fn blas_test_dgemv() {
+    // y = 2 * A * x + y
+    let x = [1.0, 0.0, -1.0];
+    let mut y = [1.0, 1.0];
+    unsafe {
+        cblas_dgemv(
+            CBlasLayout::CblasRowMajor,
+            CBlasTranspose::CblasNoTrans,
+            2,
+            3,
+            2.0,
+            A.as_ptr(),
+            3,
+            x.as_ptr(),
+            1,
+            1.0,
+            y.as_mut_ptr(),
+            1,
+        );
+    }
+    assert_eq!(y, [-3.0, -3.0]);
+
+    // A^T * x, reading every other entry of x and writing every other entry of y
+    let x = [1.0, 99.0, -1.0];
+    let mut y = [0.0, 99.0, 0.0, 99.0, 0.0];
+    unsafe {
+        cblas_dgemv(
+            CBlasLayout::CblasRowMajor,
+            CBlasTranspose::CblasTrans,
+            2,
+            3,
+            1.0,
+            A.as_ptr(),
+            3,
+            x.as_ptr(),
+            2,
+            0.0,
+            y.as_mut_ptr(),
+            2,
+        );
+    }
+    assert_eq!(y, [-3.0, 99.0, -3.0, 99.0, -3.0]);
+
+    // The same A stored column-major is [1 3 5; 2 4 6]
+    let x = [1.0, 1.0, 1.0];
+    let mut y = [0.0; 2];
+    unsafe {
+        cblas_dgemv(
+            CBlasLayout::CblasColMajor,
+            CBlasTranspose::CblasNoTrans,
+            2,
+            3,
+            1.0,
+            A.as_ptr(),
+            2,
+            x.as_ptr(),
+            1,
+            0.0,
+            y.as_mut_ptr(),
+            1,
+        );
+    }
+    assert_eq!(y, [9.0, 12.0]);
+}
+
+#[test]
+fn blas_test_level1_reductions() {
+    let x = [1.0, -7.0, 2.0, 99.0, -3.0];
+    unsafe {
+        // Every other entry: [1, 2, -3]
+        assert_eq!(cblas_ddot(3, x.as_ptr(), 2, A.as_ptr(), 1), 1.0 + 4.0 - 9.0);
+        assert_eq!(cblas_dasum(3, x.as_ptr(), 2), 6.0);
+        assert_eq!(cblas_idamax(3, x.as_ptr(), 2), 2);
+        assert_eq!(cblas_idamax(5, x.as_ptr(), 1), 3);
+        assert_eq!(cblas_dnrm2(2, [3.0, 4.0].as_ptr(), 1), 5.0);
+
+        // A negative increment walks x backwards: [-3, 2, 1]
+        assert_eq!(
+            cblas_ddot(3, x.as_ptr(), -2, A.as_ptr(), 1),
+            -3.0 + 4.0 + 3.0
+        );
+        assert_eq!(cblas_dnrm2(3, x.as_ptr(), -2), 0.0);
+    }
+}
+
+#[test]
+fn blas_test_level1_updates() {
+    let mut y = [1.0, 0.0, 1.0, 0.0, 1.0];
+    unsafe {
+        cblas_daxpy(3, 2.0, A.as_ptr(), 1, y.as_mut_ptr(), 2);
+        assert_eq!(y, [3.0, 0.0, 5.0, 0.0, 7.0]);
+        cblas_dscal(2, -1.0, y.as_mut_ptr(), 4);
+        assert_eq!(y, [-3.0, 0.0, 5.0, 0.0, -7.0]);
+    }
+
+    let (mut a, mut b, mut c, mut s) = (3.0, 4.0, 0.0, 0.0);
+    unsafe { cblas_drotg(&mut a, &mut b, &mut c, &mut s) };
+    assert_eq!((a, c, s), (5.0, 0.6, 0.8));
+    // |a| <= |b|, so z = 1 / c
+    assert!((b - 1.0 / 0.6).abs() < 1e-15);
+
+    let mut x = [3.0, 6.0];
+    let mut y = [4.0, 8.0];
+    unsafe { cblas_drot(2, x.as_mut_ptr(), 1, y.as_mut_ptr(), 1, c, s) };
+    assert_eq!(x, [5.0, 10.0]);
+    assert!(y.iter().all(|y| y.abs() < 1e-15));
+}
@@ -1,5 +1,8 @@
+use flat_functions::*;
 use rmatrix::matrices::blas_matrix::*;
+use rmatrix::{Complex, Matrix};
 use test_functions::*;
+mod flat_functions;
 mod test_functions;
 
 #[test]
@@ -41,3 +44,334 @@ fn blas_matrix_test_matrix_multiplication() {
 fn blas_matrix_test_scalar_multiplication() {
     test_scalar_multiplication::<BlasMatrix>();
 }
+
+#[test]
+fn blas_matrix_test_chained_operations() {
+    test_chained_operations::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_try_new() {
+    test_try_new::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_new_invalid_data() {
+    test_new_invalid_data::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_try_zeroes_and_identity() {
+    test_try_zeroes_and_identity::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_try_matrix_addition() {
+    test_try_matrix_addition::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_try_matrix_subtraction() {
+    test_try_matrix_subtraction::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_try_matrix_multiplication() {
+    test_try_matrix_multiplication::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_f32_elements() {
+    test_element_type::<f32, BlasMatrix<f32>>();
+}
+
+#[test]
+fn blas_matrix_test_complex_elements() {
+    test_complex_elements::<BlasMatrix<Complex<f64>>>();
+}
+
+#[test]
+fn blas_matrix_test_conjugate_transpose() {
+    test_conjugate_transpose::<BlasMatrix<Complex<f64>>>();
+}
+
+#[test]
+fn blas_matrix_test_conjugate_transpose_multiplication() {
+    let c = |re, im| Complex::new(re, im);
+    let matrix1 = BlasMatrix::new(vec![
+        vec![c(1.0, 1.0), c(2.0, 0.0)],
+        vec![c(0.0, -1.0), c(3.0, 1.0)],
+    ]);
+    let matrix2 = BlasMatrix::new(vec![vec![c(1.0, 0.0)], vec![c(0.0, 1.0)]]);
+
+    let expected = matrix1
+        .conjugate_transpose()
+        .matrix_multiplication(&matrix2);
+    let result = matrix1.conjugate_transpose_multiplication(&matrix2);
+
+    assert_eq!(result.shape(), (2, 1));
+    assert_eq!(rows(&result), rows(&expected));
+    assert_eq!(rows(&result), vec![vec![c(0.0, -1.0)], vec![c(3.0, 3.0)]]);
+}
+
+#[test]
+#[should_panic(expected = "dimension mismatch")]
+fn blas_matrix_test_conjugate_transpose_multiplication_dimension_mismatch() {
+    let matrix1 = BlasMatrix::new(vec![vec![1.0, 2.0]; 4]);
+    let matrix2 = BlasMatrix::new(vec![vec![1.0, 2.0]]);
+    matrix1.conjugate_transpose_multiplication(&matrix2);
+}
+
+#[test]
+fn blas_matrix_test_operators() {
+    test_operators::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_operators_dimension_mismatch() {
+    test_operators_dimension_mismatch::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_addition_subtraction_dimension_mismatch() {
+    test_addition_subtraction_dimension_mismatch::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_assign_dimension_mismatch() {
+    test_assign_dimension_mismatch::<BlasMatrix>();
+}
+
+#[test]
+#[should_panic(expected = "dimension mismatch")]
+fn blas_matrix_test_gemm_dimension_mismatch() {
+    test_gemm_dimension_mismatch::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_element_access() {
+    test_element_access::<BlasMatrix>();
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn blas_matrix_test_index_out_of_bounds() {
+    test_index_out_of_bounds::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_data_access() {
+    test_data_access::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_transpose() {
+    test_transpose::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_transposed_view() {
+    test_transposed_view::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_from_vec() {
+    test_from_vec::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_submatrix() {
+    test_submatrix::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_invalid_views() {
+    test_invalid_views::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_view_arithmetic() {
+    test_view_arithmetic::<BlasMatrix>();
+}
+
+#[test]
+#[should_panic(expected = "dimension mismatch")]
+fn blas_matrix_test_view_dimension_mismatch() {
+    test_view_dimension_mismatch::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_submatrix_mut() {
+    test_submatrix_mut::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_in_place_arithmetic() {
+    test_in_place_arithmetic::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_gemm() {
+    test_gemm::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_view_gemm() {
+    test_view_gemm::<BlasMatrix>();
+}
+
+#[test]
+#[should_panic(expected = "dimension mismatch")]
+fn blas_matrix_test_view_gemm_dimension_mismatch() {
+    test_view_gemm_dimension_mismatch::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_view_gemm_edge_blocks() {
+    test_view_gemm_edge_blocks::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_lu() {
+    test_lu::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_determinant() {
+    test_determinant::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_solve() {
+    test_solve::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_inverse() {
+    test_inverse::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_qr() {
+    test_qr::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_qr_with_pivoting() {
+    test_qr_with_pivoting::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_rank() {
+    test_rank::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_least_squares() {
+    test_least_squares::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_cholesky() {
+    test_cholesky::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_cholesky_solve() {
+    test_cholesky_solve::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_log_determinant() {
+    test_log_determinant::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_svd() {
+    test_svd::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_pinv() {
+    test_pinv::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_norm_2_and_condition_number() {
+    test_norm_2_and_condition_number::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_symmetric_eigen() {
+    test_symmetric_eigen::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_eigenvalues() {
+    test_eigenvalues::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_triangular_solve() {
+    test_triangular_solve::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_triangular_multiplication() {
+    test_triangular_multiplication::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_matvec() {
+    test_matvec::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_vecmat() {
+    test_vecmat::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_level1_reductions() {
+    test_level1_reductions::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_level1_updates() {
+    test_level1_updates::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_trace_and_sums() {
+    test_trace_and_sums::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_norms() {
+    test_norms::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_min_max() {
+    test_min_max::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_row_col_statistics() {
+    test_row_col_statistics::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_hadamard_and_division() {
+    test_hadamard_and_division::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_map_and_zip_map() {
+    test_map_and_zip_map::<BlasMatrix>();
+}
+
+#[test]
+fn blas_matrix_test_unary_functions() {
+    test_unary_functions::<BlasMatrix>();
+}
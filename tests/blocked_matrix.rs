@@ -1,5 +1,8 @@
 use rmatrix::matrices::blocked_matrix::*;
+mod flat_functions;
 mod test_functions;
+use flat_functions::*;
+use rmatrix::Complex;
 use test_functions::*;
 
 #[test]
@@ -41,3 +44,312 @@ fn blocked_matrix_test_matrix_multiplication() {
 fn blocked_matrix_test_scalar_multiplication() {
     test_scalar_multiplication::<BlockedMatrix>();
 }
+
+#[test]
+fn blocked_matrix_test_chained_operations() {
+    test_chained_operations::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_try_new() {
+    test_try_new::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_new_invalid_data() {
+    test_new_invalid_data::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_try_zeroes_and_identity() {
+    test_try_zeroes_and_identity::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_try_matrix_addition() {
+    test_try_matrix_addition::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_try_matrix_subtraction() {
+    test_try_matrix_subtraction::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_try_matrix_multiplication() {
+    test_try_matrix_multiplication::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_f32_elements() {
+    test_element_type::<f32, BlockedMatrix<f32>>();
+}
+
+#[test]
+fn blocked_matrix_test_i32_elements() {
+    test_element_type::<i32, BlockedMatrix<i32>>();
+}
+
+#[test]
+fn blocked_matrix_test_complex_elements() {
+    test_complex_elements::<BlockedMatrix<Complex<f64>>>();
+}
+
+#[test]
+fn blocked_matrix_test_conjugate_transpose() {
+    test_conjugate_transpose::<BlockedMatrix<Complex<f64>>>();
+}
+
+#[test]
+fn blocked_matrix_test_operators() {
+    test_operators::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_operators_dimension_mismatch() {
+    test_operators_dimension_mismatch::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_addition_subtraction_dimension_mismatch() {
+    test_addition_subtraction_dimension_mismatch::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_assign_dimension_mismatch() {
+    test_assign_dimension_mismatch::<BlockedMatrix>();
+}
+
+#[test]
+#[should_panic(expected = "dimension mismatch")]
+fn blocked_matrix_test_gemm_dimension_mismatch() {
+    test_gemm_dimension_mismatch::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_element_access() {
+    test_element_access::<BlockedMatrix>();
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn blocked_matrix_test_index_out_of_bounds() {
+    test_index_out_of_bounds::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_data_access() {
+    test_data_access::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_transpose() {
+    test_transpose::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_transposed_view() {
+    test_transposed_view::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_from_vec() {
+    test_from_vec::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_submatrix() {
+    test_submatrix::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_invalid_views() {
+    test_invalid_views::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_view_arithmetic() {
+    test_view_arithmetic::<BlockedMatrix>();
+}
+
+#[test]
+#[should_panic(expected = "dimension mismatch")]
+fn blocked_matrix_test_view_dimension_mismatch() {
+    test_view_dimension_mismatch::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_submatrix_mut() {
+    test_submatrix_mut::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_in_place_arithmetic() {
+    test_in_place_arithmetic::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_gemm() {
+    test_gemm::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_view_gemm() {
+    test_view_gemm::<BlockedMatrix>();
+}
+
+#[test]
+#[should_panic(expected = "dimension mismatch")]
+fn blocked_matrix_test_view_gemm_dimension_mismatch() {
+    test_view_gemm_dimension_mismatch::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_view_gemm_edge_blocks() {
+    test_view_gemm_edge_blocks::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_lu() {
+    test_lu::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_determinant() {
+    test_determinant::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_solve() {
+    test_solve::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_inverse() {
+    test_inverse::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_qr() {
+    test_qr::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_qr_with_pivoting() {
+    test_qr_with_pivoting::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_rank() {
+    test_rank::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_least_squares() {
+    test_least_squares::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_cholesky() {
+    test_cholesky::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_cholesky_solve() {
+    test_cholesky_solve::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_log_determinant() {
+    test_log_determinant::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_svd() {
+    test_svd::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_pinv() {
+    test_pinv::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_norm_2_and_condition_number() {
+    test_norm_2_and_condition_number::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_symmetric_eigen() {
+    test_symmetric_eigen::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_eigenvalues() {
+    test_eigenvalues::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_triangular_solve() {
+    test_triangular_solve::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_triangular_multiplication() {
+    test_triangular_multiplication::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_matvec() {
+    test_matvec::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_vecmat() {
+    test_vecmat::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_level1_reductions() {
+    test_level1_reductions::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_level1_updates() {
+    test_level1_updates::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_trace_and_sums() {
+    test_trace_and_sums::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_norms() {
+    test_norms::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_min_max() {
+    test_min_max::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_row_col_statistics() {
+    test_row_col_statistics::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_hadamard_and_division() {
+    test_hadamard_and_division::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_map_and_zip_map() {
+    test_map_and_zip_map::<BlockedMatrix>();
+}
+
+#[test]
+fn blocked_matrix_test_unary_functions() {
+    test_unary_functions::<BlockedMatrix>();
+}
@@ -0,0 +1,33 @@
+// Fixtures shared by the test crates that check one configuration or backend against another.
+// Not every crate uses all of them.
+#![allow(dead_code)]
+
+use rmatrix::FloatElement;
+
+/// Multiples of 1/4 between 1 and 6.5, so sums and products of a few of them are exact even in
+/// `f32` and no entry is zero.
+pub fn entries<T: FloatElement>(rows: usize, cols: usize, seed: usize) -> Vec<Vec<T>> {
+    (0..rows)
+        .map(|i| {
+            (0..cols)
+                .map(|j| {
+                    let x = T::from(((i * cols + j) * 7 + seed) % 23).unwrap();
+                    x / T::from(4).unwrap() + T::one()
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Checks that `actual` has the shape of `expected`, row by row, and that the entries agree to
+/// within `1e-9`.
+pub fn assert_close(actual: Vec<Vec<f64>>, expected: Vec<Vec<f64>>) {
+    assert_eq!(
+        actual.iter().map(Vec::len).collect::<Vec<_>>(),
+        expected.iter().map(Vec::len).collect::<Vec<_>>(),
+        "row lengths differ"
+    );
+    for (a, e) in actual.iter().flatten().zip(expected.iter().flatten()) {
+        assert!((a - e).abs() < 1e-9, "{} != {}", a, e);
+    }
+}
@@ -0,0 +1,909 @@
+// Helpers for the backends implementing `FlatMatrix`, kept apart from `test_functions`
+// so the other backends' test crates don't see them as dead code.
+
+use rmatrix::matrices::ndarray_matrix::NdarrayMatrix;
+use rmatrix::{
+    FlatMatrix, Givens, Level1, LinearAlgebra, LowerTriangular, Matrix, MatrixError, MatrixView,
+    MatrixViewMut, Svd, Triangle, UpperTriangular, Vector,
+};
+
+fn counting<T: FlatMatrix>(rows: usize, cols: usize) -> T {
+    T::from_vec((0..rows * cols).map(|x| x as f64).collect(), rows, cols)
+}
+
+/// Deterministic pseudo-random entries in `[-1, 1)`, plus `diagonal` on the diagonal.
+fn pseudo_random<T: FlatMatrix>(rows: usize, cols: usize, diagonal: f64) -> T {
+    let mut state = 0x2545_f491_u64;
+    let mut data = Vec::with_capacity(rows * cols);
+    for i in 0..rows {
+        for j in 0..cols {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            let x = (state >> 11) as f64 / (1u64 << 53) as f64 * 2.0 - 1.0;
+            data.push(if i == j { x + diagonal } else { x });
+        }
+    }
+    T::from_vec(data, rows, cols)
+}
+
+fn assert_close<A: Matrix, B: Matrix>(actual: &A, expected: &B, tolerance: f64) {
+    assert_eq!(actual.shape(), expected.shape());
+    for (a, e) in actual
+        .row_iter()
+        .flatten()
+        .zip(expected.row_iter().flatten())
+    {
+        assert!(
+            (a - e).abs() <= tolerance,
+            "{:?} != {:?}",
+            actual.get_data(),
+            expected.get_data()
+        );
+    }
+}
+
+/// Copies `matrix` into an `NdarrayMatrix`, to check results against an independent backend.
+fn to_ndarray<T: Matrix>(matrix: &T) -> NdarrayMatrix {
+    NdarrayMatrix::new(matrix.get_data())
+}
+
+pub fn test_from_vec<T: FlatMatrix>() {
+    let matrix = T::from_vec(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], 2, 3);
+
+    assert_eq!(matrix.shape(), (2, 3));
+    assert_eq!(
+        matrix.get_data(),
+        vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]
+    );
+}
+
+pub fn test_submatrix<T: FlatMatrix>() {
+    let matrix = counting::<T>(4, 5);
+    let block = matrix.submatrix(1..3, 2..5);
+
+    assert_eq!(block.shape(), (2, 3));
+    assert_eq!(block.row_stride(), 5);
+    assert_eq!(block.col_stride(), 1);
+    assert_eq!(block.to_vec(), vec![7.0, 8.0, 9.0, 12.0, 13.0, 14.0]);
+    assert_eq!(block[(1, 0)], 12.0);
+    assert_eq!(block.row(0), Some(&[7.0, 8.0, 9.0][..]));
+
+    // Views of views keep indexing the original storage
+    let inner = block.submatrix(1..2, 1..3);
+    assert_eq!(inner.to_vec(), vec![13.0, 14.0]);
+
+    let transposed = block.transposed();
+    assert_eq!(transposed.shape(), (3, 2));
+    assert_eq!(transposed.get(2, 1), 14.0);
+    assert_eq!(transposed.row(0), None);
+
+    let empty = matrix.submatrix(4..4, 0..5);
+    assert_eq!(empty.shape(), (0, 5));
+    assert!(empty.to_vec().is_empty());
+
+    let copy = T::from_view(&block);
+    assert_eq!(
+        copy.get_data(),
+        vec![vec![7.0, 8.0, 9.0], vec![12.0, 13.0, 14.0]]
+    );
+}
+
+pub fn test_invalid_views<T: FlatMatrix>() {
+    let matrix = counting::<T>(2, 3);
+
+    assert_eq!(
+        matrix.view().try_submatrix(0..3, 0..1).unwrap_err(),
+        MatrixError::InvalidRange {
+            rows: 0..3,
+            cols: 0..1,
+            shape: (2, 3)
+        }
+    );
+
+    let data = [0.0; 5];
+    assert_eq!(
+        MatrixView::new(&data, (2, 3), 3, 1).unwrap_err(),
+        MatrixError::StorageTooSmall {
+            required: 6,
+            len: 5
+        }
+    );
+
+    // Every other element of a 2x3 buffer, read as a column
+    let column = MatrixView::new(&data, (3, 1), 2, 1).unwrap();
+    assert_eq!(column.shape(), (3, 1));
+}
+
+pub fn test_view_arithmetic<T: FlatMatrix>() {
+    let a = counting::<T>(4, 4);
+    let b = T::identity(3);
+
+    let top_left = a.submatrix(0..2, 0..2);
+    let bottom_right = a.submatrix(2..4, 2..4);
+
+    let sum = T::view_addition(&top_left, &bottom_right);
+    assert_eq!(sum.get_data(), vec![vec![10.0, 12.0], vec![18.0, 20.0]]);
+
+    let difference = T::view_subtraction(&bottom_right, &top_left);
+    assert_eq!(
+        difference.get_data(),
+        vec![vec![10.0, 10.0], vec![10.0, 10.0]]
+    );
+
+    let scaled = T::view_scalar_multiplication(&top_left, 2.0);
+    assert_eq!(scaled.get_data(), vec![vec![0.0, 2.0], vec![8.0, 10.0]]);
+
+    // 2x3 block times a 3x2 block of the identity
+    let left = a.submatrix(1..3, 1..4);
+    let right = b.submatrix(0..3, 1..3);
+    let product = T::view_multiplication(&left, &right);
+    assert_eq!(product.get_data(), vec![vec![6.0, 7.0], vec![10.0, 11.0]]);
+
+    // Transposed views multiply like transposed copies
+    let product = T::view_multiplication(&left.transposed(), &left);
+    let expected = T::from_view(&left)
+        .transpose()
+        .matrix_multiplication(&T::from_view(&left));
+    assert_eq!(product.get_data(), expected.get_data());
+
+    // A view with no unit stride: every other column of `a`
+    let data = a.as_slice().unwrap();
+    let strided = MatrixView::new(data, (4, 2), 4, 2).unwrap();
+    let product = T::view_multiplication(&strided.transposed(), &strided);
+    let expected = T::from_view(&strided)
+        .transpose()
+        .matrix_multiplication(&T::from_view(&strided));
+    assert_eq!(product.get_data(), expected.get_data());
+}
+
+pub fn test_view_dimension_mismatch<T: FlatMatrix>() {
+    let matrix = counting::<T>(3, 3);
+    T::view_multiplication(&matrix.submatrix(0..2, 0..2), &matrix.submatrix(0..3, 0..1));
+}
+
+pub fn test_submatrix_mut<T: FlatMatrix>() {
+    let mut matrix = T::zeroes(3, 4);
+
+    let mut block = matrix.submatrix_mut(1..3, 1..3);
+    block.fill(1.0);
+    block[(0, 1)] = 5.0;
+    block.set(1, 0, 7.0);
+    {
+        let mut corner: MatrixViewMut<f64> = block.submatrix_mut(1..2, 1..2);
+        *corner.get_mut(0, 0) = 9.0;
+    }
+    assert_eq!(block.as_view().to_vec(), vec![1.0, 5.0, 7.0, 9.0]);
+
+    assert_eq!(
+        matrix.get_data(),
+        vec![
+            vec![0.0, 0.0, 0.0, 0.0],
+            vec![0.0, 1.0, 5.0, 0.0],
+            vec![0.0, 7.0, 9.0, 0.0],
+        ]
+    );
+
+    let source = counting::<T>(2, 2);
+    matrix.submatrix_mut(0..2, 2..4).copy_from(&source.view());
+    assert_eq!(matrix.row(0), &[0.0, 0.0, 0.0, 1.0]);
+    assert_eq!(matrix.row(1), &[0.0, 1.0, 2.0, 3.0]);
+
+    matrix.as_mut_slice()[0] = 4.0;
+    assert_eq!(matrix.get(0, 0), 4.0);
+}
+
+pub fn test_view_gemm<T: FlatMatrix>() {
+    let a = counting::<T>(2, 3);
+    let b = T::identity(3);
+    let mut c = T::from_vec(vec![1.0; 12], 3, 4);
+
+    // Write 2 * a + c into the bottom-right block of c, leaving the rest alone
+    T::view_gemm(
+        2.0,
+        &a.view(),
+        &b.view(),
+        1.0,
+        &mut c.submatrix_mut(1..3, 1..4),
+    );
+    assert_eq!(
+        c.get_data(),
+        vec![
+            vec![1.0, 1.0, 1.0, 1.0],
+            vec![1.0, 1.0, 3.0, 5.0],
+            vec![1.0, 7.0, 9.0, 11.0],
+        ]
+    );
+
+    // An output without unit column stride: the transpose of a block of c
+    let mut c = T::zeroes(3, 3);
+    let mut transposed = MatrixViewMut::new(c.as_mut_slice(), (3, 2), 1, 3).unwrap();
+    T::view_gemm(
+        1.0,
+        &a.view().transposed(),
+        &b.submatrix(0..2, 0..2),
+        0.0,
+        &mut transposed,
+    );
+    assert_eq!(
+        c.get_data(),
+        vec![
+            vec![0.0, 1.0, 2.0],
+            vec![3.0, 4.0, 5.0],
+            vec![0.0, 0.0, 0.0],
+        ]
+    );
+}
+
+pub fn test_view_gemm_dimension_mismatch<T: FlatMatrix>() {
+    let a = counting::<T>(2, 3);
+    let mut c = T::zeroes(3, 3);
+    T::view_gemm(
+        1.0,
+        &a.view(),
+        &a.view().transposed(),
+        0.0,
+        &mut c.view_mut(),
+    );
+}
+
+/// Shapes that leave partial micro-tiles and cache blocks on every side, checked against ndarray.
+pub fn test_view_gemm_edge_blocks<T: FlatMatrix>() {
+    for (m, k, n) in [(1, 1, 1), (5, 3, 9), (101, 300, 21), (3, 2, 4100)] {
+        let a = pseudo_random::<T>(m, k, 0.0);
+        let b_t = pseudo_random::<T>(n, k, 0.0);
+        let mut c = pseudo_random::<T>(m, n, 0.0);
+        let product = to_ndarray(&a).matrix_multiplication(&to_ndarray(&b_t).transpose());
+        let expected = product
+            .scalar_multiplication(2.0)
+            .matrix_addition(&to_ndarray(&c).scalar_multiplication(0.5));
+
+        assert_close(&a.matrix_multiplication(&b_t.transpose()), &product, 1e-10);
+        T::view_gemm(
+            2.0,
+            &a.view(),
+            &b_t.view().transposed(),
+            0.5,
+            &mut c.view_mut(),
+        );
+        assert_close(&c, &expected, 1e-10);
+    }
+}
+
+pub fn test_lu<T: LinearAlgebra>() {
+    let a = T::from_vec(vec![2.0, 1.0, 1.0, 4.0, 3.0, 3.0, 8.0, 7.0, 9.0], 3, 3);
+    let lu = a.lu().unwrap();
+
+    // Partial pivoting picks the largest entry of each column
+    assert_eq!(lu.permutation(), &[2, 0, 1]);
+    assert_close(
+        &lu.l(),
+        &T::from_vec(
+            vec![1.0, 0.0, 0.0, 0.25, 1.0, 0.0, 0.5, 2.0 / 3.0, 1.0],
+            3,
+            3,
+        ),
+        1e-12,
+    );
+    assert_close(
+        &lu.u(),
+        &T::from_vec(
+            vec![8.0, 7.0, 9.0, 0.0, -0.75, -1.25, 0.0, 0.0, -2.0 / 3.0],
+            3,
+            3,
+        ),
+        1e-12,
+    );
+    assert_close(
+        &lu.permutation_matrix().matrix_multiplication(&a),
+        &lu.l().matrix_multiplication(&lu.u()),
+        1e-12,
+    );
+    assert!(!lu.is_singular());
+
+    // Spans several blocks of the blocked factorization
+    let n = 21;
+    let a = pseudo_random::<T>(n, n, 0.0);
+    let lu = a.lu().unwrap();
+    assert_close(
+        &lu.permutation_matrix().matrix_multiplication(&a),
+        &lu.l().matrix_multiplication(&lu.u()),
+        1e-12,
+    );
+    let l = lu.l();
+    for i in 0..n {
+        for j in 0..i {
+            assert!(l.get(i, j).abs() <= 1.0);
+        }
+    }
+
+    assert_eq!(
+        T::zeroes(2, 3).lu().err(),
+        Some(MatrixError::NotSquare { shape: (2, 3) })
+    );
+}
+
+pub fn test_determinant<T: LinearAlgebra>() {
+    let a = T::from_vec(vec![2.0, 1.0, 1.0, 4.0, 3.0, 3.0, 8.0, 7.0, 9.0], 3, 3);
+    assert!((a.determinant().unwrap() - 4.0).abs() < 1e-12);
+
+    assert_eq!(T::identity(4).determinant(), Ok(1.0));
+    let swapped = T::from_vec(vec![0.0, 1.0, 1.0, 0.0], 2, 2);
+    assert_eq!(swapped.determinant(), Ok(-1.0));
+
+    let singular = T::from_vec(vec![1.0, 2.0, 2.0, 4.0], 2, 2);
+    assert_eq!(singular.determinant(), Ok(0.0));
+    assert!(singular.lu().unwrap().is_singular());
+
+    // det(A^T) = det(A)
+    let a = pseudo_random::<T>(12, 12, 2.0);
+    let det = a.determinant().unwrap();
+    assert!((a.transpose().determinant().unwrap() - det).abs() <= 1e-9 * det.abs());
+
+    assert!(T::zeroes(3, 2).determinant().is_err());
+}
+
+pub fn test_solve<T: LinearAlgebra>() {
+    let a = T::from_vec(vec![2.0, 1.0, -1.0, -3.0, -1.0, 2.0, -2.0, 1.0, 2.0], 3, 3);
+    let b = T::from_vec(vec![8.0, 1.0, -11.0, 0.0, -3.0, 0.0], 3, 2);
+    let x = a.solve(&b).unwrap();
+    assert_close(
+        &x,
+        &T::from_vec(vec![2.0, 4.0, 3.0, -2.0, -1.0, 5.0], 3, 2),
+        1e-12,
+    );
+
+    let n = 17;
+    let a = pseudo_random::<T>(n, n, 4.0);
+    let b = pseudo_random::<T>(n, 3, 0.0);
+    let x = a.solve(&b).unwrap();
+    assert_close(&a.matrix_multiplication(&x), &b, 1e-12);
+
+    assert_eq!(
+        T::from_vec(vec![1.0, 2.0, 2.0, 4.0], 2, 2)
+            .solve(&T::zeroes(2, 1))
+            .err(),
+        Some(MatrixError::Singular)
+    );
+    assert_eq!(
+        a.solve(&T::zeroes(n + 1, 1)).err(),
+        Some(MatrixError::DimensionMismatch {
+            left: (n, n),
+            right: (n + 1, 1)
+        })
+    );
+    assert!(T::zeroes(2, 3).solve(&T::zeroes(2, 1)).is_err());
+}
+
+pub fn test_inverse<T: LinearAlgebra>() {
+    let a = T::from_vec(vec![4.0, 7.0, 2.0, 6.0], 2, 2);
+    assert_close(
+        &a.inverse().unwrap(),
+        &T::from_vec(vec![0.6, -0.7, -0.2, 0.4], 2, 2),
+        1e-12,
+    );
+
+    let n = 19;
+    let a = pseudo_random::<T>(n, n, 3.0);
+    let inverse = a.inverse().unwrap();
+    assert_close(&a.matrix_multiplication(&inverse), &T::identity(n), 1e-12);
+    assert_close(&inverse.matrix_multiplication(&a), &T::identity(n), 1e-12);
+
+    let singular = T::from_vec(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0], 3, 3);
+    assert_eq!(singular.inverse().err(), Some(MatrixError::Singular));
+}
+
+pub fn test_qr<T: LinearAlgebra>() {
+    for (rows, cols) in [(7, 4), (4, 4), (3, 5)] {
+        let a = pseudo_random::<T>(rows, cols, 0.0);
+        let qr = a.qr();
+        let (q, r) = (to_ndarray(&qr.q()), to_ndarray(&qr.r()));
+        let k = rows.min(cols);
+
+        assert_eq!(q.shape(), (rows, k));
+        assert_eq!(r.shape(), (k, cols));
+        for i in 0..k {
+            for j in 0..i {
+                assert_eq!(r.get(i, j), 0.0);
+            }
+        }
+        assert_close(&q.matrix_multiplication(&r), &to_ndarray(&a), 1e-12);
+        assert_close(
+            &q.transpose().matrix_multiplication(&q),
+            &NdarrayMatrix::identity(k),
+            1e-12,
+        );
+    }
+
+    // A zero column needs no reflection
+    let a = T::from_vec(vec![0.0, 1.0, 0.0, 2.0, 0.0, 3.0], 3, 2);
+    let qr = a.qr();
+    assert_close(&qr.q().matrix_multiplication(&qr.r()), &a, 1e-12);
+}
+
+pub fn test_qr_with_pivoting<T: LinearAlgebra>() {
+    let a = pseudo_random::<T>(6, 5, 0.0);
+    let qr = a.qr_with_pivoting();
+    let (q, r) = (to_ndarray(&qr.q()), qr.r());
+
+    let mut sorted = qr.permutation().to_vec();
+    sorted.sort();
+    assert_eq!(sorted, (0..5).collect::<Vec<_>>());
+    for i in 1..5 {
+        assert!(r.get(i, i).abs() <= r.get(i - 1, i - 1).abs());
+    }
+    assert_close(
+        &q.matrix_multiplication(&to_ndarray(&r)),
+        &to_ndarray(&a).matrix_multiplication(&to_ndarray(&qr.permutation_matrix())),
+        1e-12,
+    );
+}
+
+pub fn test_rank<T: LinearAlgebra>() {
+    assert_eq!(T::identity(5).rank(), 5);
+    assert_eq!(T::zeroes(3, 4).rank(), 0);
+    assert_eq!(pseudo_random::<T>(6, 4, 0.0).rank(), 4);
+    assert_eq!(pseudo_random::<T>(3, 7, 0.0).rank(), 3);
+
+    // The third column is the sum of the first two
+    let a = T::from_vec(
+        vec![1.0, 2.0, 3.0, 4.0, 5.0, 9.0, 7.0, 8.0, 15.0, 1.0, 0.0, 1.0],
+        4,
+        3,
+    );
+    assert_eq!(a.rank(), 2);
+
+    // Outer product
+    let u = pseudo_random::<T>(5, 1, 0.0);
+    let v = pseudo_random::<T>(1, 4, 0.0);
+    assert_eq!(u.matrix_multiplication(&v).rank(), 1);
+}
+
+pub fn test_least_squares<T: LinearAlgebra>() {
+    // Points on y = 2 + 3x are fitted exactly
+    let xs = [0.0, 1.0, 2.0, 3.0, 4.0];
+    let a = T::from_vec(xs.iter().flat_map(|&x| [1.0, x]).collect(), 5, 2);
+    let b = T::from_vec(xs.iter().map(|&x| 2.0 + 3.0 * x).collect(), 5, 1);
+    let x = a.least_squares(&b).unwrap();
+    assert_close(&x, &T::from_vec(vec![2.0, 3.0], 2, 1), 1e-12);
+
+    // Otherwise the residual is orthogonal to the columns of A
+    let (rows, cols) = (12, 5);
+    let a = pseudo_random::<T>(rows, cols, 0.0);
+    let b = pseudo_random::<T>(rows, 2, 0.0);
+    let x = a.least_squares(&b).unwrap();
+    let (a, b, x) = (to_ndarray(&a), to_ndarray(&b), to_ndarray(&x));
+    let residual = a.matrix_multiplication(&x).matrix_subtraction(&b);
+    assert_close(
+        &a.transpose().matrix_multiplication(&residual),
+        &NdarrayMatrix::zeroes(cols, 2),
+        1e-12,
+    );
+
+    // Square systems agree with solve
+    let a = pseudo_random::<T>(6, 6, 3.0);
+    let b = pseudo_random::<T>(6, 1, 0.0);
+    assert_close(&a.least_squares(&b).unwrap(), &a.solve(&b).unwrap(), 1e-12);
+
+    let dependent = T::from_vec(vec![1.0, 2.0, 2.0, 4.0, 3.0, 6.0], 3, 2);
+    let b = T::from_vec(vec![1.0, 2.0, 4.0], 3, 1);
+    assert_eq!(
+        dependent.least_squares(&b).err(),
+        Some(MatrixError::RankDeficient {
+            rank: 1,
+            expected: 2
+        })
+    );
+    assert_eq!(
+        T::zeroes(2, 3).least_squares(&T::zeroes(2, 1)).err(),
+        Some(MatrixError::RankDeficient {
+            rank: 0,
+            expected: 3
+        })
+    );
+    assert!(a.least_squares(&T::zeroes(5, 1)).is_err());
+
+    // The pivoted factorization still gives a least-squares solution
+    let x = dependent.qr_with_pivoting().least_squares(&b).unwrap();
+    let residual = dependent.matrix_multiplication(&x).matrix_subtraction(&b);
+    assert_close(
+        &dependent.transpose().matrix_multiplication(&residual),
+        &T::zeroes(2, 1),
+        1e-12,
+    );
+    assert_eq!(x.row_iter().flatten().filter(|&&x| x == 0.0).count(), 1);
+}
+
+/// `X * X^T + n * I` for a pseudo-random `X`, which is symmetric positive definite.
+fn spd<T: LinearAlgebra>(n: usize) -> T {
+    let x = pseudo_random::<T>(n, n, 0.0);
+    x.matrix_multiplication(&x.transpose())
+        .matrix_addition(&T::identity(n).scalar_multiplication(n as f64))
+}
+
+pub fn test_cholesky<T: LinearAlgebra>() {
+    let a = T::from_vec(
+        vec![4.0, 12.0, -16.0, 12.0, 37.0, -43.0, -16.0, -43.0, 98.0],
+        3,
+        3,
+    );
+    let cholesky = a.cholesky().unwrap();
+    assert_close(
+        cholesky.l(),
+        &T::from_vec(vec![2.0, 0.0, 0.0, 6.0, 1.0, 0.0, -8.0, 5.0, 3.0], 3, 3),
+        1e-12,
+    );
+    assert!((cholesky.determinant() - 36.0).abs() < 1e-10);
+
+    // Large enough to take several blocks and threads
+    let n = 150;
+    let a = spd::<T>(n);
+    let l = a.cholesky().unwrap().into_l();
+    assert_close(&l.matrix_multiplication(&l.transpose()), &a, 1e-9);
+    for i in 0..n {
+        assert!(l.get(i, i) > 0.0);
+        for j in i + 1..n {
+            assert_eq!(l.get(i, j), 0.0);
+        }
+    }
+
+    assert_eq!(
+        T::from_vec(vec![1.0, 2.0, 2.0, 1.0], 2, 2).cholesky().err(),
+        Some(MatrixError::NotPositiveDefinite { pivot: 1 })
+    );
+    assert_eq!(
+        T::from_vec(vec![-1.0, 0.0, 0.0, 1.0], 2, 2)
+            .cholesky()
+            .err(),
+        Some(MatrixError::NotPositiveDefinite { pivot: 0 })
+    );
+    assert_eq!(
+        T::from_vec(vec![2.0, 1.0, 0.0, 2.0], 2, 2).cholesky().err(),
+        Some(MatrixError::NotSymmetric { index: (1, 0) })
+    );
+    assert_eq!(
+        T::zeroes(2, 3).cholesky().err(),
+        Some(MatrixError::NotSquare { shape: (2, 3) })
+    );
+}
+
+pub fn test_cholesky_solve<T: LinearAlgebra>() {
+    let a = T::from_vec(
+        vec![4.0, 12.0, -16.0, 12.0, 37.0, -43.0, -16.0, -43.0, 98.0],
+        3,
+        3,
+    );
+    let b = T::from_vec(vec![1.0, 2.0, 3.0], 3, 1);
+    assert_close(&a.cholesky_solve(&b).unwrap(), &a.solve(&b).unwrap(), 1e-9);
+
+    let n = 90;
+    let a = spd::<T>(n);
+    let b = pseudo_random::<T>(n, 3, 0.0);
+    let x = a.cholesky_solve(&b).unwrap();
+    assert_close(&a.matrix_multiplication(&x), &b, 1e-10);
+
+    assert_eq!(
+        a.cholesky_solve(&T::zeroes(n + 1, 1)).err(),
+        Some(MatrixError::DimensionMismatch {
+            left: (n, n),
+            right: (n + 1, 1)
+        })
+    );
+}
+
+pub fn test_log_determinant<T: LinearAlgebra>() {
+    let a = T::from_vec(
+        vec![4.0, 12.0, -16.0, 12.0, 37.0, -43.0, -16.0, -43.0, 98.0],
+        3,
+        3,
+    );
+    assert!((a.log_determinant().unwrap() - 36f64.ln()).abs() < 1e-12);
+
+    let a = spd::<T>(20);
+    let expected = a.determinant().unwrap().ln();
+    assert!((a.log_determinant().unwrap() - expected).abs() < 1e-9 * expected.abs());
+
+    // The determinant itself overflows here
+    let a = T::identity(400).scalar_multiplication(1e3);
+    assert!(a.determinant().unwrap().is_infinite());
+    let expected = 400.0 * 1e3f64.ln();
+    assert!((a.log_determinant().unwrap() - expected).abs() < 1e-9 * expected);
+
+    assert_eq!(
+        T::from_vec(vec![0.0, 0.0, 0.0, 1.0], 2, 2)
+            .log_determinant()
+            .err(),
+        Some(MatrixError::NotPositiveDefinite { pivot: 0 })
+    );
+}
+
+/// Checks `A = U * Σ * V^T` and that `U` and `V` have orthonormal columns.
+fn assert_svd<T: LinearAlgebra>(a: &T, svd: &Svd<f64, T>) {
+    let (u, vt, s) = (svd.u(), svd.vt(), svd.singular_values());
+    let mut sigma = T::zeroes(u.num_cols(), vt.num_rows());
+    for (i, &s_i) in s.iter().enumerate() {
+        sigma.set(i, i, s_i);
+    }
+    assert_close(
+        &u.matrix_multiplication(&sigma).matrix_multiplication(vt),
+        a,
+        1e-12,
+    );
+    assert_close(
+        &u.transpose().matrix_multiplication(u),
+        &T::identity(u.num_cols()),
+        1e-12,
+    );
+    assert_close(
+        &vt.matrix_multiplication(&vt.transpose()),
+        &T::identity(vt.num_rows()),
+        1e-12,
+    );
+    assert!(s.windows(2).all(|pair| pair[0] >= pair[1]));
+}
+
+pub fn test_svd<T: LinearAlgebra>() {
+    let a = T::from_vec(vec![3.0, 0.0, 4.0, 5.0], 2, 2);
+    let svd = a.svd();
+    assert_svd(&a, &svd);
+    let s = svd.singular_values();
+    assert!((s[0] - 45f64.sqrt()).abs() < 1e-12);
+    assert!((s[1] - 5f64.sqrt()).abs() < 1e-12);
+
+    for (rows, cols) in [(9, 4), (4, 9), (7, 7)] {
+        let a = pseudo_random::<T>(rows, cols, 0.0);
+        let k = rows.min(cols);
+
+        let full = a.svd();
+        assert_eq!(full.u().shape(), (rows, rows));
+        assert_eq!(full.vt().shape(), (cols, cols));
+        assert_eq!(full.singular_values().len(), k);
+        assert_svd(&a, &full);
+
+        let thin = a.thin_svd();
+        assert_eq!(thin.u().shape(), (rows, k));
+        assert_eq!(thin.vt().shape(), (k, cols));
+        assert_svd(&a, &thin);
+
+        // The squared singular values are the eigenvalues of A^T * A, whose sum is its trace
+        let trace = a.row_iter().flatten().map(|x| x * x).sum::<f64>();
+        let squares = a.singular_values().iter().map(|s| s * s).sum::<f64>();
+        assert!((trace - squares).abs() < 1e-12 * trace);
+    }
+
+    // Rank deficient matrices still get orthonormal singular vectors
+    let a = T::from_vec(vec![1.0, 2.0, 2.0, 4.0, 3.0, 6.0], 3, 2);
+    let svd = a.svd();
+    assert_svd(&a, &svd);
+    assert_eq!(svd.rank(), 1);
+    let zero = T::zeroes(3, 2);
+    assert_svd(&zero, &zero.thin_svd());
+    assert_eq!(zero.singular_values(), vec![0.0, 0.0]);
+}
+
+pub fn test_pinv<T: LinearAlgebra>() {
+    let a = pseudo_random::<T>(6, 6, 3.0);
+    assert_close(&a.pinv(), &a.inverse().unwrap(), 1e-12);
+
+    let a = T::from_vec(vec![1.0, 2.0, 2.0, 4.0], 2, 2);
+    assert_close(
+        &a.pinv(),
+        &T::from_vec(vec![0.04, 0.08, 0.08, 0.16], 2, 2),
+        1e-12,
+    );
+
+    // The Penrose conditions, for both orientations
+    for (rows, cols) in [(8, 3), (3, 8)] {
+        let a = pseudo_random::<T>(rows, cols, 0.0);
+        let pinv = a.pinv();
+        assert_eq!(pinv.shape(), (cols, rows));
+        assert_close(
+            &a.matrix_multiplication(&pinv).matrix_multiplication(&a),
+            &a,
+            1e-12,
+        );
+        assert_close(
+            &pinv.matrix_multiplication(&a).matrix_multiplication(&pinv),
+            &pinv,
+            1e-12,
+        );
+        let projection = a.matrix_multiplication(&pinv);
+        assert_close(&projection, &projection.transpose(), 1e-12);
+    }
+
+    // Full column rank, so it agrees with least squares
+    let a = pseudo_random::<T>(10, 4, 0.0);
+    let b = pseudo_random::<T>(10, 1, 0.0);
+    assert_close(
+        &a.pinv().matrix_multiplication(&b),
+        &a.least_squares(&b).unwrap(),
+        1e-12,
+    );
+}
+
+pub fn test_norm_2_and_condition_number<T: LinearAlgebra>() {
+    let a = T::from_vec(vec![3.0, 0.0, 4.0, 5.0], 2, 2);
+    assert!((a.norm_2() - 45f64.sqrt()).abs() < 1e-12);
+    assert!((a.condition_number() - 3.0).abs() < 1e-12);
+
+    let a = T::from_vec(vec![1.0, 2.0, 3.0, 4.0], 2, 2);
+    assert!((a.norm_2() - 5.464985704219043).abs() < 1e-12);
+
+    let a = T::identity(5).scalar_multiplication(-2.0);
+    assert!((a.norm_2() - 2.0).abs() < 1e-12);
+    assert!((a.condition_number() - 1.0).abs() < 1e-12);
+
+    assert!(
+        T::from_vec(vec![1.0, 2.0, 2.0, 4.0], 2, 2)
+            .condition_number()
+            .is_infinite()
+    );
+}
+
+pub fn test_triangular_solve<T: LinearAlgebra>() {
+    let upper = UpperTriangular::new(T::from_vec(
+        vec![2.0, 1.0, -1.0, 9.0, 3.0, 2.0, 9.0, 9.0, 4.0],
+        3,
+        3,
+    ))
+    .unwrap();
+    // Only the upper triangle is kept
+    assert_close(
+        upper.matrix(),
+        &T::from_vec(vec![2.0, 1.0, -1.0, 0.0, 3.0, 2.0, 0.0, 0.0, 4.0], 3, 3),
+        0.0,
+    );
+    assert_eq!(upper.determinant(), 24.0);
+    let x = upper
+        .solve_vector(&Vector::new(vec![3.0, 7.0, 8.0]))
+        .unwrap();
+    assert_eq!(x, Vector::new(vec![2.0, 1.0, 2.0]));
+
+    let lower = upper.transpose();
+    let x = lower
+        .solve_vector(&Vector::new(vec![4.0, 7.0, 9.0]))
+        .unwrap();
+    for (x_i, expected) in x.iter().zip([2.0, 5.0 / 3.0, 23.0 / 12.0]) {
+        assert!((x_i - expected).abs() < 1e-15);
+    }
+
+    // Several right-hand sides, compared with the product they came from
+    let n = 23;
+    let a = pseudo_random::<T>(n, n, 4.0);
+    let b = pseudo_random::<T>(n, 4, 0.0);
+    for triangle in [Triangle::Upper, Triangle::Lower] {
+        let x = a.solve_triangular(triangle, &b).unwrap();
+        let t = match triangle {
+            Triangle::Upper => UpperTriangular::new(T::from_view(&a.view()))
+                .unwrap()
+                .into_inner(),
+            Triangle::Lower => LowerTriangular::new(T::from_view(&a.view()))
+                .unwrap()
+                .into_inner(),
+        };
+        assert_close(&t.matrix_multiplication(&x), &b, 1e-12);
+    }
+    let lower = LowerTriangular::new(T::from_view(&a.view())).unwrap();
+    assert_close(
+        &lower.solve(&b).unwrap(),
+        &a.solve_triangular(Triangle::Lower, &b).unwrap(),
+        0.0,
+    );
+
+    assert_eq!(
+        UpperTriangular::new(T::from_vec(vec![1.0, 2.0, 0.0, 0.0], 2, 2))
+            .unwrap()
+            .solve_vector(&Vector::new(vec![1.0, 1.0])),
+        Err(MatrixError::Singular)
+    );
+    assert_eq!(
+        upper.solve_vector(&Vector::new(vec![1.0, 2.0])),
+        Err(MatrixError::DimensionMismatch {
+            left: (3, 3),
+            right: (2, 1)
+        })
+    );
+    assert_eq!(
+        UpperTriangular::new(T::zeroes(2, 3)).err(),
+        Some(MatrixError::NotSquare { shape: (2, 3) })
+    );
+}
+
+pub fn test_triangular_multiplication<T: LinearAlgebra>() {
+    let n = 17;
+    let a = pseudo_random::<T>(n, n, 0.0);
+    let b = pseudo_random::<T>(n, 5, 0.0);
+    let c = pseudo_random::<T>(n, n, 1.0);
+
+    let upper = UpperTriangular::new(T::from_view(&a.view())).unwrap();
+    let other = UpperTriangular::new(T::from_view(&c.view())).unwrap();
+    assert_close(
+        &upper.matrix_multiplication(&b),
+        &upper.matrix().matrix_multiplication(&b),
+        1e-12,
+    );
+    let product = upper.triangular_multiplication(&other);
+    assert_close(
+        product.matrix(),
+        &upper.matrix().matrix_multiplication(other.matrix()),
+        1e-12,
+    );
+
+    let lower = LowerTriangular::new(T::from_view(&a.view())).unwrap();
+    let other = other.transpose();
+    assert_close(
+        &lower.matrix_multiplication(&b),
+        &lower.matrix().matrix_multiplication(&b),
+        1e-12,
+    );
+    assert_close(
+        lower.triangular_multiplication(&other).matrix(),
+        &lower.matrix().matrix_multiplication(other.matrix()),
+        1e-12,
+    );
+
+    assert_eq!(
+        lower.try_matrix_multiplication(&T::zeroes(n + 1, 2)).err(),
+        Some(MatrixError::DimensionMismatch {
+            left: (n, n),
+            right: (n + 1, 2)
+        })
+    );
+}
+
+pub fn test_level1_reductions<T: Level1>() {
+    let a = T::from_vec(vec![1.0, -2.0, 3.0, -4.0, 5.0, -6.0], 2, 3);
+    let b = T::from_vec(vec![1.0, 1.0, 1.0, 1.0, 1.0, 2.0], 2, 3);
+    assert_eq!(a.dot(&b), -9.0);
+    assert_eq!(a.asum(), 21.0);
+    assert_eq!(a.iamax(), (1, 2));
+    assert!((a.nrm2() - 91.0_f64.sqrt()).abs() < 1e-14);
+    assert_eq!(
+        a.try_dot(&T::zeroes(3, 2)).err(),
+        Some(MatrixError::DimensionMismatch {
+            left: (2, 3),
+            right: (3, 2)
+        })
+    );
+
+    // Long enough to use every lane, with a tail
+    let n = 1003;
+    let a = T::from_vec((0..n).map(|i| (i % 7) as f64 - 3.0).collect(), 1, n);
+    let b = T::from_vec((0..n).map(|i| (i % 5) as f64 * 0.5).collect(), 1, n);
+    let expected = (0..n)
+        .map(|i| ((i % 7) as f64 - 3.0) * (i % 5) as f64 * 0.5)
+        .sum::<f64>();
+    assert!((a.dot(&b) - expected).abs() < 1e-10);
+    assert_eq!(a.iamax(), (0, 0));
+
+    // No overflow or underflow when squaring
+    assert!((T::from_vec(vec![3e200, 4e200], 1, 2).nrm2() / 5e200 - 1.0).abs() < 1e-15);
+    assert!((T::from_vec(vec![3e-200, 4e-200], 1, 2).nrm2() / 5e-200 - 1.0).abs() < 1e-15);
+    assert_eq!(T::zeroes(2, 2).nrm2(), 0.0);
+}
+
+pub fn test_level1_updates<T: Level1>() {
+    let mut y = T::from_vec(vec![1.0, 2.0, 3.0, 4.0], 2, 2);
+    let x = T::from_vec(vec![1.0, 1.0, -1.0, 0.5], 2, 2);
+    y.axpy(2.0, &x);
+    assert_eq!(y.as_slice().unwrap(), [3.0, 4.0, 1.0, 5.0]);
+    y.scal(-0.5);
+    assert_eq!(y.as_slice().unwrap(), [-1.5, -2.0, -0.5, -2.5]);
+    assert_eq!(
+        y.try_axpy(1.0, &T::zeroes(1, 4)).err(),
+        Some(MatrixError::DimensionMismatch {
+            left: (2, 2),
+            right: (1, 4)
+        })
+    );
+
+    // Rotating the pairs (3, 4) and (-4, 3) onto the first axis
+    let mut x = T::from_vec(vec![3.0, -4.0], 1, 2);
+    let mut y = T::from_vec(vec![4.0, 3.0], 1, 2);
+    let (givens, r) = Givens::new(3.0_f64, 4.0);
+    assert!((r - 5.0).abs() < 1e-15);
+    x.rot(&mut y, givens);
+    assert_close(&x, &T::from_vec(vec![5.0, 0.0], 1, 2), 1e-15);
+    assert_close(&y, &T::from_vec(vec![0.0, 5.0], 1, 2), 1e-15);
+    assert!(x.try_rot(&mut T::zeroes(2, 1), givens).is_err());
+}
@@ -1,5 +1,8 @@
 use rmatrix::matrices::multithread_matrix::*;
+mod flat_functions;
 mod test_functions;
+use flat_functions::*;
+use rmatrix::Complex;
 use test_functions::*;
 
 #[test]
@@ -41,3 +44,312 @@ fn multithread_matrix_test_matrix_multiplication() {
 fn multithread_matrix_test_scalar_multiplication() {
     test_scalar_multiplication::<MultithreadMatrix>();
 }
+
+#[test]
+fn multithread_matrix_test_chained_operations() {
+    test_chained_operations::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_try_new() {
+    test_try_new::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_new_invalid_data() {
+    test_new_invalid_data::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_try_zeroes_and_identity() {
+    test_try_zeroes_and_identity::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_try_matrix_addition() {
+    test_try_matrix_addition::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_try_matrix_subtraction() {
+    test_try_matrix_subtraction::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_try_matrix_multiplication() {
+    test_try_matrix_multiplication::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_f32_elements() {
+    test_element_type::<f32, MultithreadMatrix<f32>>();
+}
+
+#[test]
+fn multithread_matrix_test_i32_elements() {
+    test_element_type::<i32, MultithreadMatrix<i32>>();
+}
+
+#[test]
+fn multithread_matrix_test_complex_elements() {
+    test_complex_elements::<MultithreadMatrix<Complex<f64>>>();
+}
+
+#[test]
+fn multithread_matrix_test_conjugate_transpose() {
+    test_conjugate_transpose::<MultithreadMatrix<Complex<f64>>>();
+}
+
+#[test]
+fn multithread_matrix_test_operators() {
+    test_operators::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_operators_dimension_mismatch() {
+    test_operators_dimension_mismatch::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_addition_subtraction_dimension_mismatch() {
+    test_addition_subtraction_dimension_mismatch::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_assign_dimension_mismatch() {
+    test_assign_dimension_mismatch::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_element_access() {
+    test_element_access::<MultithreadMatrix>();
+}
+
+#[test]
+#[should_panic(expected = "dimension mismatch")]
+fn multithread_matrix_test_gemm_dimension_mismatch() {
+    test_gemm_dimension_mismatch::<MultithreadMatrix>();
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn multithread_matrix_test_index_out_of_bounds() {
+    test_index_out_of_bounds::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_data_access() {
+    test_data_access::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_transpose() {
+    test_transpose::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_transposed_view() {
+    test_transposed_view::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_from_vec() {
+    test_from_vec::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_submatrix() {
+    test_submatrix::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_invalid_views() {
+    test_invalid_views::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_view_arithmetic() {
+    test_view_arithmetic::<MultithreadMatrix>();
+}
+
+#[test]
+#[should_panic(expected = "dimension mismatch")]
+fn multithread_matrix_test_view_dimension_mismatch() {
+    test_view_dimension_mismatch::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_submatrix_mut() {
+    test_submatrix_mut::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_in_place_arithmetic() {
+    test_in_place_arithmetic::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_gemm() {
+    test_gemm::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_view_gemm() {
+    test_view_gemm::<MultithreadMatrix>();
+}
+
+#[test]
+#[should_panic(expected = "dimension mismatch")]
+fn multithread_matrix_test_view_gemm_dimension_mismatch() {
+    test_view_gemm_dimension_mismatch::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_view_gemm_edge_blocks() {
+    test_view_gemm_edge_blocks::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_lu() {
+    test_lu::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_determinant() {
+    test_determinant::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_solve() {
+    test_solve::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_inverse() {
+    test_inverse::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_qr() {
+    test_qr::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_qr_with_pivoting() {
+    test_qr_with_pivoting::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_rank() {
+    test_rank::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_least_squares() {
+    test_least_squares::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_cholesky() {
+    test_cholesky::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_cholesky_solve() {
+    test_cholesky_solve::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_log_determinant() {
+    test_log_determinant::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_svd() {
+    test_svd::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_pinv() {
+    test_pinv::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_norm_2_and_condition_number() {
+    test_norm_2_and_condition_number::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_symmetric_eigen() {
+    test_symmetric_eigen::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_eigenvalues() {
+    test_eigenvalues::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_triangular_solve() {
+    test_triangular_solve::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_triangular_multiplication() {
+    test_triangular_multiplication::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_matvec() {
+    test_matvec::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_vecmat() {
+    test_vecmat::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_level1_reductions() {
+    test_level1_reductions::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_level1_updates() {
+    test_level1_updates::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_trace_and_sums() {
+    test_trace_and_sums::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_norms() {
+    test_norms::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_min_max() {
+    test_min_max::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_row_col_statistics() {
+    test_row_col_statistics::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_hadamard_and_division() {
+    test_hadamard_and_division::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_map_and_zip_map() {
+    test_map_and_zip_map::<MultithreadMatrix>();
+}
+
+#[test]
+fn multithread_matrix_test_unary_functions() {
+    test_unary_functions::<MultithreadMatrix>();
+}
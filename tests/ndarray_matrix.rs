@@ -1,3 +1,4 @@
+use rmatrix::Complex;
 use rmatrix::matrices::ndarray_matrix::*;
 use test_functions::*;
 mod test_functions;
@@ -41,3 +42,175 @@ fn ndarray_matrix_test_matrix_multiplication() {
 fn ndarray_matrix_test_scalar_multiplication() {
     test_scalar_multiplication::<NdarrayMatrix>();
 }
+
+#[test]
+fn ndarray_matrix_test_chained_operations() {
+    test_chained_operations::<NdarrayMatrix>();
+}
+
+#[test]
+fn ndarray_matrix_test_try_new() {
+    test_try_new::<NdarrayMatrix>();
+}
+
+#[test]
+fn ndarray_matrix_test_new_invalid_data() {
+    test_new_invalid_data::<NdarrayMatrix>();
+}
+
+#[test]
+fn ndarray_matrix_test_try_zeroes_and_identity() {
+    test_try_zeroes_and_identity::<NdarrayMatrix>();
+}
+
+#[test]
+fn ndarray_matrix_test_try_matrix_addition() {
+    test_try_matrix_addition::<NdarrayMatrix>();
+}
+
+#[test]
+fn ndarray_matrix_test_try_matrix_subtraction() {
+    test_try_matrix_subtraction::<NdarrayMatrix>();
+}
+
+#[test]
+fn ndarray_matrix_test_try_matrix_multiplication() {
+    test_try_matrix_multiplication::<NdarrayMatrix>();
+}
+
+#[test]
+fn ndarray_matrix_test_f32_elements() {
+    test_element_type::<f32, NdarrayMatrix<f32>>();
+}
+
+#[test]
+fn ndarray_matrix_test_i32_elements() {
+    test_element_type::<i32, NdarrayMatrix<i32>>();
+}
+
+#[test]
+fn ndarray_matrix_test_complex_elements() {
+    test_complex_elements::<NdarrayMatrix<Complex<f64>>>();
+}
+
+#[test]
+fn ndarray_matrix_test_conjugate_transpose() {
+    test_conjugate_transpose::<NdarrayMatrix<Complex<f64>>>();
+}
+
+#[test]
+fn ndarray_matrix_test_operators() {
+    test_operators::<NdarrayMatrix>();
+}
+
+#[test]
+fn ndarray_matrix_test_operators_dimension_mismatch() {
+    test_operators_dimension_mismatch::<NdarrayMatrix>();
+}
+
+#[test]
+fn ndarray_matrix_test_addition_subtraction_dimension_mismatch() {
+    test_addition_subtraction_dimension_mismatch::<NdarrayMatrix>();
+}
+
+#[test]
+fn ndarray_matrix_test_assign_dimension_mismatch() {
+    test_assign_dimension_mismatch::<NdarrayMatrix>();
+}
+
+#[test]
+#[should_panic(expected = "dimension mismatch")]
+fn ndarray_matrix_test_gemm_dimension_mismatch() {
+    test_gemm_dimension_mismatch::<NdarrayMatrix>();
+}
+
+#[test]
+fn ndarray_matrix_test_element_access() {
+    test_element_access::<NdarrayMatrix>();
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn ndarray_matrix_test_index_out_of_bounds() {
+    test_index_out_of_bounds::<NdarrayMatrix>();
+}
+
+#[test]
+fn ndarray_matrix_test_data_access() {
+    test_data_access::<NdarrayMatrix>();
+}
+
+#[test]
+fn ndarray_matrix_test_transpose() {
+    test_transpose::<NdarrayMatrix>();
+}
+
+#[test]
+fn ndarray_matrix_test_transposed_view() {
+    test_transposed_view::<NdarrayMatrix>();
+}
+
+#[test]
+fn ndarray_matrix_test_in_place_arithmetic() {
+    test_in_place_arithmetic::<NdarrayMatrix>();
+}
+
+#[test]
+fn ndarray_matrix_test_gemm() {
+    test_gemm::<NdarrayMatrix>();
+}
+
+#[test]
+fn ndarray_matrix_test_symmetric_eigen() {
+    test_symmetric_eigen::<NdarrayMatrix>();
+}
+
+#[test]
+fn ndarray_matrix_test_eigenvalues() {
+    test_eigenvalues::<NdarrayMatrix>();
+}
+
+#[test]
+fn ndarray_matrix_test_matvec() {
+    test_matvec::<NdarrayMatrix>();
+}
+
+#[test]
+fn ndarray_matrix_test_vecmat() {
+    test_vecmat::<NdarrayMatrix>();
+}
+
+#[test]
+fn ndarray_matrix_test_trace_and_sums() {
+    test_trace_and_sums::<NdarrayMatrix>();
+}
+
+#[test]
+fn ndarray_matrix_test_norms() {
+    test_norms::<NdarrayMatrix>();
+}
+
+#[test]
+fn ndarray_matrix_test_min_max() {
+    test_min_max::<NdarrayMatrix>();
+}
+
+#[test]
+fn ndarray_matrix_test_row_col_statistics() {
+    test_row_col_statistics::<NdarrayMatrix>();
+}
+
+#[test]
+fn ndarray_matrix_test_hadamard_and_division() {
+    test_hadamard_and_division::<NdarrayMatrix>();
+}
+
+#[test]
+fn ndarray_matrix_test_map_and_zip_map() {
+    test_map_and_zip_map::<NdarrayMatrix>();
+}
+
+#[test]
+fn ndarray_matrix_test_unary_functions() {
+    test_unary_functions::<NdarrayMatrix>();
+}
@@ -1,5 +1,8 @@
 use rmatrix::matrices::one_d_vec_matrix::*;
+mod flat_functions;
 mod test_functions;
+use flat_functions::*;
+use rmatrix::Complex;
 use test_functions::*;
 #[test]
 fn one_d_vec_matrix_test_new() {
@@ -40,3 +43,312 @@ fn one_d_vec_matrix_test_matrix_multiplication() {
 fn one_d_vec_matrix_test_scalar_multiplication() {
     test_scalar_multiplication::<OneDVecMatrix>();
 }
+
+#[test]
+fn one_d_vec_matrix_test_chained_operations() {
+    test_chained_operations::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_try_new() {
+    test_try_new::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_new_invalid_data() {
+    test_new_invalid_data::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_try_zeroes_and_identity() {
+    test_try_zeroes_and_identity::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_try_matrix_addition() {
+    test_try_matrix_addition::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_try_matrix_subtraction() {
+    test_try_matrix_subtraction::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_try_matrix_multiplication() {
+    test_try_matrix_multiplication::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_f32_elements() {
+    test_element_type::<f32, OneDVecMatrix<f32>>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_i32_elements() {
+    test_element_type::<i32, OneDVecMatrix<i32>>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_complex_elements() {
+    test_complex_elements::<OneDVecMatrix<Complex<f64>>>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_conjugate_transpose() {
+    test_conjugate_transpose::<OneDVecMatrix<Complex<f64>>>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_operators() {
+    test_operators::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_operators_dimension_mismatch() {
+    test_operators_dimension_mismatch::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_addition_subtraction_dimension_mismatch() {
+    test_addition_subtraction_dimension_mismatch::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_assign_dimension_mismatch() {
+    test_assign_dimension_mismatch::<OneDVecMatrix>();
+}
+
+#[test]
+#[should_panic(expected = "dimension mismatch")]
+fn one_d_vec_matrix_test_gemm_dimension_mismatch() {
+    test_gemm_dimension_mismatch::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_element_access() {
+    test_element_access::<OneDVecMatrix>();
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn one_d_vec_matrix_test_index_out_of_bounds() {
+    test_index_out_of_bounds::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_data_access() {
+    test_data_access::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_transpose() {
+    test_transpose::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_transposed_view() {
+    test_transposed_view::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_from_vec() {
+    test_from_vec::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_submatrix() {
+    test_submatrix::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_invalid_views() {
+    test_invalid_views::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_view_arithmetic() {
+    test_view_arithmetic::<OneDVecMatrix>();
+}
+
+#[test]
+#[should_panic(expected = "dimension mismatch")]
+fn one_d_vec_matrix_test_view_dimension_mismatch() {
+    test_view_dimension_mismatch::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_submatrix_mut() {
+    test_submatrix_mut::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_in_place_arithmetic() {
+    test_in_place_arithmetic::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_gemm() {
+    test_gemm::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_view_gemm() {
+    test_view_gemm::<OneDVecMatrix>();
+}
+
+#[test]
+#[should_panic(expected = "dimension mismatch")]
+fn one_d_vec_matrix_test_view_gemm_dimension_mismatch() {
+    test_view_gemm_dimension_mismatch::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_view_gemm_edge_blocks() {
+    test_view_gemm_edge_blocks::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_lu() {
+    test_lu::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_determinant() {
+    test_determinant::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_solve() {
+    test_solve::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_inverse() {
+    test_inverse::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_qr() {
+    test_qr::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_qr_with_pivoting() {
+    test_qr_with_pivoting::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_rank() {
+    test_rank::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_least_squares() {
+    test_least_squares::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_cholesky() {
+    test_cholesky::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_cholesky_solve() {
+    test_cholesky_solve::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_log_determinant() {
+    test_log_determinant::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_svd() {
+    test_svd::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_pinv() {
+    test_pinv::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_norm_2_and_condition_number() {
+    test_norm_2_and_condition_number::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_symmetric_eigen() {
+    test_symmetric_eigen::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_eigenvalues() {
+    test_eigenvalues::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_triangular_solve() {
+    test_triangular_solve::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_triangular_multiplication() {
+    test_triangular_multiplication::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_matvec() {
+    test_matvec::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_vecmat() {
+    test_vecmat::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_level1_reductions() {
+    test_level1_reductions::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_level1_updates() {
+    test_level1_updates::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_trace_and_sums() {
+    test_trace_and_sums::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_norms() {
+    test_norms::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_min_max() {
+    test_min_max::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_row_col_statistics() {
+    test_row_col_statistics::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_hadamard_and_division() {
+    test_hadamard_and_division::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_map_and_zip_map() {
+    test_map_and_zip_map::<OneDVecMatrix>();
+}
+
+#[test]
+fn one_d_vec_matrix_test_unary_functions() {
+    test_unary_functions::<OneDVecMatrix>();
+}
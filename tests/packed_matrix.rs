@@ -0,0 +1,355 @@
+use rmatrix::matrices::packed_matrix::*;
+mod flat_functions;
+mod test_functions;
+use flat_functions::*;
+use rmatrix::Complex;
+use test_functions::*;
+
+#[test]
+fn packed_matrix_test_new() {
+    test_new::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_zeroes() {
+    test_zeroes::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_identity() {
+    test_identity::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_matrix_multiplication_identity() {
+    test_matrix_multiplication_identity::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_matrix_addition() {
+    test_matrix_addition::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_matrix_subtraction() {
+    test_matrix_subtraction::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_matrix_multiplication() {
+    test_matrix_multiplication::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_scalar_multiplication() {
+    test_scalar_multiplication::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_chained_operations() {
+    test_chained_operations::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_try_new() {
+    test_try_new::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_new_invalid_data() {
+    test_new_invalid_data::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_try_zeroes_and_identity() {
+    test_try_zeroes_and_identity::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_try_matrix_addition() {
+    test_try_matrix_addition::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_try_matrix_subtraction() {
+    test_try_matrix_subtraction::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_try_matrix_multiplication() {
+    test_try_matrix_multiplication::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_f32_elements() {
+    test_element_type::<f32, PackedMatrix<f32>>();
+}
+
+#[test]
+fn packed_matrix_test_i32_elements() {
+    test_element_type::<i32, PackedMatrix<i32>>();
+}
+
+#[test]
+fn packed_matrix_test_complex_elements() {
+    test_complex_elements::<PackedMatrix<Complex<f64>>>();
+}
+
+#[test]
+fn packed_matrix_test_conjugate_transpose() {
+    test_conjugate_transpose::<PackedMatrix<Complex<f64>>>();
+}
+
+#[test]
+fn packed_matrix_test_operators() {
+    test_operators::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_operators_dimension_mismatch() {
+    test_operators_dimension_mismatch::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_addition_subtraction_dimension_mismatch() {
+    test_addition_subtraction_dimension_mismatch::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_assign_dimension_mismatch() {
+    test_assign_dimension_mismatch::<PackedMatrix>();
+}
+
+#[test]
+#[should_panic(expected = "dimension mismatch")]
+fn packed_matrix_test_gemm_dimension_mismatch() {
+    test_gemm_dimension_mismatch::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_element_access() {
+    test_element_access::<PackedMatrix>();
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn packed_matrix_test_index_out_of_bounds() {
+    test_index_out_of_bounds::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_data_access() {
+    test_data_access::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_transpose() {
+    test_transpose::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_transposed_view() {
+    test_transposed_view::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_from_vec() {
+    test_from_vec::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_submatrix() {
+    test_submatrix::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_invalid_views() {
+    test_invalid_views::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_view_arithmetic() {
+    test_view_arithmetic::<PackedMatrix>();
+}
+
+#[test]
+#[should_panic(expected = "dimension mismatch")]
+fn packed_matrix_test_view_dimension_mismatch() {
+    test_view_dimension_mismatch::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_submatrix_mut() {
+    test_submatrix_mut::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_in_place_arithmetic() {
+    test_in_place_arithmetic::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_gemm() {
+    test_gemm::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_view_gemm() {
+    test_view_gemm::<PackedMatrix>();
+}
+
+#[test]
+#[should_panic(expected = "dimension mismatch")]
+fn packed_matrix_test_view_gemm_dimension_mismatch() {
+    test_view_gemm_dimension_mismatch::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_view_gemm_edge_blocks() {
+    test_view_gemm_edge_blocks::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_lu() {
+    test_lu::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_determinant() {
+    test_determinant::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_solve() {
+    test_solve::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_inverse() {
+    test_inverse::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_qr() {
+    test_qr::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_qr_with_pivoting() {
+    test_qr_with_pivoting::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_rank() {
+    test_rank::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_least_squares() {
+    test_least_squares::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_cholesky() {
+    test_cholesky::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_cholesky_solve() {
+    test_cholesky_solve::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_log_determinant() {
+    test_log_determinant::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_svd() {
+    test_svd::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_pinv() {
+    test_pinv::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_norm_2_and_condition_number() {
+    test_norm_2_and_condition_number::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_symmetric_eigen() {
+    test_symmetric_eigen::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_eigenvalues() {
+    test_eigenvalues::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_triangular_solve() {
+    test_triangular_solve::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_triangular_multiplication() {
+    test_triangular_multiplication::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_matvec() {
+    test_matvec::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_vecmat() {
+    test_vecmat::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_level1_reductions() {
+    test_level1_reductions::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_level1_updates() {
+    test_level1_updates::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_trace_and_sums() {
+    test_trace_and_sums::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_norms() {
+    test_norms::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_min_max() {
+    test_min_max::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_row_col_statistics() {
+    test_row_col_statistics::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_hadamard_and_division() {
+    test_hadamard_and_division::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_map_and_zip_map() {
+    test_map_and_zip_map::<PackedMatrix>();
+}
+
+#[test]
+fn packed_matrix_test_unary_functions() {
+    test_unary_functions::<PackedMatrix>();
+}
@@ -0,0 +1,124 @@
+use rmatrix::linalg::LinearAlgebra;
+use rmatrix::matrices::multithread_matrix::MultithreadMatrix;
+use rmatrix::matrices::one_d_vec_matrix::OneDVecMatrix;
+use rmatrix::parallel::{ParallelConfig, config};
+use rmatrix::{Matrix, Vector};
+
+mod common;
+use common::{assert_close, entries};
+
+/// Odd shapes, so the chunks and gemm blocks never divide evenly.
+fn check_against_one_d_vec() {
+    let (a, b, c) = (entries(37, 19, 0), entries(37, 19, 5), entries(19, 23, 3));
+    let (m_a, m_b, m_c) = (
+        MultithreadMatrix::new(a.clone()),
+        MultithreadMatrix::new(b.clone()),
+        MultithreadMatrix::new(c.clone()),
+    );
+    let (o_a, o_b, o_c) = (
+        OneDVecMatrix::new(a),
+        OneDVecMatrix::new(b),
+        OneDVecMatrix::new(c),
+    );
+
+    assert_close(
+        m_a.matrix_addition(&m_b).get_data(),
+        o_a.matrix_addition(&o_b).get_data(),
+    );
+    assert_close(
+        m_a.matrix_subtraction(&m_b).get_data(),
+        o_a.matrix_subtraction(&o_b).get_data(),
+    );
+    assert_close(
+        m_a.scalar_multiplication(1.5).get_data(),
+        o_a.scalar_multiplication(1.5).get_data(),
+    );
+    assert_close(
+        m_a.hadamard_product(&m_b).get_data(),
+        o_a.hadamard_product(&o_b).get_data(),
+    );
+    assert_close(
+        m_a.zip_map(&m_b, |x, y| x * x - y).get_data(),
+        o_a.zip_map(&o_b, |x, y| x * x - y).get_data(),
+    );
+    assert_close(
+        m_a.map(|x| 2.0 * x + 1.0).get_data(),
+        o_a.map(|x| 2.0 * x + 1.0).get_data(),
+    );
+    assert_close(m_a.transpose().get_data(), o_a.transpose().get_data());
+    assert_close(
+        m_a.matrix_multiplication(&m_c).get_data(),
+        o_a.matrix_multiplication(&o_c).get_data(),
+    );
+
+    let mut m_d = MultithreadMatrix::new(m_a.get_data());
+    let mut o_d = OneDVecMatrix::new(o_a.get_data());
+    m_d.add_assign(&m_b);
+    m_d.scale_in_place(0.5);
+    o_d.add_assign(&o_b);
+    o_d.scale_in_place(0.5);
+    assert_close(m_d.get_data(), o_d.get_data());
+
+    let x = Vector::new((0..19).map(|i| i as f64 - 9.0).collect());
+    assert_close(
+        vec![m_a.matvec(&x).into_vec()],
+        vec![o_a.matvec(&x).into_vec()],
+    );
+    assert!((m_a.sum() - o_a.sum()).abs() < 1e-9);
+    assert_close(
+        vec![m_a.col_sums().into_vec()],
+        vec![o_a.col_sums().into_vec()],
+    );
+
+    // a^T a + n I is symmetric positive definite
+    let spd = |a: &OneDVecMatrix| {
+        let mut spd = a.transpose().matrix_multiplication(a);
+        for i in 0..spd.num_rows() {
+            *spd.get_mut(i, i) += spd.num_rows() as f64;
+        }
+        spd.get_data()
+    };
+    let spd = spd(&o_a);
+    assert_close(
+        MultithreadMatrix::new(spd.clone())
+            .cholesky()
+            .unwrap()
+            .l()
+            .get_data(),
+        OneDVecMatrix::new(spd).cholesky().unwrap().l().get_data(),
+    );
+}
+
+/// One test, since the configuration is shared by every test in the binary.
+#[test]
+fn parallel_test_config() {
+    let default = config();
+    assert_eq!(default.min_elements(), ParallelConfig::DEFAULT_MIN_ELEMENTS);
+    assert_eq!(default.min_flops(), ParallelConfig::DEFAULT_MIN_FLOPS);
+    assert!(default.threads() >= 1);
+    assert_eq!(ParallelConfig::from_env().with_threads(0).threads(), 1);
+
+    // Every operation on the pool, whatever the size
+    let forced = ParallelConfig::from_env()
+        .with_threads(4)
+        .with_min_elements(1)
+        .with_min_flops(1);
+    forced.apply();
+    assert_eq!(config(), forced);
+    check_against_one_d_vec();
+
+    // A different pool size runs on a second pool
+    forced.with_threads(3).apply();
+    assert_eq!(config().threads(), 3);
+    check_against_one_d_vec();
+
+    // Every operation on the calling thread
+    forced
+        .with_min_elements(usize::MAX)
+        .with_min_flops(usize::MAX)
+        .apply();
+    check_against_one_d_vec();
+
+    default.apply();
+    assert_eq!(config(), default);
+}
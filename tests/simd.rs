@@ -0,0 +1,91 @@
+use rmatrix::matrices::basic_matrix::BasicMatrix;
+use rmatrix::matrices::multithread_matrix::MultithreadMatrix;
+use rmatrix::matrices::one_d_vec_matrix::OneDVecMatrix;
+use rmatrix::matrices::packed_matrix::PackedMatrix;
+use rmatrix::simd::{Isa, active_isa, set_isa};
+use rmatrix::{FlatMatrix, FloatElement, Matrix};
+
+mod common;
+use common::entries;
+
+/// 111 elements leave a tail after every vector width. The entries are exact in `f32`, so the
+/// kernels must match the scalar loops bit for bit.
+fn check_elementwise<T: FloatElement, M: FlatMatrix<T>>() {
+    let (a, b) = (entries::<T>(37, 3, 0), entries::<T>(37, 3, 5));
+    let (basic_a, basic_b) = (BasicMatrix::new(a.clone()), BasicMatrix::new(b.clone()));
+    let (m_a, m_b) = (M::new(a.clone()), M::new(b));
+    let scalar = T::from(1.5).unwrap();
+
+    assert_eq!(
+        m_a.matrix_addition(&m_b).get_data(),
+        basic_a.matrix_addition(&basic_b).get_data()
+    );
+    assert_eq!(
+        m_a.matrix_subtraction(&m_b).get_data(),
+        basic_a.matrix_subtraction(&basic_b).get_data()
+    );
+    assert_eq!(
+        m_a.hadamard_product(&m_b).get_data(),
+        basic_a.hadamard_product(&basic_b).get_data()
+    );
+    assert_eq!(
+        m_a.elementwise_division(&m_b).get_data(),
+        basic_a.elementwise_division(&basic_b).get_data()
+    );
+    assert_eq!(
+        m_a.scalar_multiplication(scalar).get_data(),
+        basic_a.scalar_multiplication(scalar).get_data()
+    );
+
+    let mut m_c = M::new(a);
+    m_c.add_assign(&m_b);
+    m_c.scale_in_place(scalar);
+    m_c.sub_assign(&m_b);
+    let expected = basic_a
+        .matrix_addition(&basic_b)
+        .scalar_multiplication(scalar)
+        .matrix_subtraction(&basic_b);
+    assert_eq!(m_c.get_data(), expected.get_data());
+}
+
+/// Shapes that leave partial tiles for every micro-kernel.
+fn check_gemm<T: FloatElement>() {
+    let (a, b) = (entries::<T>(13, 29, 1), entries::<T>(29, 35, 2));
+    let expected = BasicMatrix::new(a.clone()).matrix_multiplication(&BasicMatrix::new(b.clone()));
+    let actual = PackedMatrix::new(a).matrix_multiplication(&PackedMatrix::new(b));
+    assert_eq!(actual.get_data(), expected.get_data());
+}
+
+#[test]
+fn simd_test_kernels_match_scalar() {
+    let active = active_isa();
+    assert!(Isa::detect().is_supported());
+
+    for isa in [Isa::Scalar, Isa::Avx2, Isa::Avx512, Isa::Neon] {
+        if !isa.is_supported() {
+            continue;
+        }
+        set_isa(isa);
+        assert_eq!(active_isa(), isa);
+
+        check_elementwise::<f64, OneDVecMatrix<f64>>();
+        check_elementwise::<f32, OneDVecMatrix<f32>>();
+        check_elementwise::<f64, MultithreadMatrix<f64>>();
+        check_elementwise::<f32, MultithreadMatrix<f32>>();
+        check_elementwise::<f64, PackedMatrix<f64>>();
+        check_elementwise::<f32, PackedMatrix<f32>>();
+        check_gemm::<f64>();
+        check_gemm::<f32>();
+    }
+    set_isa(active);
+}
+
+#[test]
+#[should_panic(expected = "is not supported on this CPU")]
+fn simd_test_unsupported_isa() {
+    set_isa(if cfg!(target_arch = "aarch64") {
+        Isa::Avx2
+    } else {
+        Isa::Neon
+    });
+}
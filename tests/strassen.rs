@@ -0,0 +1,146 @@
+use rmatrix::matrices::basic_matrix::BasicMatrix;
+use rmatrix::matrices::blas_matrix::BlasMatrix;
+use rmatrix::matrices::blocked_matrix::BlockedMatrix;
+use rmatrix::matrices::multithread_matrix::MultithreadMatrix;
+use rmatrix::matrices::one_d_vec_matrix::OneDVecMatrix;
+use rmatrix::matrices::packed_matrix::PackedMatrix;
+use rmatrix::tuning::TuningConfig;
+use rmatrix::{Complex, FlatMatrix, Matrix};
+
+mod common;
+use common::{assert_close, entries};
+
+/// Square, odd, non-square and thin shapes, so every level peels something off.
+const SHAPES: [(usize, usize, usize); 8] = [
+    (1, 1, 1),
+    (2, 2, 2),
+    (7, 5, 3),
+    (16, 16, 16),
+    (33, 17, 65),
+    (64, 63, 65),
+    (101, 3, 90),
+    (70, 91, 2),
+];
+
+/// Strassen's product against `BasicMatrix`, on matrices built by `new`.
+fn check_f64<M: FlatMatrix>(new: impl Fn(Vec<Vec<f64>>) -> M) {
+    for (m, k, n) in SHAPES {
+        let (a, b) = (entries(m, k, 1), entries(k, n, 2));
+        let expected =
+            BasicMatrix::new(a.clone()).matrix_multiplication(&BasicMatrix::new(b.clone()));
+        let actual = new(a).strassen_multiplication(&new(b));
+
+        assert_eq!(actual.shape(), (m, n));
+        assert_close(actual.get_data(), expected.get_data());
+    }
+}
+
+/// Integer products are exact.
+fn check_i64(tuning: TuningConfig) {
+    for (m, k, n) in SHAPES {
+        let entries = |rows: usize, cols: usize, seed: i64| {
+            (0..rows)
+                .map(|i| {
+                    (0..cols)
+                        .map(|j| ((i * cols + j) as i64 * 13 + seed) % 19 - 9)
+                        .collect()
+                })
+                .collect::<Vec<Vec<i64>>>()
+        };
+        let (a, b) = (entries(m, k, 3), entries(k, n, 5));
+        let expected =
+            BasicMatrix::new(a.clone()).matrix_multiplication(&BasicMatrix::new(b.clone()));
+        let actual = BlockedMatrix::new(a)
+            .with_tuning(tuning)
+            .strassen_multiplication(&BlockedMatrix::new(b));
+        assert_eq!(actual.get_data(), expected.get_data());
+    }
+}
+
+fn check_complex(tuning: TuningConfig) {
+    let (m, k, n) = (19, 22, 13);
+    let entries = |rows: usize, cols: usize, seed: usize| {
+        let re = entries(rows, cols, seed);
+        let im = entries(rows, cols, seed + 7);
+        re.into_iter()
+            .zip(im)
+            .map(|(re, im)| {
+                re.into_iter()
+                    .zip(im)
+                    .map(|(re, im)| Complex::new(re, im))
+                    .collect()
+            })
+            .collect::<Vec<Vec<Complex<f64>>>>()
+    };
+    let (a, b) = (entries(m, k, 0), entries(k, n, 4));
+    let expected = BasicMatrix::new(a.clone()).matrix_multiplication(&BasicMatrix::new(b.clone()));
+    let actual = PackedMatrix::new(a)
+        .with_tuning(tuning)
+        .strassen_multiplication(&PackedMatrix::new(b));
+    for (x, y) in actual
+        .get_data()
+        .iter()
+        .flatten()
+        .zip(expected.get_data().iter().flatten())
+    {
+        assert!((x - y).norm() < 1e-9);
+    }
+}
+
+/// The backends that hold a tuning, each multiplying with `tuning`.
+fn check_tuned(tuning: TuningConfig) {
+    check_f64(|data| BlockedMatrix::new(data).with_tuning(tuning));
+    check_f64(|data| MultithreadMatrix::new(data).with_tuning(tuning));
+    check_f64(|data| PackedMatrix::new(data).with_tuning(tuning));
+    check_i64(tuning);
+    check_complex(tuning);
+}
+
+#[test]
+fn strassen_test_against_basic() {
+    assert_eq!(TuningConfig::default().strassen_cutoff(), 128);
+
+    // Recurse down to single elements, then to a few levels above the blocked kernel, on the
+    // calling thread and on a pool
+    for (cutoff, block_size) in [(1, 1), (4, 3), (16, 8)] {
+        for threads in [1, 4] {
+            check_tuned(
+                TuningConfig::default()
+                    .with_strassen_cutoff(cutoff)
+                    .with_block_size(block_size)
+                    .with_threads(threads),
+            );
+        }
+    }
+}
+
+/// The default cutoff multiplies small matrices with the blocked kernel alone.
+#[test]
+fn strassen_test_default_tuning() {
+    check_f64(OneDVecMatrix::new);
+    check_f64(BlasMatrix::new);
+    check_tuned(TuningConfig::default());
+}
+
+/// Large enough for the seven products of the first level to go to the pool.
+#[test]
+fn strassen_test_parallel() {
+    let (m, k, n) = (80, 72, 68);
+    let (a, b) = (entries(m, k, 1), entries(k, n, 2));
+    let expected = BasicMatrix::new(a.clone()).matrix_multiplication(&BasicMatrix::new(b.clone()));
+    let tuning = TuningConfig::default()
+        .with_strassen_cutoff(16)
+        .with_threads(4);
+    let actual = MultithreadMatrix::new(a)
+        .with_tuning(tuning)
+        .strassen_multiplication(&MultithreadMatrix::new(b));
+    assert_close(actual.get_data(), expected.get_data());
+}
+
+#[test]
+#[should_panic(expected = "dimension mismatch")]
+fn strassen_test_incompatible_shapes() {
+    let a = OneDVecMatrix::new(entries::<f64>(3, 4, 0));
+    let b = OneDVecMatrix::new(entries::<f64>(3, 4, 1));
+    a.strassen_multiplication(&b);
+}
@@ -1,4 +1,10 @@
-use rmatrix::Matrix;
+use rmatrix::{Complex, Eigen, Element, Matrix, MatrixError, Reductions, Vector};
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// Borrows the rows of `matrix` for comparisons, without copying them like `get_data`.
+pub fn rows<E: Element, T: Matrix<E>>(matrix: &T) -> Vec<&[E]> {
+    matrix.row_iter().collect()
+}
 
 pub fn test_new<T: Matrix>() {
     let data = vec![vec![1.0, 2.0], vec![3.0, 4.0]];
@@ -8,6 +14,7 @@ pub fn test_new<T: Matrix>() {
     assert_eq!(matrix.num_rows(), 2);
     assert_eq!(matrix.num_cols(), 2);
     assert_eq!(matrix.get_data(), data);
+    assert_eq!(rows(&matrix), data);
 }
 
 pub fn test_zeroes<T: Matrix>() {
@@ -18,8 +25,8 @@ pub fn test_zeroes<T: Matrix>() {
     assert_eq!(matrix.num_cols(), 3);
 
     // Check all elements are zero
-    for row in matrix.get_data() {
-        for val in row {
+    for row in matrix.row_iter() {
+        for &val in row {
             assert_eq!(val, 0.0);
         }
     }
@@ -36,9 +43,9 @@ pub fn test_identity<T: Matrix>() {
     for i in 0..3 {
         for j in 0..3 {
             if i == j {
-                assert_eq!(matrix.get_data()[i][j], 1.0);
+                assert_eq!(matrix.row(i)[j], 1.0);
             } else {
-                assert_eq!(matrix.get_data()[i][j], 0.0);
+                assert_eq!(matrix.row(i)[j], 0.0);
             }
         }
     }
@@ -52,7 +59,7 @@ pub fn test_matrix_multiplication_identity<T: Matrix>() {
     let result = matrix.matrix_multiplication(&identity);
 
     // Should be unchanged after multiplying by identity
-    assert_eq!(result.get_data(), data);
+    assert_eq!(rows(&result), data);
 }
 
 pub fn test_matrix_addition<T: Matrix>() {
@@ -62,7 +69,7 @@ pub fn test_matrix_addition<T: Matrix>() {
     let result = matrix1.matrix_addition(&matrix2);
 
     // Check result
-    assert_eq!(result.get_data(), vec![vec![6.0, 8.0], vec![10.0, 12.0]]);
+    assert_eq!(rows(&result), vec![vec![6.0, 8.0], vec![10.0, 12.0]]);
 }
 
 pub fn test_matrix_subtraction<T: Matrix>() {
@@ -72,7 +79,7 @@ pub fn test_matrix_subtraction<T: Matrix>() {
     let result = matrix1.matrix_subtraction(&matrix2);
 
     // Check result
-    assert_eq!(result.get_data(), vec![vec![4.0, 4.0], vec![4.0, 4.0]]);
+    assert_eq!(rows(&result), vec![vec![4.0, 4.0], vec![4.0, 4.0]]);
 }
 
 pub fn test_matrix_multiplication<T: Matrix>() {
@@ -86,10 +93,7 @@ pub fn test_matrix_multiplication<T: Matrix>() {
     assert_eq!(result.num_cols(), 2);
 
     // Check result: [1*7+2*9+3*11, 1*8+2*10+3*12; 4*7+5*9+6*11, 4*8+5*10+6*12]
-    assert_eq!(
-        result.get_data(),
-        vec![vec![58.0, 64.0], vec![139.0, 154.0]]
-    );
+    assert_eq!(rows(&result), vec![vec![58.0, 64.0], vec![139.0, 154.0]]);
 }
 
 pub fn test_scalar_multiplication<T: Matrix>() {
@@ -99,5 +103,904 @@ pub fn test_scalar_multiplication<T: Matrix>() {
     let result = matrix.scalar_multiplication(scalar);
 
     // Check result
-    assert_eq!(result.get_data(), vec![vec![2.0, 4.0], vec![6.0, 8.0]]);
+    assert_eq!(rows(&result), vec![vec![2.0, 4.0], vec![6.0, 8.0]]);
+}
+
+pub fn test_chained_operations<T: Matrix>() {
+    let matrix1 = T::new(vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+    let matrix2 = T::new(vec![vec![1.0, 0.0], vec![0.0, 1.0]]);
+
+    // Results of earlier operations must be usable as either operand
+    let sum = matrix1.matrix_addition(&matrix2);
+    let result = matrix1.matrix_multiplication(&sum);
+
+    assert_eq!(rows(&result), vec![vec![8.0, 12.0], vec![18.0, 26.0]]);
+}
+
+pub fn test_try_new<T: Matrix>() {
+    let data = vec![vec![1.0, 2.0], vec![3.0, 4.0]];
+    assert_eq!(rows(&T::try_new(data.clone()).unwrap()), data);
+
+    assert_eq!(T::try_new(vec![]).err(), Some(MatrixError::EmptyInput));
+    assert_eq!(
+        T::try_new(vec![vec![]]).err(),
+        Some(MatrixError::EmptyInput)
+    );
+    assert_eq!(
+        T::try_new(vec![vec![1.0, 2.0], vec![3.0]]).err(),
+        Some(MatrixError::RaggedRows {
+            row: 1,
+            expected: 2,
+            found: 1
+        })
+    );
+}
+
+/// The message `f` panics with, failing the test if it returns.
+fn panic_message(f: impl FnOnce()) -> String {
+    let payload = std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)).unwrap_err();
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => payload.downcast::<&str>().unwrap().to_string(),
+    }
+}
+
+pub fn test_new_invalid_data<T: Matrix>() {
+    assert_eq!(
+        panic_message(|| {
+            T::new(vec![]);
+        }),
+        MatrixError::EmptyInput.to_string()
+    );
+    assert_eq!(
+        panic_message(|| {
+            T::new(vec![vec![1.0, 2.0], vec![3.0]]);
+        }),
+        MatrixError::RaggedRows {
+            row: 1,
+            expected: 2,
+            found: 1
+        }
+        .to_string()
+    );
+}
+
+pub fn test_try_zeroes_and_identity<T: Matrix>() {
+    assert_eq!(T::try_zeroes(2, 3).unwrap().shape(), (2, 3));
+    assert_eq!(T::try_zeroes(0, 3).err(), Some(MatrixError::EmptyInput));
+    assert_eq!(T::try_zeroes(2, 0).err(), Some(MatrixError::EmptyInput));
+
+    assert_eq!(T::try_identity(3).unwrap().shape(), (3, 3));
+    assert_eq!(T::try_identity(0).err(), Some(MatrixError::EmptyInput));
+}
+
+pub fn test_try_matrix_addition<T: Matrix>() {
+    let matrix1 = T::new(vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+    let matrix2 = T::new(vec![vec![5.0, 6.0], vec![7.0, 8.0]]);
+    let matrix3 = T::new(vec![vec![1.0, 2.0, 3.0]]);
+
+    let result = matrix1.try_matrix_addition(&matrix2).unwrap();
+    assert_eq!(rows(&result), vec![vec![6.0, 8.0], vec![10.0, 12.0]]);
+
+    assert_eq!(
+        matrix1.try_matrix_addition(&matrix3).err(),
+        Some(MatrixError::DimensionMismatch {
+            left: (2, 2),
+            right: (1, 3)
+        })
+    );
+}
+
+pub fn test_try_matrix_subtraction<T: Matrix>() {
+    let matrix1 = T::new(vec![vec![5.0, 6.0], vec![7.0, 8.0]]);
+    let matrix2 = T::new(vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+    let matrix3 = T::new(vec![vec![1.0], vec![2.0]]);
+
+    let result = matrix1.try_matrix_subtraction(&matrix2).unwrap();
+    assert_eq!(rows(&result), vec![vec![4.0, 4.0], vec![4.0, 4.0]]);
+
+    assert_eq!(
+        matrix1.try_matrix_subtraction(&matrix3).err(),
+        Some(MatrixError::DimensionMismatch {
+            left: (2, 2),
+            right: (2, 1)
+        })
+    );
+}
+
+pub fn test_try_matrix_multiplication<T: Matrix>() {
+    let matrix1 = T::new(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
+    let matrix2 = T::new(vec![vec![7.0, 8.0], vec![9.0, 10.0], vec![11.0, 12.0]]);
+
+    let result = matrix1.try_matrix_multiplication(&matrix2).unwrap();
+    assert_eq!(rows(&result), vec![vec![58.0, 64.0], vec![139.0, 154.0]]);
+
+    assert_eq!(
+        matrix1.try_matrix_multiplication(&matrix1).err(),
+        Some(MatrixError::DimensionMismatch {
+            left: (2, 3),
+            right: (2, 3)
+        })
+    );
+}
+
+pub fn test_element_type<E: Element, T: Matrix<E>>() {
+    let one = E::one();
+    let two = one + one;
+    let three = two + one;
+
+    let matrix1 = T::new(vec![vec![one, two], vec![three, one]]);
+    let matrix2 = T::identity(2).scalar_multiplication(two);
+
+    let sum = matrix1.matrix_addition(&matrix2);
+    assert_eq!(rows(&sum), vec![vec![three, two], vec![three, three]]);
+
+    let difference = sum.matrix_subtraction(&matrix1);
+    assert_eq!(rows(&difference), rows(&matrix2));
+
+    // [1 2; 3 1] * [3 2; 3 3] = [9 8; 12 9]
+    let product = matrix1.matrix_multiplication(&sum);
+    let nine = three * three;
+    let eight = two * two * two;
+    assert_eq!(
+        rows(&product),
+        vec![vec![nine, eight], vec![nine + three, nine]]
+    );
+}
+
+pub fn test_complex_elements<T: Matrix<Complex<f64>>>() {
+    let c = |re, im| Complex::new(re, im);
+    let matrix1 = T::new(vec![
+        vec![c(1.0, 1.0), c(0.0, 2.0)],
+        vec![c(3.0, 0.0), c(1.0, -1.0)],
+    ]);
+    let matrix2 = T::new(vec![
+        vec![c(0.0, 1.0), c(1.0, 0.0)],
+        vec![c(2.0, 0.0), c(0.0, -1.0)],
+    ]);
+
+    let sum = matrix1.matrix_addition(&matrix2);
+    assert_eq!(
+        rows(&sum),
+        vec![
+            vec![c(1.0, 2.0), c(1.0, 2.0)],
+            vec![c(5.0, 0.0), c(1.0, -2.0)]
+        ]
+    );
+
+    // (1+i)i + 2i*2 = -1+5i, (1+i) + 2i(-i) = 3+i, 3i + (1-i)2 = 2+i, 3 + (1-i)(-i) = 2-i
+    let product = matrix1.matrix_multiplication(&matrix2);
+    assert_eq!(
+        rows(&product),
+        vec![
+            vec![c(-1.0, 5.0), c(3.0, 1.0)],
+            vec![c(2.0, 1.0), c(2.0, -1.0)]
+        ]
+    );
+}
+
+pub fn test_conjugate_transpose<T: Matrix<Complex<f64>>>() {
+    let c = |re, im| Complex::new(re, im);
+    let matrix = T::new(vec![
+        vec![c(1.0, 1.0), c(2.0, 0.0), c(0.0, 3.0)],
+        vec![c(4.0, -1.0), c(5.0, 2.0), c(6.0, 0.0)],
+    ]);
+
+    let result = matrix.conjugate_transpose();
+
+    assert_eq!(result.shape(), (3, 2));
+    assert_eq!(
+        rows(&result),
+        vec![
+            vec![c(1.0, -1.0), c(4.0, 1.0)],
+            vec![c(2.0, 0.0), c(5.0, -2.0)],
+            vec![c(0.0, -3.0), c(6.0, 0.0)],
+        ]
+    );
+}
+
+pub fn test_operators<T>()
+where
+    T: Matrix
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Mul<f64, Output = T>
+        + Neg<Output = T>
+        + for<'a> AddAssign<&'a T>
+        + SubAssign
+        + MulAssign
+        + MulAssign<f64>,
+    for<'a> &'a T: Add<&'a T, Output = T>
+        + Sub<&'a T, Output = T>
+        + Mul<&'a T, Output = T>
+        + Mul<f64, Output = T>
+        + Neg<Output = T>,
+    f64: Mul<T, Output = T>,
+{
+    let new = || T::new(vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+    let matrix1 = new();
+    let matrix2 = T::new(vec![vec![5.0, 6.0], vec![7.0, 8.0]]);
+
+    assert_eq!(
+        rows(&(&matrix1 + &matrix2)),
+        vec![vec![6.0, 8.0], vec![10.0, 12.0]]
+    );
+    assert_eq!(
+        rows(&(&matrix2 - &matrix1)),
+        vec![vec![4.0, 4.0], vec![4.0, 4.0]]
+    );
+    assert_eq!(
+        rows(&(&matrix1 * &matrix2)),
+        vec![vec![19.0, 22.0], vec![43.0, 50.0]]
+    );
+    assert_eq!(
+        rows(&(&matrix1 * 2.0)),
+        vec![vec![2.0, 4.0], vec![6.0, 8.0]]
+    );
+    assert_eq!(rows(&(-&matrix1)), vec![vec![-1.0, -2.0], vec![-3.0, -4.0]]);
+
+    // Owned operands and formulas
+    let result = 2.0 * new() + new() * T::identity(2) - -new();
+    assert_eq!(rows(&result), vec![vec![4.0, 8.0], vec![12.0, 16.0]]);
+
+    let mut matrix = new();
+    matrix += &matrix2;
+    matrix -= new();
+    matrix *= T::identity(2);
+    matrix *= 0.5;
+    assert_eq!(rows(&matrix), vec![vec![2.5, 3.0], vec![3.5, 4.0]]);
+}
+
+pub fn test_operators_dimension_mismatch<T: Matrix>()
+where
+    for<'a> &'a T: Add<&'a T, Output = T>,
+{
+    let matrix1 = T::new(vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+    let matrix2 = T::new(vec![vec![1.0, 2.0, 3.0]]);
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| &matrix1 + &matrix2));
+    assert!(result.is_err());
+}
+
+/// Neither side may be truncated to the shape of the other, whichever has less storage.
+pub fn test_addition_subtraction_dimension_mismatch<T: Matrix>() {
+    let matrix = T::new(vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+    for other in [T::zeroes(1, 1), T::zeroes(2, 3), T::zeroes(3, 2)] {
+        let mismatch = |left, right| MatrixError::DimensionMismatch { left, right }.to_string();
+        assert_eq!(
+            panic_message(|| {
+                matrix.matrix_addition(&other);
+            }),
+            mismatch((2, 2), other.shape())
+        );
+        assert_eq!(
+            panic_message(|| {
+                other.matrix_subtraction(&matrix);
+            }),
+            mismatch(other.shape(), (2, 2))
+        );
+    }
+}
+
+/// The in-place operations must not truncate to the shorter storage, whichever side it is.
+pub fn test_assign_dimension_mismatch<T: Matrix>() {
+    let mut matrix = T::new(vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+    let shorter = T::new(vec![vec![10.0, 20.0, 30.0]]);
+    let longer = T::zeroes(3, 2);
+
+    let add =
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| matrix.add_assign(&shorter)));
+    assert!(add.is_err());
+    let sub = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| matrix.sub_assign(&longer)));
+    assert!(sub.is_err());
+    assert_eq!(rows(&matrix), vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+}
+
+/// `c` is far too small for the product, so writing it unchecked would run off its storage.
+pub fn test_gemm_dimension_mismatch<T: Matrix>() {
+    let a = T::identity(64);
+    let mut c = T::zeroes(1, 1);
+    T::gemm(1.0, &a, &a, 0.0, &mut c);
+}
+
+pub fn test_element_access<T>()
+where
+    T: Matrix + Index<(usize, usize), Output = f64> + IndexMut<(usize, usize)>,
+{
+    let mut matrix = T::new(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
+
+    assert_eq!(matrix.get(1, 2), 6.0);
+    assert_eq!(matrix[(0, 1)], 2.0);
+
+    matrix.set(0, 0, 10.0);
+    *matrix.get_mut(1, 0) = 40.0;
+    matrix[(1, 2)] = 60.0;
+    assert_eq!(
+        rows(&matrix),
+        vec![vec![10.0, 2.0, 3.0], vec![40.0, 5.0, 60.0]]
+    );
+
+    // Writes must be visible when the matrix is used as either operand
+    let mut identity = T::identity(3);
+    identity.set(2, 2, 2.0);
+    assert_eq!(
+        rows(&matrix.matrix_multiplication(&identity)),
+        vec![vec![10.0, 2.0, 6.0], vec![40.0, 5.0, 120.0]]
+    );
+    identity[(0, 0)] = 3.0;
+    assert_eq!(
+        rows(&matrix.matrix_multiplication(&identity)),
+        vec![vec![30.0, 2.0, 6.0], vec![120.0, 5.0, 120.0]]
+    );
+
+    assert_eq!(matrix.try_get(0, 2), Ok(3.0));
+    assert_eq!(
+        matrix.try_get(0, 3),
+        Err(MatrixError::IndexOutOfBounds {
+            index: (0, 3),
+            shape: (2, 3)
+        })
+    );
+    assert_eq!(matrix.try_set(1, 1, 50.0), Ok(()));
+    assert_eq!(matrix[(1, 1)], 50.0);
+    assert_eq!(
+        matrix.try_set(2, 0, 0.0),
+        Err(MatrixError::IndexOutOfBounds {
+            index: (2, 0),
+            shape: (2, 3)
+        })
+    );
+}
+
+pub fn test_index_out_of_bounds<T>()
+where
+    T: Matrix + Index<(usize, usize), Output = f64>,
+{
+    // (0, 3) is inside the underlying storage of a flat 2x3 matrix, but not a valid index
+    let matrix = T::new(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
+    let _ = matrix[(0, 3)];
+}
+
+pub fn test_data_access<T: Matrix>() {
+    let data = vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]];
+    let flat = vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+    let matrix = T::new(data.clone());
+
+    if let Some(slice) = matrix.as_slice() {
+        assert_eq!(slice, flat.as_slice());
+    }
+    assert_eq!(matrix.row(1), &[4.0, 5.0, 6.0]);
+    assert_eq!(matrix.row_iter().count(), 2);
+
+    assert_eq!(matrix.into_vec(), flat);
+    assert_eq!(T::new(data.clone()).into_rows(), data);
+}
+
+pub fn test_transpose<T: Matrix>() {
+    let matrix = T::new(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
+
+    let result = matrix.transpose();
+    assert_eq!(result.shape(), (3, 2));
+    assert_eq!(
+        rows(&result),
+        vec![vec![1.0, 4.0], vec![2.0, 5.0], vec![3.0, 6.0]]
+    );
+    assert_eq!(rows(&result.transpose()), rows(&matrix));
+
+    // Large enough to span several tiles and thread chunks, and not a multiple of either
+    let (num_rows, num_cols) = (70, 45);
+    let data: Vec<Vec<f64>> = (0..num_rows)
+        .map(|i| (0..num_cols).map(|j| (i * num_cols + j) as f64).collect())
+        .collect();
+    let result = T::new(data.clone()).transpose();
+    assert_eq!(result.shape(), (num_cols, num_rows));
+    for (j, row) in data.iter().enumerate() {
+        for (i, &val) in row.iter().enumerate() {
+            assert_eq!(result.get(i, j), val);
+        }
+    }
+}
+
+pub fn test_transposed_view<T>()
+where
+    T: Matrix + Index<(usize, usize), Output = f64>,
+{
+    let matrix = T::new(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
+    let view = matrix.transposed_view();
+
+    assert_eq!(view.shape(), (3, 2));
+    assert_eq!(view.num_rows(), 3);
+    assert_eq!(view.num_cols(), 2);
+    assert_eq!(view.get(2, 1), 6.0);
+    assert_eq!(view[(0, 1)], 4.0);
+    assert_eq!(view.col(1), &[4.0, 5.0, 6.0]);
+    assert_eq!(rows(&view.to_matrix()), rows(&matrix.transpose()));
+}
+
+pub fn test_in_place_arithmetic<T: Matrix>() {
+    let mut matrix = T::new(vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+    let other = T::new(vec![vec![5.0, 6.0], vec![7.0, 8.0]]);
+
+    matrix.add_assign(&other);
+    assert_eq!(rows(&matrix), vec![vec![6.0, 8.0], vec![10.0, 12.0]]);
+
+    matrix.sub_assign(&other);
+    matrix.sub_assign(&other);
+    assert_eq!(rows(&matrix), vec![vec![-4.0, -4.0], vec![-4.0, -4.0]]);
+
+    matrix.scale_in_place(-0.5);
+    assert_eq!(rows(&matrix), vec![vec![2.0, 2.0], vec![2.0, 2.0]]);
+
+    // The result must be visible to every kind of read, including multiplication
+    let product = matrix.matrix_multiplication(&T::identity(2));
+    assert_eq!(rows(&product), vec![vec![2.0, 2.0], vec![2.0, 2.0]]);
+
+    assert_eq!(
+        matrix.try_add_assign(&T::zeroes(2, 3)),
+        Err(MatrixError::DimensionMismatch {
+            left: (2, 2),
+            right: (2, 3)
+        })
+    );
+    assert!(matrix.try_sub_assign(&T::zeroes(3, 2)).is_err());
+    assert_eq!(rows(&matrix), vec![vec![2.0, 2.0], vec![2.0, 2.0]]);
+}
+
+pub fn test_gemm<T: Matrix>() {
+    let a = T::new(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
+    let b = T::new(vec![vec![7.0, 8.0], vec![9.0, 10.0], vec![11.0, 12.0]]);
+
+    // With beta = 0, whatever c held is ignored, even NaNs
+    let mut c = T::new(vec![vec![f64::NAN; 2]; 2]);
+    T::matmul_into(&a, &b, &mut c);
+    assert_eq!(rows(&c), vec![vec![58.0, 64.0], vec![139.0, 154.0]]);
+
+    let mut c = T::new(vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+    T::gemm(2.0, &a, &b, 3.0, &mut c);
+    assert_eq!(rows(&c), vec![vec![119.0, 134.0], vec![287.0, 320.0]]);
+
+    // Large enough to span several blocks, compared against the allocating product
+    let (m, k, n) = (19, 17, 13);
+    let a = T::new(
+        (0..m)
+            .map(|i| (0..k).map(|p| ((i * k + p) % 7) as f64).collect())
+            .collect(),
+    );
+    let b = T::new(
+        (0..k)
+            .map(|p| (0..n).map(|j| ((p + 2 * j) % 5) as f64).collect())
+            .collect(),
+    );
+    let mut c = T::new(vec![vec![1.0; n]; m]);
+    T::gemm(-1.0, &a, &b, 2.0, &mut c);
+    let product = a.matrix_multiplication(&b);
+    for i in 0..m {
+        for j in 0..n {
+            assert_eq!(c.get(i, j), 2.0 - product.get(i, j));
+        }
+    }
+
+    let mut wrong = T::zeroes(m, m);
+    assert_eq!(
+        T::try_matmul_into(&a, &b, &mut wrong),
+        Err(MatrixError::DimensionMismatch {
+            left: (m, n),
+            right: (m, m)
+        })
+    );
+    assert_eq!(
+        T::try_gemm(1.0, &b, &b, 0.0, &mut wrong),
+        Err(MatrixError::DimensionMismatch {
+            left: (k, n),
+            right: (k, n)
+        })
+    );
+}
+
+/// Reproducible entries in `[-1, 1)` from a linear congruential generator.
+fn pseudo_random_rows(rows: usize, cols: usize) -> Vec<Vec<f64>> {
+    let mut state = 0x9e37_79b9_u64;
+    (0..rows)
+        .map(|_| {
+            (0..cols)
+                .map(|_| {
+                    state = state
+                        .wrapping_mul(6364136223846793005)
+                        .wrapping_add(1442695040888963407);
+                    (state >> 11) as f64 / (1u64 << 53) as f64 * 2.0 - 1.0
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// `||(A - λI)^-1 * b||^-1` for `b` of ones, which is tiny when `λ` is an eigenvalue of `a`.
+/// Uses Gaussian elimination with partial pivoting in complex arithmetic.
+fn eigenvalue_residual(a: &[Vec<f64>], lambda: Complex<f64>) -> f64 {
+    let n = a.len();
+    let mut m = a
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let mut row = row
+                .iter()
+                .enumerate()
+                .map(|(j, &x)| {
+                    Complex::new(x, 0.0)
+                        - if i == j {
+                            lambda
+                        } else {
+                            Complex::new(0.0, 0.0)
+                        }
+                })
+                .collect::<Vec<_>>();
+            row.push(Complex::new(1.0, 0.0));
+            row
+        })
+        .collect::<Vec<_>>();
+
+    for k in 0..n {
+        let pivot = (k..n)
+            .max_by(|&i, &j| m[i][k].norm().total_cmp(&m[j][k].norm()))
+            .unwrap();
+        m.swap(k, pivot);
+        if m[k][k].norm() == 0.0 {
+            return 0.0;
+        }
+        let (top, bottom) = m.split_at_mut(k + 1);
+        let pivot_row = &top[k];
+        for row in bottom {
+            let factor = row[k] / pivot_row[k];
+            for (m_ij, &m_kj) in row[k..].iter_mut().zip(&pivot_row[k..]) {
+                *m_ij -= factor * m_kj;
+            }
+        }
+    }
+    let mut x = vec![Complex::new(0.0, 0.0); n];
+    for i in (0..n).rev() {
+        let sum = (i + 1..n).fold(m[i][n], |sum, j| sum - m[i][j] * x[j]);
+        x[i] = sum / m[i][i];
+    }
+    1.0 / x.iter().map(|x_i| x_i.norm_sqr()).sum::<f64>().sqrt()
+}
+
+pub fn test_symmetric_eigen<T: Matrix>() {
+    let eigen = T::new(vec![vec![2.0, 1.0], vec![1.0, 2.0]])
+        .symmetric_eigen()
+        .unwrap();
+    let eigenvalues = eigen.eigenvalues();
+    assert!((eigenvalues[0] - 1.0).abs() < 1e-12);
+    assert!((eigenvalues[1] - 3.0).abs() < 1e-12);
+
+    // A * Q = Q * Λ and Q^T * Q = I for a random symmetric matrix
+    let n = 12;
+    let random = pseudo_random_rows(n, n);
+    let data = (0..n)
+        .map(|i| (0..n).map(|j| random[i][j] + random[j][i]).collect())
+        .collect::<Vec<Vec<f64>>>();
+    let a = T::new(data.clone());
+    let (eigenvalues, q) = a.symmetric_eigen().unwrap().into_parts();
+    assert!(eigenvalues.windows(2).all(|pair| pair[0] <= pair[1]));
+    let aq = a.matrix_multiplication(&q);
+    let qtq = q.transpose().matrix_multiplication(&q);
+    for i in 0..n {
+        for (j, &lambda) in eigenvalues.iter().enumerate() {
+            assert!((aq.get(i, j) - q.get(i, j) * lambda).abs() < 1e-12);
+            let identity = if i == j { 1.0 } else { 0.0 };
+            assert!((qtq.get(i, j) - identity).abs() < 1e-12);
+        }
+    }
+    let trace = (0..n).map(|i| data[i][i]).sum::<f64>();
+    assert!((eigenvalues.iter().sum::<f64>() - trace).abs() < 1e-12);
+
+    // Already diagonal, and repeated eigenvalues
+    let eigen = T::new(vec![
+        vec![3.0, 0.0, 0.0],
+        vec![0.0, -1.0, 0.0],
+        vec![0.0, 0.0, 3.0],
+    ])
+    .symmetric_eigen()
+    .unwrap();
+    assert_eq!(eigen.eigenvalues(), [-1.0, 3.0, 3.0]);
+
+    assert_eq!(
+        T::new(vec![vec![1.0, 2.0], vec![0.0, 1.0]])
+            .symmetric_eigen()
+            .err(),
+        Some(MatrixError::NotSymmetric { index: (1, 0) })
+    );
+    assert_eq!(
+        T::zeroes(2, 3).symmetric_eigen().err(),
+        Some(MatrixError::NotSquare { shape: (2, 3) })
+    );
+}
+
+pub fn test_eigenvalues<T: Matrix>() {
+    // A rotation by 90 degrees
+    let eigenvalues = T::new(vec![vec![0.0, -1.0], vec![1.0, 0.0]])
+        .eigenvalues()
+        .unwrap();
+    assert_eq!(eigenvalues.len(), 2);
+    assert!((eigenvalues[0] - Complex::new(0.0, 1.0)).norm() < 1e-12);
+    assert!((eigenvalues[1] - Complex::new(0.0, -1.0)).norm() < 1e-12);
+
+    // Companion matrix of (x - 1) * (x - 2) * (x - 3)
+    let mut eigenvalues = T::new(vec![
+        vec![6.0, -11.0, 6.0],
+        vec![1.0, 0.0, 0.0],
+        vec![0.0, 1.0, 0.0],
+    ])
+    .eigenvalues()
+    .unwrap();
+    eigenvalues.sort_by(|a, b| a.re.total_cmp(&b.re));
+    for (lambda, expected) in eigenvalues.iter().zip([1.0, 2.0, 3.0]) {
+        assert!((lambda - Complex::new(expected, 0.0)).norm() < 1e-10);
+    }
+
+    // Every eigenvalue of a random matrix makes A - λI singular, and they sum to the trace
+    let n = 15;
+    let data = pseudo_random_rows(n, n);
+    let eigenvalues = T::new(data.clone()).eigenvalues().unwrap();
+    assert_eq!(eigenvalues.len(), n);
+    for &lambda in &eigenvalues {
+        assert!(eigenvalue_residual(&data, lambda) < 1e-12);
+        if lambda.im != 0.0 {
+            assert!(eigenvalues.contains(&lambda.conj()));
+        }
+    }
+    let sum = eigenvalues.iter().sum::<Complex<f64>>();
+    let trace = (0..n).map(|i| data[i][i]).sum::<f64>();
+    assert!((sum - Complex::new(trace, 0.0)).norm() < 1e-12);
+
+    // Agrees with the symmetric solver on a symmetric matrix
+    let a = T::new(vec![
+        vec![4.0, 1.0, 2.0],
+        vec![1.0, 3.0, 0.0],
+        vec![2.0, 0.0, 5.0],
+    ]);
+    let mut general = a.eigenvalues().unwrap();
+    general.sort_by(|a, b| a.re.total_cmp(&b.re));
+    let symmetric = a.symmetric_eigen().unwrap();
+    for (lambda, &expected) in general.iter().zip(symmetric.eigenvalues()) {
+        assert!((lambda - Complex::new(expected, 0.0)).norm() < 1e-12);
+    }
+
+    assert!(T::zeroes(2, 3).eigenvalues().is_err());
+}
+
+pub fn test_matvec<T: Matrix>() {
+    let a = T::new(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
+    let y = a.matvec(&Vector::new(vec![1.0, 0.0, -1.0]));
+    assert_eq!(y, Vector::new(vec![-2.0, -2.0]));
+
+    // Agrees with multiplying by a single-column matrix
+    let (m, n) = (37, 53);
+    let a = T::new(pseudo_random_rows(m, n));
+    let x = pseudo_random_rows(n, 1);
+    let expected = a.matrix_multiplication(&T::new(x.clone()));
+    let y = a.matvec(&Vector::new(x.concat()));
+    assert_eq!(y.len(), m);
+    for (y_i, expected_i) in y.iter().zip(rows(&expected).concat()) {
+        assert!((y_i - expected_i).abs() < 1e-12);
+    }
+
+    assert_eq!(
+        a.try_matvec(&Vector::zeroes(n + 1)).err(),
+        Some(MatrixError::DimensionMismatch {
+            left: (m, n),
+            right: (n + 1, 1)
+        })
+    );
+}
+
+pub fn test_vecmat<T: Matrix>() {
+    let a = T::new(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
+    let y = a.vecmat(&Vector::new(vec![1.0, -1.0]));
+    assert_eq!(y, Vector::new(vec![-3.0, -3.0, -3.0]));
+
+    // Agrees with multiplying a single-row matrix
+    let (m, n) = (53, 37);
+    let a = T::new(pseudo_random_rows(m, n));
+    let x = pseudo_random_rows(1, m);
+    let expected = T::new(x.clone()).matrix_multiplication(&a);
+    let y = a.vecmat(&Vector::new(x.concat()));
+    assert_eq!(y.len(), n);
+    for (y_i, expected_i) in y.iter().zip(rows(&expected).concat()) {
+        assert!((y_i - expected_i).abs() < 1e-12);
+    }
+
+    assert_eq!(
+        a.try_vecmat(&Vector::zeroes(m - 1)).err(),
+        Some(MatrixError::DimensionMismatch {
+            left: (1, m - 1),
+            right: (m, n)
+        })
+    );
+}
+
+pub fn test_trace_and_sums<T: Matrix>() {
+    let a = T::new(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
+    assert_eq!(a.trace(), 6.0);
+    assert_eq!(a.sum(), 21.0);
+    assert_eq!(a.row_sums(), Vector::new(vec![6.0, 15.0]));
+    assert_eq!(a.col_sums(), Vector::new(vec![5.0, 7.0, 9.0]));
+
+    // Uneven chunks for the threaded backend
+    let (m, n) = (37, 23);
+    let data = pseudo_random_rows(m, n);
+    let a = T::new(data.clone());
+    let row_sums = data
+        .iter()
+        .map(|row| row.iter().sum())
+        .collect::<Vec<f64>>();
+    let col_sums = (0..n)
+        .map(|j| data.iter().map(|row| row[j]).sum())
+        .collect::<Vec<f64>>();
+    assert!((a.sum() - row_sums.iter().sum::<f64>()).abs() < 1e-12);
+    assert!((a.trace() - (0..n).map(|i| data[i][i]).sum::<f64>()).abs() < 1e-12);
+    for (actual, expected) in a.row_sums().iter().zip(&row_sums) {
+        assert!((actual - expected).abs() < 1e-12);
+    }
+    for (actual, expected) in a.col_sums().iter().zip(&col_sums) {
+        assert!((actual - expected).abs() < 1e-12);
+    }
+}
+
+pub fn test_norms<T: Reductions>() {
+    let a = T::new(vec![vec![1.0, -2.0], vec![-3.0, 4.0], vec![0.5, 0.0]]);
+    assert!((a.norm_frobenius() - 30.25_f64.sqrt()).abs() < 1e-15);
+    assert_eq!(a.norm_1(), 6.0);
+    assert_eq!(a.norm_inf(), 7.0);
+    assert_eq!(a.norm_max(), 4.0);
+
+    // A NaN entry is not skipped
+    let nan = T::new(vec![vec![f64::NAN, 1.0], vec![2.0, 3.0]]);
+    assert!(nan.norm_frobenius().is_nan());
+    assert!(nan.norm_1().is_nan());
+    assert!(nan.norm_inf().is_nan());
+    assert!(nan.norm_max().is_nan());
+
+    // No overflow when squaring
+    let big = T::new(vec![vec![3e200], vec![4e200]]);
+    assert!((big.norm_frobenius() / 5e200 - 1.0).abs() < 1e-15);
+
+    let (m, n) = (37, 23);
+    let data = pseudo_random_rows(m, n);
+    let a = T::new(data.clone());
+    let frobenius = data.concat().iter().map(|x| x * x).sum::<f64>().sqrt();
+    let norm_1 = (0..n)
+        .map(|j| data.iter().map(|row| row[j].abs()).sum::<f64>())
+        .fold(0.0, f64::max);
+    let norm_inf = data
+        .iter()
+        .map(|row| row.iter().map(|x| x.abs()).sum::<f64>())
+        .fold(0.0, f64::max);
+    let norm_max = data.concat().iter().fold(0.0_f64, |m, x| m.max(x.abs()));
+    assert!((a.norm_frobenius() - frobenius).abs() < 1e-12);
+    assert!((a.norm_1() - norm_1).abs() < 1e-12);
+    assert!((a.norm_inf() - norm_inf).abs() < 1e-12);
+    assert_eq!(a.norm_max(), norm_max);
+}
+
+pub fn test_min_max<T: Reductions>() {
+    let a = T::new(vec![vec![2.0, -1.0, 7.0], vec![7.0, -1.0, 0.0]]);
+    assert_eq!(a.min(), -1.0);
+    assert_eq!(a.max(), 7.0);
+    // Ties go to the first entry in row-major order
+    assert_eq!(a.argmin(), (0, 1));
+    assert_eq!(a.argmax(), (0, 2));
+    assert_eq!(a.mean(), 14.0 / 6.0);
+
+    let a = T::new(vec![vec![f64::NAN, 3.0], vec![-2.0, f64::NAN]]);
+    assert_eq!((a.argmin(), a.argmax()), ((1, 0), (0, 1)));
+    assert!(T::new(vec![vec![f64::NAN; 2]]).max().is_nan());
+
+    // The extremes land in different chunks for the threaded backend
+    let (m, n) = (37, 23);
+    let mut data = pseudo_random_rows(m, n);
+    data[29][4] = -5.0;
+    data[3][17] = 5.0;
+    data[35][0] = 5.0;
+    let a = T::new(data);
+    assert_eq!(a.argmin(), (29, 4));
+    assert_eq!(a.argmax(), (3, 17));
+}
+
+pub fn test_row_col_statistics<T: Reductions>() {
+    let a = T::new(vec![vec![1.0, 2.0, 6.0], vec![3.0, 2.0, 4.0]]);
+    assert_eq!(a.row_means(), Vector::new(vec![3.0, 3.0]));
+    assert_eq!(a.col_means(), Vector::new(vec![2.0, 2.0, 5.0]));
+    assert_eq!(a.row_variances(), Vector::new(vec![14.0 / 3.0, 2.0 / 3.0]));
+    assert_eq!(a.col_variances(), Vector::new(vec![1.0, 0.0, 1.0]));
+
+    // Two passes keep the variance of values far from zero accurate
+    let a = T::new(vec![vec![1e9 + 1.0, 1e9 + 2.0, 1e9 + 3.0]]);
+    assert_eq!(a.row_variances(), Vector::new(vec![2.0 / 3.0]));
+
+    let (m, n) = (37, 23);
+    let data = pseudo_random_rows(m, n);
+    let a = T::new(data.clone());
+    let variance = |x: &[f64]| {
+        let mean = x.iter().sum::<f64>() / x.len() as f64;
+        x.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / x.len() as f64
+    };
+    for (actual, row) in a.row_variances().iter().zip(&data) {
+        assert!((actual - variance(row)).abs() < 1e-12);
+    }
+    for (j, actual) in a.col_variances().iter().enumerate() {
+        let col = data.iter().map(|row| row[j]).collect::<Vec<_>>();
+        assert!((actual - variance(&col)).abs() < 1e-12);
+    }
+}
+
+pub fn test_hadamard_and_division<T: Matrix>() {
+    let a = T::new(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
+    let b = T::new(vec![vec![2.0, 0.5, -1.0], vec![0.25, 2.0, 3.0]]);
+    assert_eq!(
+        a.hadamard_product(&b).get_data(),
+        vec![vec![2.0, 1.0, -3.0], vec![1.0, 10.0, 18.0]]
+    );
+    assert_eq!(
+        a.elementwise_division(&b).get_data(),
+        vec![vec![0.5, 4.0, -3.0], vec![16.0, 2.5, 2.0]]
+    );
+    assert_eq!(
+        a.try_hadamard_product(&T::zeroes(3, 2)).err(),
+        Some(MatrixError::DimensionMismatch {
+            left: (2, 3),
+            right: (3, 2)
+        })
+    );
+    assert!(a.try_elementwise_division(&T::zeroes(2, 2)).is_err());
+}
+
+pub fn test_map_and_zip_map<T: Matrix>() {
+    let a = T::new(vec![vec![1.0, -2.0], vec![3.0, -4.0]]);
+    assert_eq!(
+        a.map(|x| 2.0 * x + 1.0).get_data(),
+        vec![vec![3.0, -3.0], vec![7.0, -7.0]]
+    );
+    let b = T::new(vec![vec![5.0, 6.0], vec![7.0, 8.0]]);
+    assert_eq!(
+        a.zip_map(&b, f64::max).get_data(),
+        vec![vec![5.0, 6.0], vec![7.0, 8.0]]
+    );
+    assert!(a.try_zip_map(&T::zeroes(1, 2), f64::max).is_err());
+
+    // Uneven chunks for the threaded backend, with a captured value
+    let (m, n) = (37, 23);
+    let data = pseudo_random_rows(m, n);
+    let a = T::new(data.clone());
+    let shift = 0.5;
+    let expected = data
+        .iter()
+        .map(|row| row.iter().map(|x| x * x - shift).collect())
+        .collect::<Vec<Vec<f64>>>();
+    assert_eq!(a.map(|x| x * x - shift).get_data(), expected);
+    assert_eq!(a.zip_map(&a, |x, y| x * y - shift).get_data(), expected);
+}
+
+pub fn test_unary_functions<T: Matrix>() {
+    let a = T::new(vec![vec![1.0, 4.0], vec![0.25, 9.0]]);
+    assert_eq!(a.sqrt().get_data(), vec![vec![1.0, 2.0], vec![0.5, 3.0]]);
+    assert_eq!(
+        a.powi(2).get_data(),
+        vec![vec![1.0, 16.0], vec![0.0625, 81.0]]
+    );
+    assert_eq!(
+        a.clamp(0.5, 5.0).get_data(),
+        vec![vec![1.0, 4.0], vec![0.5, 5.0]]
+    );
+    for (x, row) in a.ln().exp().row_iter().zip(rows(&a)) {
+        for (x, expected) in x.iter().zip(row) {
+            assert!((x - expected).abs() < 1e-14);
+        }
+    }
+    let b = T::new(vec![vec![-1.5, 0.0], vec![2.0, -0.0]]);
+    assert_eq!(b.abs().get_data(), vec![vec![1.5, 0.0], vec![2.0, 0.0]]);
+    assert!(b.ln().get(0, 0).is_nan());
+    let clamped = T::new(vec![vec![f64::NAN, -7.0]]).clamp(0.5, 5.0);
+    assert!(clamped.get(0, 0).is_nan());
+    assert_eq!(clamped.get(0, 1), 0.5);
 }
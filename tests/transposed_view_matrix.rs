@@ -1,5 +1,6 @@
 use rmatrix::matrices::transposed_view_matrix::*;
 mod test_functions;
+use rmatrix::Complex;
 use test_functions::*;
 
 #[test]
@@ -41,3 +42,175 @@ fn transposed_view_matrix_test_matrix_multiplication() {
 fn transposed_view_matrix_test_scalar_multiplication() {
     test_scalar_multiplication::<TransposedViewMatrix>();
 }
+
+#[test]
+fn transposed_view_matrix_test_chained_operations() {
+    test_chained_operations::<TransposedViewMatrix>();
+}
+
+#[test]
+fn transposed_view_matrix_test_try_new() {
+    test_try_new::<TransposedViewMatrix>();
+}
+
+#[test]
+fn transposed_view_matrix_test_new_invalid_data() {
+    test_new_invalid_data::<TransposedViewMatrix>();
+}
+
+#[test]
+fn transposed_view_matrix_test_try_zeroes_and_identity() {
+    test_try_zeroes_and_identity::<TransposedViewMatrix>();
+}
+
+#[test]
+fn transposed_view_matrix_test_try_matrix_addition() {
+    test_try_matrix_addition::<TransposedViewMatrix>();
+}
+
+#[test]
+fn transposed_view_matrix_test_try_matrix_subtraction() {
+    test_try_matrix_subtraction::<TransposedViewMatrix>();
+}
+
+#[test]
+fn transposed_view_matrix_test_try_matrix_multiplication() {
+    test_try_matrix_multiplication::<TransposedViewMatrix>();
+}
+
+#[test]
+fn transposed_view_matrix_test_f32_elements() {
+    test_element_type::<f32, TransposedViewMatrix<f32>>();
+}
+
+#[test]
+fn transposed_view_matrix_test_i32_elements() {
+    test_element_type::<i32, TransposedViewMatrix<i32>>();
+}
+
+#[test]
+fn transposed_view_matrix_test_complex_elements() {
+    test_complex_elements::<TransposedViewMatrix<Complex<f64>>>();
+}
+
+#[test]
+fn transposed_view_matrix_test_conjugate_transpose() {
+    test_conjugate_transpose::<TransposedViewMatrix<Complex<f64>>>();
+}
+
+#[test]
+fn transposed_view_matrix_test_operators() {
+    test_operators::<TransposedViewMatrix>();
+}
+
+#[test]
+fn transposed_view_matrix_test_operators_dimension_mismatch() {
+    test_operators_dimension_mismatch::<TransposedViewMatrix>();
+}
+
+#[test]
+fn transposed_view_matrix_test_addition_subtraction_dimension_mismatch() {
+    test_addition_subtraction_dimension_mismatch::<TransposedViewMatrix>();
+}
+
+#[test]
+fn transposed_view_matrix_test_assign_dimension_mismatch() {
+    test_assign_dimension_mismatch::<TransposedViewMatrix>();
+}
+
+#[test]
+#[should_panic(expected = "dimension mismatch")]
+fn transposed_view_matrix_test_gemm_dimension_mismatch() {
+    test_gemm_dimension_mismatch::<TransposedViewMatrix>();
+}
+
+#[test]
+fn transposed_view_matrix_test_element_access() {
+    test_element_access::<TransposedViewMatrix>();
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn transposed_view_matrix_test_index_out_of_bounds() {
+    test_index_out_of_bounds::<TransposedViewMatrix>();
+}
+
+#[test]
+fn transposed_view_matrix_test_data_access() {
+    test_data_access::<TransposedViewMatrix>();
+}
+
+#[test]
+fn transposed_view_matrix_test_transpose() {
+    test_transpose::<TransposedViewMatrix>();
+}
+
+#[test]
+fn transposed_view_matrix_test_transposed_view() {
+    test_transposed_view::<TransposedViewMatrix>();
+}
+
+#[test]
+fn transposed_view_matrix_test_in_place_arithmetic() {
+    test_in_place_arithmetic::<TransposedViewMatrix>();
+}
+
+#[test]
+fn transposed_view_matrix_test_gemm() {
+    test_gemm::<TransposedViewMatrix>();
+}
+
+#[test]
+fn transposed_view_matrix_test_symmetric_eigen() {
+    test_symmetric_eigen::<TransposedViewMatrix>();
+}
+
+#[test]
+fn transposed_view_matrix_test_eigenvalues() {
+    test_eigenvalues::<TransposedViewMatrix>();
+}
+
+#[test]
+fn transposed_view_matrix_test_matvec() {
+    test_matvec::<TransposedViewMatrix>();
+}
+
+#[test]
+fn transposed_view_matrix_test_vecmat() {
+    test_vecmat::<TransposedViewMatrix>();
+}
+
+#[test]
+fn transposed_view_matrix_test_trace_and_sums() {
+    test_trace_and_sums::<TransposedViewMatrix>();
+}
+
+#[test]
+fn transposed_view_matrix_test_norms() {
+    test_norms::<TransposedViewMatrix>();
+}
+
+#[test]
+fn transposed_view_matrix_test_min_max() {
+    test_min_max::<TransposedViewMatrix>();
+}
+
+#[test]
+fn transposed_view_matrix_test_row_col_statistics() {
+    test_row_col_statistics::<TransposedViewMatrix>();
+}
+
+#[test]
+fn transposed_view_matrix_test_hadamard_and_division() {
+    test_hadamard_and_division::<TransposedViewMatrix>();
+}
+
+#[test]
+fn transposed_view_matrix_test_map_and_zip_map() {
+    test_map_and_zip_map::<TransposedViewMatrix>();
+}
+
+#[test]
+fn transposed_view_matrix_test_unary_functions() {
+    test_unary_functions::<TransposedViewMatrix>();
+}
@@ -0,0 +1,149 @@
+use rmatrix::linalg::LinearAlgebra;
+use rmatrix::matrices::basic_matrix::BasicMatrix;
+use rmatrix::matrices::blocked_matrix::BlockedMatrix;
+use rmatrix::matrices::multithread_matrix::MultithreadMatrix;
+use rmatrix::matrices::packed_matrix::PackedMatrix;
+use rmatrix::parallel;
+use rmatrix::tuning::{Autotuner, BlockSizes, TuningConfig, config};
+use rmatrix::{FlatMatrix, Matrix};
+use std::fs;
+
+mod common;
+use common::{assert_close, entries};
+
+/// Block sizes that leave partial blocks at every level, on three threads.
+fn odd_tuning() -> TuningConfig {
+    TuningConfig::default()
+        .with_block_size(3)
+        .with_cholesky_block_size(5)
+        .with_packed_block_sizes(BlockSizes {
+            mc: 5,
+            kc: 7,
+            nc: 9,
+        })
+        .with_threads(3)
+}
+
+fn check_against_basic<M: FlatMatrix + LinearAlgebra>(new: impl Fn(Vec<Vec<f64>>) -> M) {
+    let tuning = odd_tuning();
+    let (a, b, c) = (entries(23, 17, 0), entries(23, 17, 4), entries(17, 29, 2));
+    let (basic_a, basic_b, basic_c) = (
+        BasicMatrix::new(a.clone()),
+        BasicMatrix::new(b.clone()),
+        BasicMatrix::new(c.clone()),
+    );
+    let (m_a, m_b, m_c) = (new(a), new(b), new(c));
+    assert_eq!(m_a.tuning(), tuning);
+
+    // The results keep the tuning of the matrix they came from
+    let sum = m_a.matrix_addition(&m_b);
+    assert_eq!(sum.tuning(), tuning);
+    assert_close(sum.get_data(), basic_a.matrix_addition(&basic_b).get_data());
+    let scaled = m_a.scalar_multiplication(-1.5);
+    assert_eq!(scaled.tuning(), tuning);
+    assert_close(
+        scaled.get_data(),
+        basic_a.scalar_multiplication(-1.5).get_data(),
+    );
+    let product = m_a.matrix_multiplication(&m_c);
+    assert_eq!(product.tuning(), tuning);
+    assert_close(
+        product.get_data(),
+        basic_a.matrix_multiplication(&basic_c).get_data(),
+    );
+    let transpose = m_a.transpose();
+    assert_eq!(transpose.tuning(), tuning);
+    assert_close(transpose.get_data(), basic_a.transpose().get_data());
+
+    // Diagonally dominant, so symmetric positive definite
+    let spd = (0..21usize)
+        .map(|i| {
+            (0..21)
+                .map(|j| 1.0 / (1.0 + i.abs_diff(j) as f64) + if i == j { 21.0 } else { 0.0 })
+                .collect::<Vec<f64>>()
+        })
+        .collect::<Vec<_>>();
+    let l = new(spd.clone()).cholesky().unwrap().into_l();
+    assert_close(
+        l.matrix_multiplication(&l.transpose()).get_data(),
+        spd.clone(),
+    );
+    let lu = new(spd.clone()).lu().unwrap();
+    assert!(lu.determinant() > 0.0);
+}
+
+#[test]
+fn tuning_test_save_and_load() {
+    let dir = std::env::temp_dir();
+    let path = dir.join(format!("rmatrix-tuning-{}.json", std::process::id()));
+
+    let tuned = TuningConfig::default()
+        .with_block_size(16)
+        .with_cholesky_block_size(32)
+        .with_packed_block_sizes(BlockSizes {
+            mc: 48,
+            kc: 128,
+            nc: 1024,
+        })
+        .with_strassen_cutoff(256)
+        .with_threads(2);
+    tuned.save(&path).unwrap();
+    assert_eq!(TuningConfig::load(&path).unwrap(), tuned);
+
+    // Missing fields take their defaults and zero sizes are raised to one
+    fs::write(&path, r#"{"block_size": 0, "threads": null}"#).unwrap();
+    let partial = TuningConfig::load(&path).unwrap();
+    assert_eq!(partial, TuningConfig::default().with_block_size(1));
+    assert_eq!(partial.threads(), None);
+
+    fs::write(&path, "not json").unwrap();
+    assert!(TuningConfig::load(&path).is_err());
+    fs::remove_file(&path).unwrap();
+    assert!(TuningConfig::load(&path).is_err());
+}
+
+#[test]
+fn tuning_test_with_tuning() {
+    check_against_basic(|data| BlockedMatrix::new(data).with_tuning(odd_tuning()));
+    check_against_basic(|data| MultithreadMatrix::new(data).with_tuning(odd_tuning()));
+    check_against_basic(|data| PackedMatrix::new(data).with_tuning(odd_tuning()));
+
+    // Without `with_tuning`, the configuration loaded at startup
+    assert_eq!(
+        BlockedMatrix::new(entries::<f64>(2, 2, 0)).tuning(),
+        config()
+    );
+    assert_eq!(
+        PackedMatrix::new(entries::<f64>(2, 2, 0)).tuning(),
+        config()
+    );
+}
+
+#[test]
+fn tuning_test_autotuner() {
+    let initial = config();
+    let initial_threads = parallel::config().threads();
+
+    let tuner = Autotuner::new()
+        .with_size(24)
+        .with_repeats(1)
+        .with_thread_counts(vec![1, 2])
+        .with_block_sizes(vec![4, 16])
+        .with_cholesky_block_sizes(vec![])
+        .with_strassen_cutoffs(vec![6])
+        .with_packed_block_sizes(vec![BlockSizes {
+            mc: 8,
+            kc: 16,
+            nc: 32,
+        }]);
+    let best = tuner.run();
+    assert!([1, 2].contains(&best.threads().unwrap()));
+    assert!([4, 16].contains(&best.block_size()));
+    assert_eq!(best.cholesky_block_size(), initial.cholesky_block_size());
+    assert_eq!(best.packed_block_sizes().kc, 16);
+    assert_eq!(best.strassen_cutoff(), 6);
+
+    // The candidates are only given to the matrices they are timed on
+    assert_eq!(config(), initial);
+    assert_eq!(parallel::config().threads(), initial_threads);
+}
@@ -0,0 +1,38 @@
+use rmatrix::matrices::blocked_matrix::BlockedMatrix;
+use rmatrix::matrices::packed_matrix::PackedMatrix;
+use rmatrix::tuning::{self, TuningConfig, config};
+use rmatrix::{FlatMatrix, Matrix};
+use std::fs;
+use std::io::ErrorKind;
+
+mod common;
+use common::entries;
+
+/// One test, since the loaded configuration is shared by every test in the binary.
+#[test]
+fn tuning_init_test_load() {
+    let dir = std::env::temp_dir();
+    let path = dir.join(format!("rmatrix-tuning-init-{}.json", std::process::id()));
+
+    // A missing or malformed file is reported and leaves the configuration unset
+    let _ = fs::remove_file(&path);
+    assert_eq!(tuning::init(&path).unwrap_err().kind(), ErrorKind::NotFound);
+    fs::write(&path, "not json").unwrap();
+    assert!(tuning::init(&path).is_err());
+
+    let tuned = TuningConfig::default()
+        .with_block_size(5)
+        .with_strassen_cutoff(32)
+        .with_threads(2);
+    tuned.save(&path).unwrap();
+    assert_eq!(tuning::init(&path).unwrap(), tuned);
+    assert_eq!(config(), tuned);
+    assert_eq!(BlockedMatrix::new(entries::<f64>(2, 2, 0)).tuning(), tuned);
+    assert_eq!(PackedMatrix::new(entries::<f64>(2, 2, 0)).tuning(), tuned);
+
+    // The configuration is fixed once loaded
+    let err = tuning::init(&path).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+    assert_eq!(config(), tuned);
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,36 @@
+use rmatrix::{Givens, Vector};
+
+#[test]
+fn vector_test_level1() {
+    let mut y = Vector::new(vec![1.0, 2.0, -3.0]);
+    let x = Vector::new(vec![2.0, 0.0, 1.0]);
+    assert_eq!(y.dot(&x), -1.0);
+    assert_eq!(y.asum(), 6.0);
+    assert_eq!(y.iamax(), 2);
+    assert_eq!(Vector::new(vec![2.0, -2.0, 1.0]).nrm2(), 3.0);
+
+    y.axpy(-1.0, &x);
+    assert_eq!(y, Vector::new(vec![-1.0, 2.0, -4.0]));
+    y.scal(2.0);
+    assert_eq!(y, Vector::new(vec![-2.0, 4.0, -8.0]));
+    assert_eq!(Vector::<f64>::new(vec![]).iamax(), 0);
+}
+
+#[test]
+#[should_panic(expected = "dimension mismatch: 3x1 and 1x1")]
+fn vector_test_dot_mismatch() {
+    Vector::new(vec![1.0, 2.0, 3.0]).dot(&Vector::new(vec![1.0]));
+}
+
+#[test]
+fn vector_test_givens() {
+    // r takes the sign of the larger entry
+    let (givens, r) = Givens::new(-1.0, 0.5);
+    assert!((r + 1.25_f64.sqrt()).abs() < 1e-15);
+    let mut x = Vector::new(vec![-1.0]);
+    let mut y = Vector::new(vec![0.5]);
+    x.rot(&mut y, givens);
+    assert!((x[0] - r).abs() < 1e-15 && y[0].abs() < 1e-15);
+
+    assert_eq!(Givens::new(0.0, 0.0), (Givens { c: 1.0, s: 0.0 }, 0.0));
+}